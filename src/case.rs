@@ -0,0 +1,100 @@
+//!
+//! Convert names between the cases used for Rust items, ie. `snake_case` for fields
+//! and functions, `UpperCamelCase` for types and variants, and `SCREAMING_SNAKE_CASE`
+//! for constants.
+//!
+//! Names are split into words at any non-alphanumeric character, at a lowercase to
+//! uppercase transition (`userId`) and before the last capital of an acronym
+//! (`HTTPServer`). Digits stay attached to the word they follow (`sha256Sum`).
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! assert_eq!(to_snake_case("HTTPServerURL"), "http_server_url");
+//! assert_eq!(to_upper_camel_case("user-id"), "UserId");
+//! assert_eq!(to_screaming_snake_case("maxRetries2"), "MAX_RETRIES2");
+//! assert_eq!(Case::UpperCamel.convert("über_größe"), "ÜberGröße");
+//! ```
+
+/// A naming convention for Rust identifiers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Case {
+    /// `snake_case`, used for fields, functions, parameters and modules
+    Snake,
+    /// `UpperCamelCase`, used for types, traits and enum variants
+    UpperCamel,
+    /// `SCREAMING_SNAKE_CASE`, used for constants and statics
+    ScreamingSnake,
+}
+
+impl Case {
+    /// Convert `name` into this case
+    pub fn convert(self, name: &str) -> String {
+        match self {
+            Case::Snake => to_snake_case(name),
+            Case::UpperCamel => to_upper_camel_case(name),
+            Case::ScreamingSnake => to_screaming_snake_case(name),
+        }
+    }
+}
+
+/// Split `name` into its words
+fn words(name: &str) -> Vec<String> {
+    let mut words = vec![];
+    for part in name.split(|c: char| !c.is_alphanumeric()) {
+        let chars = part.chars().collect::<Vec<char>>();
+        let mut word = String::new();
+        for (idx, &c) in chars.iter().enumerate() {
+            if idx > 0 && c.is_uppercase() {
+                let prev = chars[idx - 1];
+                let next_is_lower = chars.get(idx + 1).is_some_and(|n| n.is_lowercase());
+                // `userId` -> `user` `Id`, `HTTPServer` -> `HTTP` `Server`
+                if !prev.is_uppercase() || next_is_lower {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            word.push(c);
+        }
+        words.push(word);
+    }
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+/// Convert `name` into `snake_case`
+pub fn to_snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+/// Convert `name` into `SCREAMING_SNAKE_CASE`
+pub fn to_screaming_snake_case(name: &str) -> String {
+    words(name)
+        .iter()
+        .map(|w| w.to_uppercase())
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+/// Convert `name` into `UpperCamelCase`. Words which both end and start with a
+/// digit are joined with `_`, to keep `v1_2` from becoming `V12`.
+pub fn to_upper_camel_case(name: &str) -> String {
+    let mut result = String::new();
+    for word in words(name) {
+        let ends_with_digit = result.chars().last().is_some_and(|c| c.is_numeric());
+        if ends_with_digit && word.chars().next().is_some_and(|c| c.is_numeric()) {
+            result.push('_');
+        }
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    result
+}
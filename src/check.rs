@@ -0,0 +1,267 @@
+//!
+//! Syntax check generated source code with `syn`, enabled with the `check` feature.
+//!
+//! Most objects accept raw source code for parts like function bodies, types and
+//! `Variant` inners, so a typo in any of those produces invalid Rust. `generate_checked`
+//! parses the rendered source as the matching `syn` type and reports where it broke.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let s = Struct::new("Foo")
+//!     .add_field(Field::new("bar", "Vec<u8"))
+//!     .to_owned();
+//!
+//! let err = s.generate_checked().unwrap_err();
+//! assert!(err.snippet().contains("bar: Vec<u8,"));
+//! assert!(err.to_string().contains(&format!("line {}", err.line())));
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use syn::parse::{ParseStream, Parser};
+
+use crate::*;
+
+/// Lines of context shown either side of the offending line in `CheckError::snippet`
+const CONTEXT_LINES: usize = 2;
+
+/// Error returned when generated source code isn't valid Rust.
+#[derive(Debug, Clone)]
+pub struct CheckError {
+    message: String,
+    line: usize,
+    column: usize,
+    snippet: String,
+    src_code: String,
+}
+
+impl CheckError {
+    fn new(err: &syn::Error, src_code: String) -> Self {
+        let lines = src_code.lines().collect::<Vec<&str>>();
+        let (line, column) = match err.span().start() {
+            // Errors at the end of the input have no location, point past the last line.
+            start if start.line == 0 => {
+                let last_line = lines.last().map_or(0, |l| l.chars().count());
+                (lines.len().max(1), last_line)
+            }
+            start => (start.line, start.column),
+        };
+        let first = line.saturating_sub(CONTEXT_LINES + 1);
+        let last = (line + CONTEXT_LINES).min(lines.len());
+        let mut snippet = String::new();
+        for (idx, src_line) in lines.iter().enumerate().take(last).skip(first) {
+            snippet.push_str(&format!("{:>4} | {}\n", idx + 1, src_line));
+            if idx + 1 == line {
+                snippet.push_str(&format!("     | {}^\n", " ".repeat(column)));
+            }
+        }
+        Self {
+            message: err.to_string(),
+            line,
+            column,
+            snippet,
+            src_code,
+        }
+    }
+
+    /// The error reported by the parser
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// Line of the generated source code where parsing failed, starting from 1
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Column of the generated source code where parsing failed, starting from 0
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// The offending line, with surrounding lines for context
+    pub fn snippet(&self) -> &str {
+        self.snippet.as_str()
+    }
+
+    /// The complete generated source code which failed to parse
+    pub fn src_code(&self) -> &str {
+        self.src_code.as_str()
+    }
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Generated invalid Rust source code at line {}, column {}: {}\n{}",
+            self.line, self.column, self.message, self.snippet
+        )
+    }
+}
+
+impl Error for CheckError {}
+
+/// Parse `src_code` as `T`, returning it unchanged if it's valid.
+fn check<T: syn::parse::Parse>(src_code: String) -> Result<String, CheckError> {
+    check_with(T::parse, src_code)
+}
+
+/// Parse all of `src_code` with `parser`, returning it unchanged if it's valid.
+fn check_with(parser: impl Parser, src_code: String) -> Result<String, CheckError> {
+    match parser.parse_str(&src_code) {
+        Ok(_) => Ok(src_code),
+        Err(err) => Err(CheckError::new(&err, src_code)),
+    }
+}
+
+/// Parse a named field along with its trailing comma
+fn field(input: ParseStream) -> syn::Result<()> {
+    syn::Field::parse_named(input)?;
+    input.parse::<Option<syn::Token![,]>>()?;
+    Ok(())
+}
+
+/// Parse a single `macro_rules!` rule, ie. `($a:expr) => { $a };`
+fn macro_rule(input: ParseStream) -> syn::Result<()> {
+    input.parse::<proc_macro2::Group>()?;
+    input.parse::<syn::Token![=>]>()?;
+    input.parse::<proc_macro2::Group>()?;
+    input.parse::<Option<syn::Token![;]>>()?;
+    Ok(())
+}
+
+/// Parse generics along with their where clause, ie. `<T> where T: Clone,`
+fn generics(input: ParseStream) -> syn::Result<()> {
+    input.parse::<syn::Generics>()?;
+    input.parse::<Option<syn::WhereClause>>()?;
+    Ok(())
+}
+
+/// Parse a function signature with its docs, attributes and visibility, optionally
+/// followed by `;` as in a trait or extern block
+fn function_signature(input: ParseStream) -> syn::Result<()> {
+    syn::Attribute::parse_outer(input)?;
+    input.parse::<syn::Visibility>()?;
+    input.parse::<syn::Signature>()?;
+    input.parse::<Option<syn::Token![;]>>()?;
+    Ok(())
+}
+
+/// Parse a function with a body, which unlike a foreign function can't be variadic
+fn function(input: ParseStream) -> syn::Result<()> {
+    let item = input.parse::<syn::ItemFn>()?;
+    match item.sig.variadic {
+        Some(variadic) => Err(syn::Error::new_spanned(
+            variadic,
+            "only foreign functions may be variadic",
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Parse the statements of a function body, preceded by its inner attributes
+fn function_body(input: ParseStream) -> syn::Result<()> {
+    syn::Attribute::parse_inner(input)?;
+    syn::Block::parse_within(input)?;
+    Ok(())
+}
+
+/// Parse comments only, ie. a file header, which may include doc comments
+fn comments(input: ParseStream) -> syn::Result<()> {
+    let file = input.parse::<syn::File>()?;
+    match file.items.first() {
+        Some(item) => Err(syn::Error::new_spanned(item, "expected only comments")),
+        None => Ok(()),
+    }
+}
+
+/// Trait implemented for elements which can verify their generated source code.
+pub trait CheckedSrcCode: SrcCode {
+    /// Generate the source code, returning an error if it isn't valid Rust.
+    fn generate_checked(&self) -> Result<String, CheckError>;
+}
+
+macro_rules! impl_checked_src_code {
+    ($($ty:ty => $syn_ty:ty),* $(,)?) => {
+        $(
+            impl CheckedSrcCode for $ty {
+                fn generate_checked(&self) -> Result<String, CheckError> {
+                    check::<$syn_ty>(self.generate())
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_checked_src_code_with {
+    ($($ty:ty => $parser:expr),* $(,)?) => {
+        $(
+            impl CheckedSrcCode for $ty {
+                fn generate_checked(&self) -> Result<String, CheckError> {
+                    check_with($parser, self.generate())
+                }
+            }
+        )*
+    };
+}
+
+impl_checked_src_code!(
+    AssociatedTypeDeclaration => syn::TraitItemType,
+    AssociatedTypeDefinition => syn::ImplItemType,
+    Block => syn::Block,
+    Cfg => syn::Meta,
+    Const => syn::ItemConst,
+    Enum => syn::ItemEnum,
+    Expr => syn::Expr,
+    ExternBlock => syn::ItemForeignMod,
+    ForeignStatic => syn::ForeignItemStatic,
+    ForeignType => syn::ForeignItemType,
+    Impl => syn::ItemImpl,
+    MacroInvocation => syn::ItemMacro,
+    MacroRules => syn::ItemMacro,
+    MatchArm => syn::Arm,
+    Meta => syn::Meta,
+    MetaList => syn::Meta,
+    Module => syn::ItemMod,
+    Parameter => syn::FnArg,
+    Static => syn::ItemStatic,
+    Stmt => syn::Stmt,
+    Struct => syn::ItemStruct,
+    Trait => syn::ItemTrait,
+    TypeAlias => syn::ItemType,
+    Union => syn::ItemUnion,
+    Variant => syn::Variant,
+);
+
+impl_checked_src_code_with!(
+    Comment => comments,
+    Field => field,
+    FileHeader => comments,
+    Function => function,
+    FunctionBody => function_body,
+    FunctionSignature => function_signature,
+    MacroRule => macro_rule,
+    Vec<Generic> => generics,
+);
+
+impl CheckedSrcCode for Attribute {
+    fn generate_checked(&self) -> Result<String, CheckError> {
+        match self {
+            Attribute::ItemAttr(_) => check_with(syn::Attribute::parse_outer, self.generate()),
+            Attribute::ScopeAttr(_) => check_with(syn::Attribute::parse_inner, self.generate()),
+        }
+    }
+}
+
+impl Module {
+    /// Render this module as a source file, see `Module::generate_file`, returning
+    /// an error if it isn't valid Rust.
+    pub fn generate_file_checked(&self) -> Result<String, CheckError> {
+        check::<syn::File>(self.generate_file())
+    }
+}
@@ -0,0 +1,727 @@
+//!
+//! Compare two versions of a module tree, ie. the code generated before and after a
+//! schema change, and list what changed at the level of items rather than lines.
+//!
+//! Items are matched by name, impl blocks by the type and trait they implement, and
+//! compared member by member: fields, variants, functions and associated types. Items
+//! sharing a name, ie. several inherent `impl User` blocks, are paired in order.
+//! Comments and the order of items aren't considered changes.
+//!
+//! `use` statements (`pub use` re-exports included), extern blocks, macro invocations
+//! and raw items aren't compared: they aren't named items, so neither the diff nor
+//! `Module::semver_check` will report changes to them.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let old = Module::new("api")
+//!     .with_struct(Struct::new("User").with_field(Field::new("id", "u32")))
+//!     .with_enum(Enum::new("Role").with_variant(Variant::new("Admin")).with_variant(Variant::new("Guest")));
+//! let new = Module::new("api")
+//!     .with_struct(Struct::new("User").with_field(Field::new("id", "u64")).with_field(Field::new("name", "String")))
+//!     .with_enum(Enum::new("Role").with_variant(Variant::new("Admin")))
+//!     .with_submodule(Module::new("auth"));
+//!
+//! let diff = old.diff(&new);
+//! assert_eq!(
+//!     diff.to_string(),
+//!     "field `User::id` type changed from `u32` to `u64`
+//! added field `User::name`
+//! removed variant `Role::Guest`
+//! added mod `auth`
+//! "
+//! );
+//! assert_eq!(diff.changes()[0].detail(), &ChangeKind::TypeChanged { from: "u32".into(), to: "u64".into() });
+//! ```
+
+use std::fmt;
+
+use crate::merge::{impl_name, pair_named};
+use crate::*;
+
+/// What changed about an item
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ChangeKind {
+    /// The item was added
+    Added,
+    /// The item was removed
+    Removed,
+    /// The visibility changed, or if a macro is exported
+    VisibilityChanged {
+        /// If the item is now public
+        is_pub: bool,
+    },
+    /// The type of a field, const, static or type alias, or the fields of a variant
+    TypeChanged {
+        /// The old type
+        from: String,
+        /// The new type
+        to: String,
+    },
+    /// The value of a const or static
+    ValueChanged {
+        /// The old value
+        from: String,
+        /// The new value
+        to: String,
+    },
+    /// The signature of a function, ie. its parameters or return type
+    SignatureChanged {
+        /// The old signature
+        from: String,
+        /// The new signature
+        to: String,
+    },
+    /// The generics of an item or their bounds
+    GenericsChanged {
+        /// The old generics, empty if there were none
+        from: String,
+        /// The new generics, empty if there are none
+        to: String,
+    },
+    /// The derived traits
+    DerivesChanged {
+        /// The traits derived now
+        added: Vec<String>,
+        /// The traits no longer derived
+        removed: Vec<String>,
+    },
+    /// The attributes other than derives
+    AttributesChanged {
+        /// The attributes added
+        added: Vec<String>,
+        /// The attributes removed
+        removed: Vec<String>,
+    },
+    /// The documentation
+    DocsChanged,
+    /// The body of a function or the rules of a macro
+    BodyChanged,
+    /// A trait function gained or lost its default implementation
+    DefaultChanged {
+        /// If the function now has a default implementation
+        has_default: bool,
+    },
+}
+
+/// A change to a single item
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    path: Vec<String>,
+    kind: ItemKind,
+    name: String,
+    detail: ChangeKind,
+}
+
+impl Change {
+    /// Names of the submodules containing the item, relative to the compared module
+    pub fn path(&self) -> &[String] {
+        self.path.as_slice()
+    }
+
+    /// Kind of the item
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Name of the item, qualified by its parent for members, ie. `User::id` for a field
+    /// or `<User as Display>::fmt` for a function of a trait impl
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// What changed
+    pub fn detail(&self) -> &ChangeKind {
+        &self.detail
+    }
+}
+
+/// `added `A`, `B`, removed `C``
+fn describe_sets(added: &[String], removed: &[String]) -> String {
+    let list = |items: &[String]| {
+        items
+            .iter()
+            .map(|i| format!("`{}`", i))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    match (added.is_empty(), removed.is_empty()) {
+        (false, true) => format!("added {}", list(added)),
+        (true, false) => format!("removed {}", list(removed)),
+        _ => format!("added {}, removed {}", list(added), list(removed)),
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let item = format!(
+            "{} `{}{}`",
+            self.kind,
+            self.path
+                .iter()
+                .map(|m| format!("{}::", m))
+                .collect::<String>(),
+            self.name
+        );
+        match &self.detail {
+            ChangeKind::Added => write!(f, "added {}", item),
+            ChangeKind::Removed => write!(f, "removed {}", item),
+            ChangeKind::VisibilityChanged { is_pub: true } => write!(f, "{} is now public", item),
+            ChangeKind::VisibilityChanged { is_pub: false } => {
+                write!(f, "{} is no longer public", item)
+            }
+            ChangeKind::TypeChanged { from, to } => {
+                write!(f, "{} type changed from `{}` to `{}`", item, from, to)
+            }
+            ChangeKind::ValueChanged { from, to } => {
+                write!(f, "{} value changed from `{}` to `{}`", item, from, to)
+            }
+            ChangeKind::SignatureChanged { from, to } => {
+                write!(f, "{} signature changed from `{}` to `{}`", item, from, to)
+            }
+            ChangeKind::GenericsChanged { from, to } => {
+                write!(f, "{} generics changed from `{}` to `{}`", item, from, to)
+            }
+            ChangeKind::DerivesChanged { added, removed } => {
+                write!(
+                    f,
+                    "{} derives changed: {}",
+                    item,
+                    describe_sets(added, removed)
+                )
+            }
+            ChangeKind::AttributesChanged { added, removed } => write!(
+                f,
+                "{} attributes changed: {}",
+                item,
+                describe_sets(added, removed)
+            ),
+            ChangeKind::DocsChanged => write!(f, "{} docs changed", item),
+            ChangeKind::BodyChanged => write!(f, "{} body changed", item),
+            ChangeKind::DefaultChanged { has_default: true } => {
+                write!(f, "{} now has a default implementation", item)
+            }
+            ChangeKind::DefaultChanged { has_default: false } => {
+                write!(f, "{} no longer has a default implementation", item)
+            }
+        }
+    }
+}
+
+/// The changes between two versions of a module tree. Displays as a report with one
+/// change per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    /// The changes, in the order items are rendered with submodules last
+    pub fn changes(&self) -> &[Change] {
+        self.changes.as_slice()
+    }
+
+    /// If nothing changed
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+impl Module {
+    /// The changes from this module to `new`
+    pub fn diff(&self, new: &Module) -> Diff {
+        let mut differ = Differ::default();
+        differ.module(self.name(), self, new);
+        differ.module_items(self, new);
+        Diff {
+            changes: differ.changes,
+        }
+    }
+}
+
+/// `<T: Clone, S>`, empty without generics
+fn generics_src(generics: &[Generic]) -> String {
+    if generics.is_empty() {
+        return String::new();
+    }
+    let generics = generics
+        .iter()
+        .map(|g| {
+            if g.traits().is_empty() {
+                g.name().to_string()
+            } else {
+                format!("{}: {}", g.name(), g.traits().join(" + "))
+            }
+        })
+        .collect::<Vec<String>>();
+    format!("<{}>", generics.join(", "))
+}
+
+/// `async fn name<T>(a: A, ...) -> R`, without visibility, attributes or docs
+fn signature_src(sig: &FunctionSignature) -> String {
+    let mut parameters = sig
+        .parameters()
+        .iter()
+        .map(|p| format!("{}: {}", p.name(), p.ty()))
+        .collect::<Vec<String>>();
+    if sig.is_variadic() {
+        parameters.push("...".to_string());
+    }
+    format!(
+        "{}fn {}{}({}){}",
+        if sig.is_async() { "async " } else { "" },
+        sig.name(),
+        generics_src(sig.generics()),
+        parameters.join(", "),
+        sig.return_ty()
+            .map(|ty| format!(" -> {}", ty))
+            .unwrap_or_default()
+    )
+}
+
+/// Name of a function within an impl block, ie. `<User as Display>::fmt`
+fn impl_member(ipl: &Impl, member: &str) -> String {
+    match ipl.impl_trait() {
+        Some(tr8t) => format!("<{} as {}>::{}", ipl.obj_name(), tr8t.name(), member),
+        None => format!("{}::{}", ipl.obj_name(), member),
+    }
+}
+
+/// A function of a trait, either required or provided
+struct TraitMember<'a> {
+    name: String,
+    signature: &'a FunctionSignature,
+    body: Option<&'a [String]>,
+}
+
+fn trait_members(tr8t: &Trait) -> Vec<TraitMember<'_>> {
+    let required = tr8t.signatures().iter().map(|sig| TraitMember {
+        name: format!("{}::{}", tr8t.name(), sig.name()),
+        signature: sig,
+        body: None,
+    });
+    let provided = tr8t.functions().iter().map(|func| TraitMember {
+        name: format!("{}::{}", tr8t.name(), func.name()),
+        signature: func.signature(),
+        body: Some(func.body()),
+    });
+    required.chain(provided).collect()
+}
+
+#[derive(Default)]
+struct Differ {
+    path: Vec<String>,
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, kind: ItemKind, name: &str, detail: ChangeKind) {
+        self.changes.push(Change {
+            path: self.path.clone(),
+            kind,
+            name: name.to_string(),
+            detail,
+        });
+    }
+
+    /// Match the items of `old` and `new` by name, comparing those in both with
+    /// `compare`. Items sharing a name are paired in order. Removed items are listed
+    /// before added ones.
+    fn items<T>(
+        &mut self,
+        kind: ItemKind,
+        old: &[T],
+        new: &[T],
+        name: impl Fn(&T) -> String,
+        compare: fn(&mut Self, &str, &T, &T),
+    ) {
+        let pairs = pair_named(old, new, &name);
+        let mut paired: Vec<Option<&T>> = old.iter().map(|_| None).collect();
+        for (idx, new_item) in &pairs {
+            if let Some(idx) = idx {
+                paired[*idx] = Some(new_item);
+            }
+        }
+        for (old_item, new_item) in old.iter().zip(paired) {
+            let item_name = name(old_item);
+            match new_item {
+                Some(new_item) => compare(self, &item_name, old_item, new_item),
+                None => self.push(kind, &item_name, ChangeKind::Removed),
+            }
+        }
+        for (_, new_item) in pairs.iter().filter(|(idx, _)| idx.is_none()) {
+            self.push(kind, &name(new_item), ChangeKind::Added);
+        }
+    }
+
+    fn visibility(&mut self, kind: ItemKind, name: &str, old: bool, new: bool) {
+        if old != new {
+            self.push(kind, name, ChangeKind::VisibilityChanged { is_pub: new });
+        }
+    }
+
+    fn ty(&mut self, kind: ItemKind, name: &str, old: &str, new: &str) {
+        if old != new {
+            let detail = ChangeKind::TypeChanged {
+                from: old.to_string(),
+                to: new.to_string(),
+            };
+            self.push(kind, name, detail);
+        }
+    }
+
+    fn generics(&mut self, kind: ItemKind, name: &str, old: &[Generic], new: &[Generic]) {
+        if old != new {
+            let detail = ChangeKind::GenericsChanged {
+                from: generics_src(old),
+                to: generics_src(new),
+            };
+            self.push(kind, name, detail);
+        }
+    }
+
+    fn docs(&mut self, kind: ItemKind, name: &str, old: &[String], new: &[String]) {
+        if old != new {
+            self.push(kind, name, ChangeKind::DocsChanged);
+        }
+    }
+
+    fn attributes(&mut self, kind: ItemKind, name: &str, old: &[Attribute], new: &[Attribute]) {
+        let derives = |attrs: &[Attribute]| {
+            attrs
+                .iter()
+                .flat_map(|a| a.derives())
+                .collect::<Vec<String>>()
+        };
+        let others = |attrs: &[Attribute]| {
+            attrs
+                .iter()
+                .filter(|a| a.derives().is_empty())
+                .map(|a| a.generate())
+                .collect::<Vec<String>>()
+        };
+        let difference = |a: &[String], b: &[String]| {
+            a.iter()
+                .filter(|x| !b.contains(x))
+                .cloned()
+                .collect::<Vec<String>>()
+        };
+
+        let (old_derives, new_derives) = (derives(old), derives(new));
+        let added = difference(&new_derives, &old_derives);
+        let removed = difference(&old_derives, &new_derives);
+        if !added.is_empty() || !removed.is_empty() {
+            self.push(kind, name, ChangeKind::DerivesChanged { added, removed });
+        }
+
+        let (old_others, new_others) = (others(old), others(new));
+        let added = difference(&new_others, &old_others);
+        let removed = difference(&old_others, &new_others);
+        if !added.is_empty() || !removed.is_empty() {
+            self.push(kind, name, ChangeKind::AttributesChanged { added, removed });
+        }
+    }
+
+    fn module(&mut self, name: &str, old: &Module, new: &Module) {
+        self.visibility(ItemKind::Module, name, old.is_pub(), new.is_pub());
+        self.attributes(ItemKind::Module, name, old.attributes(), new.attributes());
+        self.docs(ItemKind::Module, name, old.docs(), new.docs());
+    }
+
+    fn module_items(&mut self, old: &Module, new: &Module) {
+        self.items(
+            ItemKind::Macro,
+            old.macros(),
+            new.macros(),
+            |m| m.name().to_string(),
+            Self::macro_rules,
+        );
+        self.items(
+            ItemKind::Const,
+            old.consts(),
+            new.consts(),
+            |c| c.name().to_string(),
+            Self::constant,
+        );
+        self.items(
+            ItemKind::Static,
+            old.statics(),
+            new.statics(),
+            |s| s.name().to_string(),
+            Self::stat1c,
+        );
+        self.items(
+            ItemKind::TypeAlias,
+            old.type_aliases(),
+            new.type_aliases(),
+            |t| t.name().to_string(),
+            Self::type_alias,
+        );
+        self.items(
+            ItemKind::Trait,
+            old.traits(),
+            new.traits(),
+            |t| t.name().to_string(),
+            Self::tr8t,
+        );
+        self.items(
+            ItemKind::Function,
+            old.functions(),
+            new.functions(),
+            |f| f.name().to_string(),
+            |differ, name, old, new| differ.function(name, old, new),
+        );
+        self.items(
+            ItemKind::Struct,
+            old.structs(),
+            new.structs(),
+            |s| s.name().to_string(),
+            Self::stct,
+        );
+        self.items(
+            ItemKind::Impl,
+            old.impls(),
+            new.impls(),
+            impl_name,
+            Self::ipl,
+        );
+        self.items(
+            ItemKind::Enum,
+            old.enums(),
+            new.enums(),
+            |e| e.name().to_string(),
+            Self::enumm,
+        );
+        self.items(
+            ItemKind::Union,
+            old.unions(),
+            new.unions(),
+            |u| u.name().to_string(),
+            Self::union,
+        );
+        self.items(
+            ItemKind::Module,
+            &old.submodules(),
+            &new.submodules(),
+            |m| m.name().to_string(),
+            |differ, name, old, new| {
+                differ.module(name, old, new);
+                differ.path.push(name.to_string());
+                differ.module_items(old, new);
+                differ.path.pop();
+            },
+        );
+    }
+
+    fn macro_rules(&mut self, name: &str, old: &MacroRules, new: &MacroRules) {
+        let kind = ItemKind::Macro;
+        self.visibility(kind, name, old.is_exported(), new.is_exported());
+        if old.rules() != new.rules() {
+            self.push(kind, name, ChangeKind::BodyChanged);
+        }
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn constant(&mut self, name: &str, old: &Const, new: &Const) {
+        let kind = ItemKind::Const;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.ty(kind, name, old.ty(), new.ty());
+        if old.value() != new.value() {
+            let detail = ChangeKind::ValueChanged {
+                from: old.value().to_string(),
+                to: new.value().to_string(),
+            };
+            self.push(kind, name, detail);
+        }
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn stat1c(&mut self, name: &str, old: &Static, new: &Static) {
+        let kind = ItemKind::Static;
+        let ty = |s: &Static| format!("{}{}", if s.is_mut() { "mut " } else { "" }, s.ty());
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.ty(kind, name, &ty(old), &ty(new));
+        if old.value() != new.value() {
+            let detail = ChangeKind::ValueChanged {
+                from: old.value().to_string(),
+                to: new.value().to_string(),
+            };
+            self.push(kind, name, detail);
+        }
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn type_alias(&mut self, name: &str, old: &TypeAlias, new: &TypeAlias) {
+        let kind = ItemKind::TypeAlias;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.generics(kind, name, old.generics(), new.generics());
+        self.ty(kind, name, old.ty(), new.ty());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn signature(&mut self, name: &str, old: &FunctionSignature, new: &FunctionSignature) {
+        let kind = ItemKind::Function;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        let (old_src, new_src) = (signature_src(old), signature_src(new));
+        if old_src != new_src {
+            let detail = ChangeKind::SignatureChanged {
+                from: old_src,
+                to: new_src,
+            };
+            self.push(kind, name, detail);
+        }
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn function(&mut self, name: &str, old: &Function, new: &Function) {
+        self.signature(name, old.signature(), new.signature());
+        if old.body() != new.body() {
+            self.push(ItemKind::Function, name, ChangeKind::BodyChanged);
+        }
+    }
+
+    fn tr8t(&mut self, name: &str, old: &Trait, new: &Trait) {
+        let kind = ItemKind::Trait;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.generics(kind, name, old.generics(), new.generics());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+        self.items(
+            ItemKind::AssociatedType,
+            old.associated_types(),
+            new.associated_types(),
+            |t| format!("{}::{}", name, t.name()),
+            |differ, name, old, new| {
+                let kind = ItemKind::AssociatedType;
+                differ.ty(
+                    kind,
+                    name,
+                    &old.traits().join(" + "),
+                    &new.traits().join(" + "),
+                );
+                differ.attributes(kind, name, old.attributes(), new.attributes());
+            },
+        );
+        self.items(
+            ItemKind::Function,
+            &trait_members(old),
+            &trait_members(new),
+            |m| m.name.clone(),
+            |differ, name, old, new| {
+                differ.signature(name, old.signature, new.signature);
+                match (old.body, new.body) {
+                    (Some(old_body), Some(new_body)) if old_body != new_body => {
+                        differ.push(ItemKind::Function, name, ChangeKind::BodyChanged)
+                    }
+                    (Some(_), None) | (None, Some(_)) => {
+                        let detail = ChangeKind::DefaultChanged {
+                            has_default: new.body.is_some(),
+                        };
+                        differ.push(ItemKind::Function, name, detail)
+                    }
+                    _ => (),
+                }
+            },
+        );
+    }
+
+    fn field(&mut self, name: &str, old: &Field, new: &Field) {
+        let kind = ItemKind::Field;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.ty(kind, name, old.ty(), new.ty());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+    }
+
+    fn stct(&mut self, name: &str, old: &Struct, new: &Struct) {
+        let kind = ItemKind::Struct;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.generics(kind, name, old.generics(), new.generics());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+        self.items(
+            ItemKind::Field,
+            old.fields(),
+            new.fields(),
+            |f| format!("{}::{}", name, f.name()),
+            Self::field,
+        );
+    }
+
+    fn union(&mut self, name: &str, old: &Union, new: &Union) {
+        let kind = ItemKind::Union;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.generics(kind, name, old.generics(), new.generics());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+        self.items(
+            ItemKind::Field,
+            old.fields(),
+            new.fields(),
+            |f| format!("{}::{}", name, f.name()),
+            Self::field,
+        );
+    }
+
+    fn enumm(&mut self, name: &str, old: &Enum, new: &Enum) {
+        let kind = ItemKind::Enum;
+        self.visibility(kind, name, old.is_pub(), new.is_pub());
+        self.generics(kind, name, old.generics(), new.generics());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+        self.items(
+            ItemKind::Variant,
+            old.variants(),
+            new.variants(),
+            |v| format!("{}::{}", name, v.name()),
+            |differ, name, old, new| {
+                let kind = ItemKind::Variant;
+                let inner = |v: &Variant| v.inner().unwrap_or_default().to_string();
+                differ.ty(kind, name, &inner(old), &inner(new));
+                differ.attributes(kind, name, old.attributes(), new.attributes());
+                differ.docs(kind, name, old.docs(), new.docs());
+            },
+        );
+    }
+
+    fn ipl(&mut self, name: &str, old: &Impl, new: &Impl) {
+        let kind = ItemKind::Impl;
+        self.generics(kind, name, old.generics(), new.generics());
+        self.attributes(kind, name, old.attributes(), new.attributes());
+        self.docs(kind, name, old.docs(), new.docs());
+        self.items(
+            ItemKind::AssociatedType,
+            old.associated_types(),
+            new.associated_types(),
+            |t| impl_member(old, t.name()),
+            |differ, name, old, new| {
+                let kind = ItemKind::AssociatedType;
+                differ.ty(kind, name, old.implementer(), new.implementer());
+                differ.attributes(kind, name, old.attributes(), new.attributes());
+            },
+        );
+        self.items(
+            ItemKind::Function,
+            old.functions(),
+            new.functions(),
+            |f| impl_member(old, f.name()),
+            |differ, name, old, new| differ.function(name, old, new),
+        );
+    }
+}
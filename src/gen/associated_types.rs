@@ -10,11 +10,12 @@ use crate::traits::SrcCode;
 use crate::{internal, Attribute, SrcCodeVec};
 
 /// Represent the declaration of a associated type in a trait
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AssociatedTypeDeclaration {
     name: String,
     traits: Vec<String>,
     attributes: Vec<Attribute>,
+    docs: Vec<String>,
 }
 
 impl AssociatedTypeDeclaration {
@@ -25,6 +26,22 @@ impl AssociatedTypeDeclaration {
             ..Self::default()
         }
     }
+    /// The name of this associated type
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    /// The trait bounds of this associated type
+    pub fn traits(&self) -> &[String] {
+        self.traits.as_slice()
+    }
+    /// The attributes of this associated type
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+    /// The doc comments of this associated type
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
 }
 
 impl internal::Attributes for AssociatedTypeDeclaration {
@@ -33,6 +50,12 @@ impl internal::Attributes for AssociatedTypeDeclaration {
     }
 }
 
+impl internal::Docs for AssociatedTypeDeclaration {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl internal::TraitBounds for AssociatedTypeDeclaration {
     fn trait_bounds_mut(&mut self) -> &mut Vec<String> {
         &mut self.traits
@@ -42,6 +65,8 @@ impl internal::TraitBounds for AssociatedTypeDeclaration {
 impl SrcCode for AssociatedTypeDeclaration {
     fn generate(&self) -> String {
         let template = r#"
+        {{ self.docs | join(sep="
+        ") }}
         {{ attributes | join(sep="
         ") }}
         type {{ self.name }}{% if has_traits %}: {{ self.traits | join(sep=" + ") }}{% endif %};
@@ -55,11 +80,12 @@ impl SrcCode for AssociatedTypeDeclaration {
 }
 
 /// Represent the definition of a associated type in a trait implementation
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct AssociatedTypeDefinition {
     name: String,
     implementer: String,
     attributes: Vec<Attribute>,
+    docs: Vec<String>,
 }
 
 impl AssociatedTypeDefinition {
@@ -71,6 +97,22 @@ impl AssociatedTypeDefinition {
             ..Self::default()
         }
     }
+    /// The name of this associated type
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    /// The type assigned to this associated type
+    pub fn implementer(&self) -> &str {
+        self.implementer.as_str()
+    }
+    /// The attributes of this associated type
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+    /// The doc comments of this associated type
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
 }
 
 impl internal::Attributes for AssociatedTypeDefinition {
@@ -79,9 +121,17 @@ impl internal::Attributes for AssociatedTypeDefinition {
     }
 }
 
+impl internal::Docs for AssociatedTypeDefinition {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl SrcCode for AssociatedTypeDefinition {
     fn generate(&self) -> String {
         let template = r#"
+        {{ self.docs | join(sep="
+        ") }}
         {{ attributes | join(sep="
         ") }}
         type {{ self.name }} = {{ self.implementer }};
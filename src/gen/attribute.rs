@@ -13,12 +13,35 @@
 //! let expected = "#![be_cool]";
 //! assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code))
 //! ```
+//!
+//! Attributes can also be built from structured parts, and existing derives on an
+//! item are merged rather than repeated.
+//!
+//! ```
+//! use proffer::*;
+//!
+//! let s = Struct::new("Foo")
+//!     .add_attribute(Attribute::derive(["Debug", "Clone"]))
+//!     .add_attribute(Attribute::cfg(Cfg::all([Cfg::feature("serde"), Cfg::not(Cfg::option("test"))])))
+//!     .add_attribute(Attribute::derive(["Clone", "PartialEq"]))
+//!     .add_attribute(Attribute::repr(["C"]))
+//!     .to_owned();
+//! let expected = r#"
+//!     #[derive(Debug, Clone, PartialEq)]
+//!     #[cfg(all(feature = "serde", not(test)))]
+//!     #[repr(C)]
+//!     struct Foo
+//!     {
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&s.generate()));
+//! ```
 
 use crate::SrcCode;
 use serde::{Deserialize, Serialize};
 
 /// Represents a single Rust attribute to a module, function, etc.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum Attribute {
     /// Attribute representing an attribute for an item. ie. `#[foo(bar)]`
     ItemAttr(String),
@@ -26,6 +49,122 @@ pub enum Attribute {
     ScopeAttr(String),
 }
 
+impl Attribute {
+    /// Create an item attribute from `meta`, ie. `#[serde(default)]`
+    pub fn meta(meta: impl Into<Meta>) -> Self {
+        Attribute::ItemAttr(format!("#[{}]", meta.into().generate()))
+    }
+
+    /// Create a scope attribute from `meta`, ie. `#![allow(dead_code)]`
+    pub fn scope_meta(meta: impl Into<Meta>) -> Self {
+        Attribute::ScopeAttr(format!("#![{}]", meta.into().generate()))
+    }
+
+    /// Create a `#[derive(...)]` attribute
+    pub fn derive(traits: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self::meta(MetaList::new("derive").add_paths(traits).to_owned())
+    }
+
+    /// Create a `#[cfg(...)]` attribute
+    pub fn cfg(predicate: Cfg) -> Self {
+        Self::meta(MetaList::new("cfg").add_item(predicate).to_owned())
+    }
+
+    /// Create a `#[cfg_attr(..., ...)]` attribute, applying `meta` when `predicate` holds
+    pub fn cfg_attr(predicate: Cfg, meta: impl Into<Meta>) -> Self {
+        Self::meta(
+            MetaList::new("cfg_attr")
+                .add_item(predicate)
+                .add_item(meta)
+                .to_owned(),
+        )
+    }
+
+    /// Create a `#[repr(...)]` attribute, ie. `Attribute::repr(["C", "u8"])`
+    pub fn repr(reprs: impl IntoIterator<Item = impl ToString>) -> Self {
+        Self::meta(MetaList::new("repr").add_paths(reprs).to_owned())
+    }
+
+    /// Check if this is a scope attribute, ie. `#![...]`
+    pub fn is_scope(&self) -> bool {
+        matches!(self, Attribute::ScopeAttr(_))
+    }
+
+    /// Parse the content of this attribute, `None` if it isn't a single, well formed
+    /// meta item such as `path`, `path(...)` or `path = value`.
+    pub fn parse_meta(&self) -> Option<Meta> {
+        let (Attribute::ItemAttr(s) | Attribute::ScopeAttr(s)) = self;
+        let content = s
+            .trim_start_matches('#')
+            .trim_start_matches('!')
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?;
+        Meta::parse(content)
+    }
+
+    /// The path of this attribute, ie. `serde` for `#[serde(default)]`
+    pub fn path(&self) -> Option<String> {
+        self.parse_meta().map(|meta| meta.path().to_string())
+    }
+
+    /// The traits derived by this attribute, empty if it isn't a `derive` attribute
+    pub fn derives(&self) -> Vec<String> {
+        match self.parse_meta() {
+            Some(Meta::List(list)) if list.path == "derive" && !self.is_scope() => {
+                list.items.iter().map(|item| item.generate()).collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Add `attribute` to `attributes`, merging its traits into an existing derive
+/// attribute rather than adding a second one.
+pub(crate) fn push_attribute(attributes: &mut Vec<Attribute>, attribute: Attribute) {
+    let derives = attribute.derives();
+    if !derives.is_empty() {
+        if let Some(existing) = attributes.iter_mut().find(|a| !a.derives().is_empty()) {
+            let mut merged = existing.derives();
+            for derive in derives {
+                if !merged.contains(&derive) {
+                    merged.push(derive);
+                }
+            }
+            *existing = Attribute::derive(merged);
+            return;
+        }
+    }
+    attributes.push(attribute);
+}
+
+/// Merge the derive attributes in `attributes`, preceded by `defaults`, into a single,
+/// deduplicated, derive attribute placed where the first one was.
+pub(crate) fn merge_derives(attributes: &[Attribute], defaults: &[String]) -> Vec<Attribute> {
+    let mut derives = defaults.to_vec();
+    let mut position = None;
+    let mut merged = vec![];
+    for attribute in attributes {
+        let attribute_derives = attribute.derives();
+        if attribute_derives.is_empty() {
+            merged.push(attribute.clone());
+            continue;
+        }
+        position.get_or_insert(merged.len());
+        derives.extend(attribute_derives);
+    }
+    let mut unique: Vec<String> = vec![];
+    for derive in derives {
+        if !unique.contains(&derive) {
+            unique.push(derive);
+        }
+    }
+    if !unique.is_empty() {
+        merged.insert(position.unwrap_or(0), Attribute::derive(unique));
+    }
+    merged
+}
+
 // TODO: Use TryFrom when https://github.com/rust-lang/rust/issues/50133 is resolved.
 impl<S: ToString> From<S> for Attribute {
     fn from(attribute: S) -> Self {
@@ -48,3 +187,261 @@ impl SrcCode for Attribute {
         }
     }
 }
+
+/// The content of an attribute
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Meta {
+    /// A path, ie. `test` or `serde::Serialize`
+    Path(String),
+    /// A path followed by a list of nested meta items, ie. `derive(Debug, Clone)`
+    List(MetaList),
+    /// A path and a value, expected to be valid Rust source code, ie. `rename = "foo"`
+    NameValue(String, String),
+}
+
+impl Meta {
+    /// Create a `path = value` meta item, `value` is expected to be valid Rust
+    /// source code, ie. `"\"foo\""` or `"1"`.
+    pub fn name_value(path: impl ToString, value: impl ToString) -> Self {
+        Meta::NameValue(path.to_string(), value.to_string())
+    }
+
+    /// Create a `path = "value"` meta item, quoting and escaping `value`
+    pub fn name_str(path: impl ToString, value: &str) -> Self {
+        Meta::NameValue(path.to_string(), format!("{:?}", value))
+    }
+
+    /// The path of this meta item
+    pub fn path(&self) -> &str {
+        match self {
+            Meta::Path(path) | Meta::NameValue(path, _) => path.as_str(),
+            Meta::List(list) => list.path.as_str(),
+        }
+    }
+
+    /// Parse a meta item from source code, ie. `serde(rename = "a", default)`
+    fn parse(src: &str) -> Option<Self> {
+        let src = src.trim();
+        let end = src.find(['(', '=']).unwrap_or(src.len());
+        let path = src[..end].trim();
+        if path.is_empty() {
+            return None;
+        }
+        let rest = &src[end..];
+        if rest.is_empty() {
+            Some(Meta::Path(path.to_string()))
+        } else if let Some(value) = rest.strip_prefix('=') {
+            Some(Meta::name_value(path, value.trim()))
+        } else {
+            let inner = rest.strip_prefix('(')?.strip_suffix(')')?;
+            let mut list = MetaList::new(path);
+            for item in split_top_level(inner)? {
+                list.add_item(Meta::parse(&item)?);
+            }
+            Some(Meta::List(list))
+        }
+    }
+}
+
+/// Split `src` at commas which aren't nested within delimiters or string literals.
+/// Returns `None` if the delimiters are unbalanced.
+fn split_top_level(src: &str) -> Option<Vec<String>> {
+    let mut items = vec![];
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut in_str = false;
+    let mut escaped = false;
+    for c in src.chars() {
+        if in_str {
+            in_str = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else {
+            match c {
+                '"' => in_str = true,
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth = depth.checked_sub(1)?,
+                ',' if depth == 0 => {
+                    items.push(std::mem::take(&mut current));
+                    continue;
+                }
+                _ => (),
+            }
+        }
+        current.push(c);
+    }
+    if depth != 0 || in_str {
+        return None;
+    }
+    items.push(current);
+    items.retain(|item| !item.trim().is_empty());
+    Some(items)
+}
+
+impl From<&str> for Meta {
+    fn from(path: &str) -> Self {
+        Meta::Path(path.to_string())
+    }
+}
+
+impl From<String> for Meta {
+    fn from(path: String) -> Self {
+        Meta::Path(path)
+    }
+}
+
+impl From<MetaList> for Meta {
+    fn from(list: MetaList) -> Self {
+        Meta::List(list)
+    }
+}
+
+impl From<Cfg> for Meta {
+    fn from(cfg: Cfg) -> Self {
+        match cfg {
+            Cfg::Option(name) => Meta::Path(name),
+            Cfg::KeyValue(key, value) => Meta::name_str(key, &value),
+            Cfg::All(cfgs) => MetaList::new("all").add_items(cfgs).to_owned().into(),
+            Cfg::Any(cfgs) => MetaList::new("any").add_items(cfgs).to_owned().into(),
+            Cfg::Not(cfg) => MetaList::new("not").add_item(*cfg).to_owned().into(),
+        }
+    }
+}
+
+impl SrcCode for Meta {
+    fn generate(&self) -> String {
+        match self {
+            Meta::Path(path) => path.to_owned(),
+            Meta::List(list) => list.generate(),
+            Meta::NameValue(path, value) => format!("{} = {}", path, value),
+        }
+    }
+}
+
+/// Builder for a meta list, ie. `serde(rename = "foo", default)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MetaList {
+    path: String,
+    items: Vec<Meta>,
+}
+
+impl MetaList {
+    /// Create a new, empty, meta list
+    pub fn new(path: impl ToString) -> Self {
+        Self {
+            path: path.to_string(),
+            items: vec![],
+        }
+    }
+
+    /// Add a nested meta item
+    pub fn add_item(&mut self, item: impl Into<Meta>) -> &mut Self {
+        self.items.push(item.into());
+        self
+    }
+
+    /// Add a nested meta item
+    pub fn with_item(mut self, item: impl Into<Meta>) -> Self {
+        self.add_item(item);
+        self
+    }
+
+    /// Add multiple nested meta items
+    pub fn add_items(&mut self, items: impl IntoIterator<Item = impl Into<Meta>>) -> &mut Self {
+        self.items.extend(items.into_iter().map(|i| i.into()));
+        self
+    }
+
+    /// Add multiple nested meta items
+    pub fn with_items(mut self, items: impl IntoIterator<Item = impl Into<Meta>>) -> Self {
+        self.add_items(items);
+        self
+    }
+
+    /// Add multiple nested paths, ie. the traits of a `derive`
+    pub fn add_paths(&mut self, paths: impl IntoIterator<Item = impl ToString>) -> &mut Self {
+        self.items
+            .extend(paths.into_iter().map(|p| Meta::Path(p.to_string())));
+        self
+    }
+
+    /// Add multiple nested paths, ie. the traits of a `derive`
+    pub fn with_paths(mut self, paths: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.add_paths(paths);
+        self
+    }
+
+    /// The nested meta items
+    pub fn items(&self) -> &[Meta] {
+        self.items.as_slice()
+    }
+
+    /// The path of this meta list, ie. `derive`
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+}
+
+impl SrcCode for MetaList {
+    fn generate(&self) -> String {
+        let items = self
+            .items
+            .iter()
+            .map(|item| item.generate())
+            .collect::<Vec<String>>();
+        format!("{}({})", self.path, items.join(", "))
+    }
+}
+
+/// A `cfg` predicate, ie. `all(feature = "serde", not(test))`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Cfg {
+    /// A configuration option, ie. `test` or `unix`
+    Option(String),
+    /// A key and value, ie. `target_os = "linux"`
+    KeyValue(String, String),
+    /// True if all predicates are true
+    All(Vec<Cfg>),
+    /// True if any of the predicates is true
+    Any(Vec<Cfg>),
+    /// True if the predicate is false
+    Not(Box<Cfg>),
+}
+
+impl Cfg {
+    /// A configuration option, ie. `Cfg::option("test")`
+    pub fn option(name: impl ToString) -> Self {
+        Cfg::Option(name.to_string())
+    }
+
+    /// A key and value, ie. `Cfg::key_value("target_os", "linux")`
+    pub fn key_value(key: impl ToString, value: impl ToString) -> Self {
+        Cfg::KeyValue(key.to_string(), value.to_string())
+    }
+
+    /// A cargo feature, ie. `feature = "serde"`
+    pub fn feature(name: impl ToString) -> Self {
+        Self::key_value("feature", name)
+    }
+
+    /// True if all predicates are true
+    pub fn all(predicates: impl IntoIterator<Item = Cfg>) -> Self {
+        Cfg::All(predicates.into_iter().collect())
+    }
+
+    /// True if any of the predicates is true
+    pub fn any(predicates: impl IntoIterator<Item = Cfg>) -> Self {
+        Cfg::Any(predicates.into_iter().collect())
+    }
+
+    /// True if `predicate` is false
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(predicate: Cfg) -> Self {
+        Cfg::Not(Box::new(predicate))
+    }
+}
+
+impl SrcCode for Cfg {
+    fn generate(&self) -> String {
+        Meta::from(self.clone()).generate()
+    }
+}
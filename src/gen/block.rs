@@ -0,0 +1,166 @@
+//!
+//!
+//! Blocks of statements, usable as function bodies or within expressions.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let block = Block::new()
+//!     .add_stmt(Stmt::let_("name", Expr::path("self").field("name").method("clone", vec![])))
+//!     .add_stmt(Stmt::semi(Expr::macro_call("println", "\"{}\", name", MacroDelimiter::Paren)))
+//!     .add_stmt(Stmt::expr(Expr::path("name")))
+//!     .to_owned();
+//!
+//! let expected = r#"
+//!     {
+//!         let name = self.name.clone();
+//!         println!("{}", name);
+//!         name
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&block.generate()));
+//! ```
+
+use serde::Serialize;
+
+use crate::*;
+
+/// Represents a block of statements, rendered within `{ ... }`
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Block {
+    stmts: Vec<Stmt>,
+}
+
+/// Represents a single statement within a `Block`
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Stmt {
+    /// A `let` binding
+    Let {
+        /// Pattern being bound, ie. `x` or `mut x` or `(a, b)`
+        pat: String,
+        /// Optional type annotation
+        ty: Option<String>,
+        /// Optional initial value
+        init: Option<Expr>,
+    },
+    /// An expression followed by a `;`
+    Semi(Expr),
+    /// An expression without a trailing `;`, ie. the final value of a block
+    /// or a block-like expression such as `if` or `match`
+    Expr(Expr),
+    /// Raw source code, expected to be valid Rust statement(s) or item(s)
+    Raw(String),
+    /// A `//` comment or a blank line
+    Comment(Comment),
+}
+
+impl Block {
+    /// Create a new, empty, `Block`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a statement to this block
+    pub fn add_stmt(&mut self, stmt: Stmt) -> &mut Self {
+        self.stmts.push(stmt);
+        self
+    }
+
+    /// Add a statement to this block
+    pub fn with_stmt(mut self, stmt: Stmt) -> Self {
+        self.add_stmt(stmt);
+        self
+    }
+
+    /// Add multiple statements at once
+    pub fn add_stmts(&mut self, stmts: impl IntoIterator<Item = Stmt>) -> &mut Self {
+        self.stmts.extend(stmts);
+        self
+    }
+
+    /// Add multiple statements at once
+    pub fn with_stmts(mut self, stmts: impl IntoIterator<Item = Stmt>) -> Self {
+        self.add_stmts(stmts);
+        self
+    }
+
+    /// The statements of this block, rendered without the surrounding braces.
+    pub(crate) fn generate_stmts(&self) -> Vec<String> {
+        self.stmts.to_src_vec()
+    }
+
+    /// The statements of this block
+    pub fn stmts(&self) -> &[Stmt] {
+        self.stmts.as_slice()
+    }
+}
+
+impl Stmt {
+    /// Create a `let pat = init;` statement
+    pub fn let_(pat: impl ToString, init: impl Into<Expr>) -> Self {
+        Stmt::Let {
+            pat: pat.to_string(),
+            ty: None,
+            init: Some(init.into()),
+        }
+    }
+
+    /// Create a `let pat: ty = init;` statement
+    pub fn let_typed(pat: impl ToString, ty: impl ToString, init: impl Into<Expr>) -> Self {
+        Stmt::Let {
+            pat: pat.to_string(),
+            ty: Some(ty.to_string()),
+            init: Some(init.into()),
+        }
+    }
+
+    /// Create an expression statement followed by `;`
+    pub fn semi(expr: impl Into<Expr>) -> Self {
+        Stmt::Semi(expr.into())
+    }
+
+    /// Create an expression statement without a trailing `;`
+    pub fn expr(expr: impl Into<Expr>) -> Self {
+        Stmt::Expr(expr.into())
+    }
+
+    /// Create a `//` comment, given as plain text which may span multiple lines
+    pub fn comment(text: impl ToString) -> Self {
+        Stmt::Comment(Comment::line(text))
+    }
+
+    /// Create a blank line
+    pub fn blank_line() -> Self {
+        Stmt::Comment(Comment::Blank)
+    }
+}
+
+impl SrcCode for Stmt {
+    fn generate(&self) -> String {
+        match self {
+            Stmt::Let { pat, ty, init } => {
+                let mut src = format!("let {}", pat);
+                if let Some(ty) = ty {
+                    src.push_str(&format!(": {}", ty));
+                }
+                if let Some(init) = init {
+                    src.push_str(&format!(" = {}", init.generate()));
+                }
+                src.push(';');
+                src
+            }
+            Stmt::Semi(expr) => format!("{};", expr.generate()),
+            Stmt::Expr(expr) => expr.generate(),
+            Stmt::Raw(src) => src.clone(),
+            Stmt::Comment(comment) => comment.generate(),
+        }
+    }
+}
+
+impl SrcCode for Block {
+    fn generate(&self) -> String {
+        format!("{{\n{}\n}}", self.generate_stmts().join("\n"))
+    }
+}
@@ -0,0 +1,68 @@
+//!
+//!
+//! Regular, non-doc, comments and blank lines, placed before items or within blocks,
+//! or standalone among the items of a module or impl, see `SeparatorExt`.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let s = Struct::new("User")
+//!     .add_comment("Generated from schema.json: table users")
+//!     .add_field(Field::new("id", "u64"))
+//!     .add_field(Field::new("name", "String").add_blank_line().add_comment("region: profile").to_owned())
+//!     .to_owned();
+//!
+//! let expected = r#"
+//!     // Generated from schema.json: table users
+//!     struct User
+//!     {
+//!         id: u64,
+//!
+//!         // region: profile
+//!         name: String,
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&s.generate()));
+//! ```
+
+use serde::Serialize;
+
+use crate::SrcCode;
+
+/// Represents a `//` comment or a blank line
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Comment {
+    /// A comment, which may span multiple lines, each rendered as `// ...`
+    Line(String),
+    /// An empty line, to visually separate items
+    Blank,
+}
+
+impl Comment {
+    /// Create a comment from plain text, without the leading `//`
+    pub fn line(text: impl ToString) -> Self {
+        Comment::Line(text.to_string())
+    }
+}
+
+impl SrcCode for Comment {
+    fn generate(&self) -> String {
+        match self {
+            Comment::Line(text) if text.is_empty() => "//".to_string(),
+            Comment::Line(text) => text
+                .lines()
+                .map(|line| {
+                    if line.is_empty() {
+                        "//".to_string()
+                    } else {
+                        format!("// {}", line)
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join("\n"),
+            Comment::Blank => String::new(),
+        }
+    }
+}
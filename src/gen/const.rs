@@ -0,0 +1,157 @@
+//!
+//!
+//! Create a `const` item.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents a `const` item in source code.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let c = Const::new("VERSION", "&str", "\"1.0.0\"")
+///     .set_is_pub(true)
+///     .add_doc("/// Schema version")
+///     .to_owned();
+///
+/// let expected = r#"
+///     /// Schema version
+///     pub const VERSION: &str = "1.0.0";
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&c.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Const {
+    name: String,
+    is_pub: bool,
+    ty: String,
+    value: String,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl Const {
+    /// Create a new `Const`, `value` is expected to be valid Rust source code.
+    pub fn new(name: impl ToString, ty: impl ToString, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            value: value.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a new `Const`, converting `name` into `SCREAMING_SNAKE_CASE` and escaping it
+    /// if it isn't a valid identifier
+    pub fn new_from_external_name(
+        name: &str,
+        ty: impl ToString,
+        value: impl ToString,
+        policy: &IdentPolicy,
+    ) -> Self {
+        Self::new(policy.convert(name, Case::ScreamingSnake), ty, value)
+    }
+
+    /// Set if this const is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this const is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this const
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this const
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this const
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this const is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The type of this const
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// The value of this const, as source code
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    /// The documentation lines of this const
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this const
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this const
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for Const {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Const {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for Const {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for Const {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}const {{ self.name }}: {{ self.ty }} = {{ self.value }};
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
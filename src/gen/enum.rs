@@ -32,19 +32,25 @@ use crate::*;
 use tera::{Context, Tera};
 
 /// Represent an `enum` object
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Enum {
     name: String,
     generics: Vec<Generic>,
     is_pub: bool,
-    variants: Vec<Variant>,
+    pub(crate) variants: Vec<Variant>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
 }
 
 /// Represent an enum variant/arm
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Variant {
     name: String,
     inner: Option<String>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
 }
 
 impl Enum {
@@ -55,16 +61,86 @@ impl Enum {
             ..Self::default()
         }
     }
+    /// Create a new `Enum`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+    /// Create a new `Enum`, converting `name` into `UpperCamelCase` and escaping it if
+    /// it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::UpperCamel))
+    }
     /// Set if this is public
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
+    /// Set if this is public
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
     /// Add a variant
     pub fn add_variant(&mut self, variant: Variant) -> &mut Self {
         self.variants.push(variant);
         self
     }
+    /// Add a variant
+    pub fn with_variant(mut self, variant: Variant) -> Self {
+        self.add_variant(variant);
+        self
+    }
+    /// The name of this enum
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    /// Set the name of this enum
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+    /// Set the name of this enum
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+    /// If this enum is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+    /// The variants of this enum
+    pub fn variants(&self) -> &[Variant] {
+        self.variants.as_slice()
+    }
+    /// The generics of this enum
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+    /// The documentation lines of this enum
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+    /// The attributes of this enum
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+    /// The comments placed before this enum
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+    /// Get a reference to the variant named `name`
+    pub fn get_variant(&self, name: &str) -> Option<&Variant> {
+        self.variants.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the variant named `name`
+    pub fn get_variant_mut(&mut self, name: &str) -> Option<&mut Variant> {
+        self.variants.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the variant named `name`, returning it if it existed
+    pub fn remove_variant(&mut self, name: &str) -> Option<Variant> {
+        let idx = self.variants.iter().position(|v| v.name() == name)?;
+        Some(self.variants.remove(idx))
+    }
 }
 
 impl Variant {
@@ -75,19 +151,94 @@ impl Variant {
             ..Self::default()
         }
     }
+    /// Create a new variant, escaping `name` if it isn't a valid identifier. If the name
+    /// had to be changed, the policy's rename attribute is added to the variant.
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        let ident = policy.escape(name);
+        let mut variant = Self::new(&ident);
+        variant.attributes.extend(policy.rename_attribute(&ident));
+        variant
+    }
+    /// Create a new variant, converting `name` into `UpperCamelCase` and escaping it if
+    /// it isn't a valid identifier. If the name had to be changed, the policy's rename
+    /// attribute is added to the variant.
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        let ident = policy.convert(name, Case::UpperCamel);
+        let mut variant = Self::new(&ident);
+        variant.attributes.extend(policy.rename_attribute(&ident));
+        variant
+    }
     /// Set the inner portion of this variant, expected to be valid Rust source code.
     pub fn set_inner(&mut self, inner: Option<impl ToString>) -> &mut Self {
         self.inner = inner.map(|s| s.to_string());
         self
     }
+    /// Set the inner portion of this variant, expected to be valid Rust source code.
+    pub fn with_inner(mut self, inner: Option<impl ToString>) -> Self {
+        self.set_inner(inner);
+        self
+    }
+    /// The name of this variant
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    /// Set the name of this variant
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+    /// Set the name of this variant
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+    /// The inner portion of this variant, ie. `(T)`
+    pub fn inner(&self) -> Option<&str> {
+        self.inner.as_deref()
+    }
+    /// The documentation lines of this variant
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+    /// The attributes of this variant
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+    /// The comments placed before this variant
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for Variant {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Variant {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for Variant {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
 }
 
 impl SrcCode for Variant {
     fn generate(&self) -> String {
-        let template = r#"{{ self.name }}{{ inner }}"#;
+        let template = r#"{% for comment in comments %}{{ comment }}
+        {% endfor %}{% for doc in self.docs %}{{ doc }}
+        {% endfor %}{% for attribute in attributes %}{{ attribute }}
+        {% endfor %}{{ self.name }}{{ inner }}"#;
         let mut ctx = Context::new();
         ctx.insert("self", &self);
+        ctx.insert("attributes", &self.attributes.to_src_vec());
         ctx.insert("inner", &self.inner.as_ref().unwrap_or(&"".to_string()));
+        ctx.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &ctx, false).unwrap()
     }
 }
@@ -101,9 +252,39 @@ impl internal::Generics for Enum {
     }
 }
 
+impl internal::Attributes for Enum {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Enum {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Derives for Enum {
+    fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
+impl internal::Docs for Enum {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl SrcCode for Enum {
     fn generate(&self) -> String {
         let template = r#"
+            {{ comments | join(sep="
+            ") }}
+            {{ self.docs | join(sep="
+            ") }}
+            {{ attributes | join(sep="
+            ") }}
             {% if self.is_pub %}pub {% endif %}enum {{ self.name }}{{ generics }}
             {
                 {% for variant in variants %}{{ variant }},
@@ -114,6 +295,11 @@ impl SrcCode for Enum {
         ctx.insert("self", &self);
         ctx.insert("generics", &self.generics.generate());
         ctx.insert("variants", &self.variants.to_src_vec());
+        ctx.insert(
+            "attributes",
+            &merge_derives(&self.attributes, &[]).to_src_vec(),
+        );
+        ctx.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &ctx, false).unwrap()
     }
 }
@@ -0,0 +1,758 @@
+//!
+//!
+//! Expressions for building function bodies, see `Block` and `Stmt` for the
+//! statements which hold them.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let expr = Expr::match_(Expr::path("self"))
+//!     .add_arm(MatchArm::new("Self::A", Expr::lit_str("a")))
+//!     .add_arm(MatchArm::new("Self::B(inner)", Expr::path("inner").method("as_str", vec![])))
+//!     .to_owned();
+//!
+//! let expected = r#"
+//!     match self
+//!     {
+//!         Self::A => "a",
+//!         Self::B(inner) => inner.as_str(),
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&expr.generate()));
+//! ```
+
+use serde::Serialize;
+
+use crate::*;
+
+/// Represents a Rust expression.
+///
+/// Anything which doesn't have a dedicated variant can be expressed with `Expr::Raw`,
+/// which is also what `&str` and `String` convert into.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Expr {
+    /// Raw source code, expected to be a valid Rust expression. Unless it's a single
+    /// operand, ie. `foo.bar()` or `-1`, it's wrapped in parenthesis when used as one.
+    Raw(String),
+    /// A path or identifier, ie. `foo` or `Self::Bar`
+    Path(String),
+    /// A function call, ie. `foo(a, b)`
+    Call {
+        /// Expression being called
+        func: Box<Expr>,
+        /// Arguments to the call
+        args: Vec<Expr>,
+    },
+    /// A method call, ie. `foo.bar(a, b)`
+    MethodCall {
+        /// Receiver of the method
+        receiver: Box<Expr>,
+        /// Name of the method, may include a turbofish
+        method: String,
+        /// Arguments to the method
+        args: Vec<Expr>,
+    },
+    /// Field access, ie. `foo.bar`
+    Field {
+        /// Expression being accessed
+        base: Box<Expr>,
+        /// Name or index of the field
+        member: String,
+    },
+    /// Indexing, ie. `foo[0]`
+    Index {
+        /// Expression being indexed
+        base: Box<Expr>,
+        /// The index
+        index: Box<Expr>,
+    },
+    /// A macro invocation, ie. `vec![1, 2]` or `format!("{}", a)`
+    Macro {
+        /// Path of the macro, without the `!`
+        path: String,
+        /// Tokens passed to the macro
+        tokens: String,
+        /// Delimiter around the tokens
+        delimiter: MacroDelimiter,
+    },
+    /// A reference, ie. `&foo` or `&mut foo`
+    Reference {
+        /// If this is a mutable reference
+        is_mut: bool,
+        /// Expression being referenced
+        expr: Box<Expr>,
+    },
+    /// A unary operation, ie. `!foo`, `-foo` or `*foo`
+    Unary {
+        /// The operator
+        op: String,
+        /// The operand
+        expr: Box<Expr>,
+    },
+    /// A binary operation or assignment, ie. `a + b`, `a == b` or `a = b`
+    Binary {
+        /// Left hand side
+        lhs: Box<Expr>,
+        /// The operator
+        op: String,
+        /// Right hand side
+        rhs: Box<Expr>,
+    },
+    /// A cast, ie. `foo as u32`
+    Cast {
+        /// Expression being cast
+        expr: Box<Expr>,
+        /// Target type
+        ty: String,
+    },
+    /// The `?` operator, ie. `foo?`
+    Try(Box<Expr>),
+    /// A `return`, optionally with a value
+    Return(Option<Box<Expr>>),
+    /// A `break`, optionally with a value
+    Break(Option<Box<Expr>>),
+    /// A `continue`
+    Continue,
+    /// A parenthesised expression, ie. `(a + b)`
+    Paren(Box<Expr>),
+    /// A tuple, ie. `(a, b)`
+    Tuple(Vec<Expr>),
+    /// An array, ie. `[a, b]`
+    Array(Vec<Expr>),
+    /// A struct literal, ie. `Foo { a: 1, ..Default::default() }`
+    Struct {
+        /// Path of the struct
+        path: String,
+        /// Field names and their values
+        fields: Vec<(String, Expr)>,
+        /// Base expression for functional update syntax
+        base: Option<Box<Expr>>,
+    },
+    /// A closure, ie. `move |a, b| a + b`
+    Closure {
+        /// If this is a `move` closure
+        is_move: bool,
+        /// Parameters, optionally with type annotations ie. `a: u32`
+        params: Vec<String>,
+        /// Body of the closure
+        body: Box<Expr>,
+    },
+    /// A block expression
+    Block(Block),
+    /// An `if` expression, with an optional `else` branch which is expected
+    /// to be either `Expr::Block` or another `Expr::If`
+    If {
+        /// The condition, may be `let Some(x) = y`
+        cond: Box<Expr>,
+        /// Branch taken if `cond` holds
+        then: Block,
+        /// The `else` branch
+        otherwise: Option<Box<Expr>>,
+    },
+    /// A `match` expression
+    Match {
+        /// Expression being matched on
+        expr: Box<Expr>,
+        /// The arms, in order
+        arms: Vec<MatchArm>,
+    },
+    /// A `loop`
+    Loop(Block),
+    /// A `while` loop, `cond` may be `let Some(x) = y`
+    While {
+        /// The condition
+        cond: Box<Expr>,
+        /// Body of the loop
+        body: Block,
+    },
+    /// A `for` loop
+    For {
+        /// Pattern binding each item
+        pat: String,
+        /// Expression being iterated
+        iter: Box<Expr>,
+        /// Body of the loop
+        body: Block,
+    },
+}
+
+/// Represents a single arm of a `match` expression
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MatchArm {
+    pat: String,
+    guard: Option<Expr>,
+    body: Expr,
+}
+
+impl MatchArm {
+    /// Create a new arm matching `pat`, which is expected to be a valid Rust pattern.
+    pub fn new(pat: impl ToString, body: impl Into<Expr>) -> Self {
+        Self {
+            pat: pat.to_string(),
+            guard: None,
+            body: body.into(),
+        }
+    }
+
+    /// Set an `if` guard for this arm
+    pub fn set_guard(&mut self, guard: Option<impl Into<Expr>>) -> &mut Self {
+        self.guard = guard.map(Into::into);
+        self
+    }
+
+    /// Set an `if` guard for this arm
+    pub fn with_guard(mut self, guard: Option<impl Into<Expr>>) -> Self {
+        self.set_guard(guard);
+        self
+    }
+}
+
+impl SrcCode for MatchArm {
+    fn generate(&self) -> String {
+        match &self.guard {
+            Some(guard) => format!(
+                "{} if {} => {},",
+                self.pat,
+                guard.generate(),
+                self.body.generate()
+            ),
+            None => format!("{} => {},", self.pat, self.body.generate()),
+        }
+    }
+}
+
+impl Expr {
+    /// Create a raw expression from valid Rust source code
+    pub fn raw(src: impl ToString) -> Self {
+        Expr::Raw(src.to_string())
+    }
+
+    /// Create a path expression, ie. `foo` or `Self::Bar`
+    pub fn path(path: impl ToString) -> Self {
+        Expr::Path(path.to_string())
+    }
+
+    /// Create a string literal, quoting and escaping `s`
+    pub fn lit_str(s: &str) -> Self {
+        Expr::Raw(format!("{:?}", s))
+    }
+
+    /// Create a call of `func` with `args`
+    pub fn call(func: impl Into<Expr>, args: Vec<Expr>) -> Self {
+        Expr::Call {
+            func: Box::new(func.into()),
+            args,
+        }
+    }
+
+    /// Create a macro invocation, ie. `vec![1, 2]`
+    pub fn macro_call(
+        path: impl ToString,
+        tokens: impl ToString,
+        delimiter: MacroDelimiter,
+    ) -> Self {
+        Expr::Macro {
+            path: path.to_string(),
+            tokens: tokens.to_string(),
+            delimiter,
+        }
+    }
+
+    /// Create a struct literal with no fields, see `Expr::add_field_value`
+    pub fn struct_(path: impl ToString) -> Self {
+        Expr::Struct {
+            path: path.to_string(),
+            fields: vec![],
+            base: None,
+        }
+    }
+
+    /// Create a closure with `params` and `body`
+    pub fn closure(params: impl IntoIterator<Item = impl ToString>, body: impl Into<Expr>) -> Self {
+        Expr::Closure {
+            is_move: false,
+            params: params.into_iter().map(|p| p.to_string()).collect(),
+            body: Box::new(body.into()),
+        }
+    }
+
+    /// Create an `if` expression without an `else` branch, see `Expr::set_else`
+    pub fn if_(cond: impl Into<Expr>, then: Block) -> Self {
+        Expr::If {
+            cond: Box::new(cond.into()),
+            then,
+            otherwise: None,
+        }
+    }
+
+    /// Create a `match` expression without arms, see `Expr::add_arm`
+    pub fn match_(expr: impl Into<Expr>) -> Self {
+        Expr::Match {
+            expr: Box::new(expr.into()),
+            arms: vec![],
+        }
+    }
+
+    /// Create a `for` loop
+    pub fn for_(pat: impl ToString, iter: impl Into<Expr>, body: Block) -> Self {
+        Expr::For {
+            pat: pat.to_string(),
+            iter: Box::new(iter.into()),
+            body,
+        }
+    }
+
+    /// Create a `while` loop
+    pub fn while_(cond: impl Into<Expr>, body: Block) -> Self {
+        Expr::While {
+            cond: Box::new(cond.into()),
+            body,
+        }
+    }
+
+    /// Create a `return` with a value
+    pub fn return_(expr: impl Into<Expr>) -> Self {
+        Expr::Return(Some(Box::new(expr.into())))
+    }
+
+    /// Create a binary operation `lhs op rhs`
+    pub fn binary(lhs: impl Into<Expr>, op: impl ToString, rhs: impl Into<Expr>) -> Self {
+        Expr::Binary {
+            lhs: Box::new(lhs.into()),
+            op: op.to_string(),
+            rhs: Box::new(rhs.into()),
+        }
+    }
+
+    /// Call `method` on this expression
+    pub fn method(self, method: impl ToString, args: Vec<Expr>) -> Self {
+        Expr::MethodCall {
+            receiver: Box::new(self),
+            method: method.to_string(),
+            args,
+        }
+    }
+
+    /// Access `member` of this expression
+    pub fn field(self, member: impl ToString) -> Self {
+        Expr::Field {
+            base: Box::new(self),
+            member: member.to_string(),
+        }
+    }
+
+    /// Apply the `?` operator to this expression
+    pub fn try_op(self) -> Self {
+        Expr::Try(Box::new(self))
+    }
+
+    /// Take a reference to this expression
+    pub fn reference(self, is_mut: bool) -> Self {
+        Expr::Reference {
+            is_mut,
+            expr: Box::new(self),
+        }
+    }
+
+    /// Add an arm to a `match` expression, no-op for any other expression.
+    pub fn add_arm(&mut self, arm: MatchArm) -> &mut Self {
+        if let Expr::Match { arms, .. } = self {
+            arms.push(arm);
+        }
+        self
+    }
+
+    /// Add an arm to a `match` expression, no-op for any other expression.
+    pub fn with_arm(mut self, arm: MatchArm) -> Self {
+        self.add_arm(arm);
+        self
+    }
+
+    /// Add a field to a struct literal, no-op for any other expression.
+    pub fn add_field_value(&mut self, name: impl ToString, value: impl Into<Expr>) -> &mut Self {
+        if let Expr::Struct { fields, .. } = self {
+            fields.push((name.to_string(), value.into()));
+        }
+        self
+    }
+
+    /// Add a field to a struct literal, no-op for any other expression.
+    pub fn with_field_value(mut self, name: impl ToString, value: impl Into<Expr>) -> Self {
+        self.add_field_value(name, value);
+        self
+    }
+
+    /// Set the base of a struct literal, ie. `..Default::default()`, no-op for any
+    /// other expression.
+    pub fn set_struct_base(&mut self, base: Option<impl Into<Expr>>) -> &mut Self {
+        if let Expr::Struct { base: b, .. } = self {
+            *b = base.map(|e| Box::new(e.into()));
+        }
+        self
+    }
+
+    /// Set the base of a struct literal, ie. `..Default::default()`, no-op for any
+    /// other expression.
+    pub fn with_struct_base(mut self, base: Option<impl Into<Expr>>) -> Self {
+        self.set_struct_base(base);
+        self
+    }
+
+    /// Set the `else` branch of an `if` expression, no-op for any other expression.
+    pub fn set_else(&mut self, otherwise: Option<impl Into<Expr>>) -> &mut Self {
+        if let Expr::If { otherwise: o, .. } = self {
+            *o = otherwise.map(|e| Box::new(e.into()));
+        }
+        self
+    }
+
+    /// Set the `else` branch of an `if` expression, no-op for any other expression.
+    pub fn with_else(mut self, otherwise: Option<impl Into<Expr>>) -> Self {
+        self.set_else(otherwise);
+        self
+    }
+
+    /// Set if a closure is a `move` closure, no-op for any other expression.
+    pub fn set_is_move(&mut self, is_move: bool) -> &mut Self {
+        if let Expr::Closure { is_move: m, .. } = self {
+            *m = is_move;
+        }
+        self
+    }
+
+    /// Set if a closure is a `move` closure, no-op for any other expression.
+    pub fn with_is_move(mut self, is_move: bool) -> Self {
+        self.set_is_move(is_move);
+        self
+    }
+
+    /// Render this expression, wrapped in parenthesis if it can't be used
+    /// directly as the operand of a postfix expression.
+    fn generate_as_operand(&self) -> String {
+        if self.precedence() < u8::MAX {
+            format!("({})", self.generate())
+        } else {
+            self.generate()
+        }
+    }
+
+    /// Binding power of this expression when used as an operand, higher binds
+    /// tighter. Expressions which aren't operators at all bind tightest.
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Raw(src) => raw_precedence(src),
+            Expr::Binary { op, .. } => binary_precedence(op),
+            Expr::Cast { .. } => 12,
+            Expr::Reference { .. } | Expr::Unary { .. } => 13,
+            Expr::Return(_)
+            | Expr::Break(_)
+            | Expr::Closure { .. }
+            | Expr::If { .. }
+            | Expr::Match { .. }
+            | Expr::Loop(_)
+            | Expr::While { .. }
+            | Expr::For { .. } => 0,
+            _ => u8::MAX,
+        }
+    }
+
+    /// If this expression would be wrapped in parenthesis as an operand of the
+    /// binary operator `op`
+    fn needs_parens(&self, op: &str, is_lhs: bool) -> bool {
+        let (own, parent) = (self.precedence(), binary_precedence(op));
+        // `a as u32 < b` reads as the start of generic arguments to `u32`
+        if is_lhs && matches!(op, "<" | "<<") && self.ends_with_cast() {
+            return true;
+        }
+        own < parent
+            || own == parent
+                && match parent {
+                    // Assignment is right associative
+                    1 => is_lhs,
+                    // Ranges and comparisons can't be chained
+                    2 | 5 => true,
+                    _ => !is_lhs,
+                }
+    }
+
+    /// If this expression renders ending with a cast's type, ie. `a + b as u32`
+    fn ends_with_cast(&self) -> bool {
+        match self {
+            Expr::Cast { .. } => true,
+            Expr::Binary { op, rhs, .. } => !rhs.needs_parens(op, false) && rhs.ends_with_cast(),
+            _ => false,
+        }
+    }
+
+    /// Render this expression as an operand of the binary operator `op`, wrapped
+    /// in parenthesis if it would otherwise bind differently.
+    fn generate_as_binary_operand(&self, op: &str, is_lhs: bool) -> String {
+        if self.needs_parens(op, is_lhs) {
+            format!("({})", self.generate())
+        } else {
+            self.generate()
+        }
+    }
+
+    /// If this expression renders a struct literal outside of any delimiters,
+    /// which `rustc` rejects in the condition of `if`, `while`, `match` and `for`
+    fn has_bare_struct(&self) -> bool {
+        match self {
+            Expr::Struct { .. } => true,
+            Expr::Raw(src) => !src.trim_start().starts_with("let ") && has_bare_brace(src),
+            Expr::Binary { lhs, rhs, .. } => lhs.has_bare_struct() || rhs.has_bare_struct(),
+            Expr::Call { func: expr, .. }
+            | Expr::MethodCall { receiver: expr, .. }
+            | Expr::Field { base: expr, .. }
+            | Expr::Index { base: expr, .. }
+            | Expr::Reference { expr, .. }
+            | Expr::Unary { expr, .. }
+            | Expr::Cast { expr, .. }
+            | Expr::Try(expr) => expr.has_bare_struct(),
+            _ => false,
+        }
+    }
+
+    /// Render this expression as the condition of `if` or `while`, or the
+    /// expression of `match` or `for`, wrapped in parenthesis if it holds a struct
+    /// literal
+    fn generate_as_condition(&self) -> String {
+        if self.has_bare_struct() {
+            format!("({})", self.generate())
+        } else {
+            self.generate()
+        }
+    }
+}
+
+/// Binding power of raw source code: the lowest unless it's a single operand,
+/// ie. `foo.bar()`, `"a b"`, `-1` or `vec![a, b]`, so it's wrapped in parenthesis
+/// whenever it might bind differently.
+fn raw_precedence(src: &str) -> u8 {
+    let src = src.trim();
+    let operand = src.trim_start_matches(&['-', '!', '*', '&'][..]);
+    if operand.is_empty() || !is_single_operand(operand) {
+        0
+    } else if operand.len() < src.len() {
+        13
+    } else {
+        u8::MAX
+    }
+}
+
+/// If `src` holds no operator or whitespace outside of delimiters and literals
+fn is_single_operand(src: &str) -> bool {
+    let mut delimiters = vec![];
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => (),
+                    }
+                }
+            }
+            '(' | '[' | '{' => delimiters.push(c),
+            ')' | ']' | '}' => {
+                delimiters.pop();
+            }
+            // A turbofish, ie. `Vec::<u8>::new()`
+            ':' if chars.peek() == Some(&':') => {
+                chars.next();
+                if chars.peek() == Some(&'<') {
+                    chars.next();
+                    delimiters.push('<');
+                }
+            }
+            '>' if delimiters.last() == Some(&'<') => {
+                delimiters.pop();
+            }
+            // A macro invocation, ie. `vec![a]`
+            '!' if matches!(chars.peek(), Some('(') | Some('[') | Some('{')) => (),
+            '.' if chars.peek() == Some(&'.') && delimiters.is_empty() => return false,
+            c if delimiters.is_empty() && (c.is_whitespace() || "+-*/%&|^<>=!".contains(c)) => {
+                return false
+            }
+            _ => (),
+        }
+    }
+    true
+}
+
+/// If `src` holds a `{` outside of parenthesis and brackets
+fn has_bare_brace(src: &str) -> bool {
+    let mut depth = 0;
+    for c in src.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '{' if depth == 0 => return true,
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Precedence of a binary operator, following the Rust reference.
+fn binary_precedence(op: &str) -> u8 {
+    match op {
+        "*" | "/" | "%" => 11,
+        "+" | "-" => 10,
+        "<<" | ">>" => 9,
+        "&" => 8,
+        "^" => 7,
+        "|" => 6,
+        "==" | "!=" | "<" | ">" | "<=" | ">=" => 5,
+        "&&" => 4,
+        "||" => 3,
+        ".." | "..=" => 2,
+        _ => 1,
+    }
+}
+
+impl From<&str> for Expr {
+    fn from(src: &str) -> Self {
+        Expr::Raw(src.to_string())
+    }
+}
+
+impl From<String> for Expr {
+    fn from(src: String) -> Self {
+        Expr::Raw(src)
+    }
+}
+
+impl From<Block> for Expr {
+    fn from(block: Block) -> Self {
+        Expr::Block(block)
+    }
+}
+
+fn join(exprs: &[Expr]) -> String {
+    exprs
+        .iter()
+        .map(SrcCode::generate)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+impl SrcCode for Expr {
+    fn generate(&self) -> String {
+        match self {
+            Expr::Raw(s) | Expr::Path(s) => s.clone(),
+            Expr::Call { func, args } => format!("{}({})", func.generate_as_operand(), join(args)),
+            Expr::MethodCall {
+                receiver,
+                method,
+                args,
+            } => format!(
+                "{}.{}({})",
+                receiver.generate_as_operand(),
+                method,
+                join(args)
+            ),
+            Expr::Field { base, member } => format!("{}.{}", base.generate_as_operand(), member),
+            Expr::Index { base, index } => {
+                format!("{}[{}]", base.generate_as_operand(), index.generate())
+            }
+            Expr::Macro {
+                path,
+                tokens,
+                delimiter,
+            } => match delimiter {
+                MacroDelimiter::Paren => format!("{}!({})", path, tokens),
+                MacroDelimiter::Bracket => format!("{}![{}]", path, tokens),
+                MacroDelimiter::Brace => format!("{}! {{ {} }}", path, tokens),
+            },
+            Expr::Reference { is_mut, expr } => format!(
+                "&{}{}",
+                if *is_mut { "mut " } else { "" },
+                expr.generate_as_operand()
+            ),
+            Expr::Unary { op, expr } => format!("{}{}", op, expr.generate_as_operand()),
+            Expr::Binary { lhs, op, rhs } => {
+                format!(
+                    "{} {} {}",
+                    lhs.generate_as_binary_operand(op, true),
+                    op,
+                    rhs.generate_as_binary_operand(op, false)
+                )
+            }
+            Expr::Cast { expr, ty } => format!("{} as {}", expr.generate_as_operand(), ty),
+            Expr::Try(expr) => format!("{}?", expr.generate_as_operand()),
+            Expr::Return(None) => "return".to_string(),
+            Expr::Return(Some(expr)) => format!("return {}", expr.generate()),
+            Expr::Break(None) => "break".to_string(),
+            Expr::Break(Some(expr)) => format!("break {}", expr.generate()),
+            Expr::Continue => "continue".to_string(),
+            Expr::Paren(expr) => format!("({})", expr.generate()),
+            Expr::Tuple(exprs) if exprs.len() == 1 => format!("({},)", exprs[0].generate()),
+            Expr::Tuple(exprs) => format!("({})", join(exprs)),
+            Expr::Array(exprs) => format!("[{}]", join(exprs)),
+            Expr::Struct { path, fields, base } => {
+                let mut parts = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name, value.generate()))
+                    .collect::<Vec<String>>();
+                if let Some(base) = base {
+                    parts.push(format!("..{}", base.generate()));
+                }
+                format!("{} {{ {} }}", path, parts.join(", "))
+            }
+            Expr::Closure {
+                is_move,
+                params,
+                body,
+            } => format!(
+                "{}|{}| {}",
+                if *is_move { "move " } else { "" },
+                params.join(", "),
+                body.generate()
+            ),
+            Expr::Block(block) => block.generate(),
+            Expr::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                let mut src = format!("if {}\n{}", cond.generate_as_condition(), then.generate());
+                match otherwise.as_deref() {
+                    Some(otherwise @ Expr::If { .. }) | Some(otherwise @ Expr::Block(_)) => {
+                        src.push_str(&format!("\nelse {}", otherwise.generate()))
+                    }
+                    Some(otherwise) => {
+                        src.push_str(&format!("\nelse\n{{\n{}\n}}", otherwise.generate()))
+                    }
+                    None => (),
+                }
+                src
+            }
+            Expr::Match { expr, arms } => format!(
+                "match {}\n{{\n{}\n}}",
+                expr.generate_as_condition(),
+                arms.to_src_vec().join("\n")
+            ),
+            Expr::Loop(body) => format!("loop\n{}", body.generate()),
+            Expr::While { cond, body } => format!(
+                "while {}\n{}",
+                cond.generate_as_condition(),
+                body.generate()
+            ),
+            Expr::For { pat, iter, body } => {
+                format!(
+                    "for {} in {}\n{}",
+                    pat,
+                    iter.generate_as_condition(),
+                    body.generate()
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,360 @@
+//!
+//!
+//! Create `extern` blocks declaring foreign functions, statics and types.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents an `extern "ABI" { ... }` block
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let block = ExternBlock::new("C")
+///     .add_attribute("#[link(name = \"m\")]")
+///     .add_function(
+///         FunctionSignature::new("cos")
+///             .add_parameter(Parameter::new("x", "f64"))
+///             .set_return_ty(Some("f64"))
+///             .to_owned()
+///     )
+///     .add_static(ForeignStatic::new("errno", "i32"))
+///     .to_owned();
+///
+/// let expected = r#"
+///     #[link(name = "m")]
+///     extern "C"
+///     {
+///         static errno: i32;
+///         fn cos(x: f64) -> f64;
+///     }
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&block.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ExternBlock {
+    abi: String,
+    pub(crate) functions: Vec<FunctionSignature>,
+    pub(crate) statics: Vec<ForeignStatic>,
+    pub(crate) types: Vec<ForeignType>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl ExternBlock {
+    /// Create a new `extern` block for the given ABI, ie. `"C"` or `"system"`
+    pub fn new(abi: impl ToString) -> Self {
+        Self {
+            abi: abi.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Add a foreign function declaration to this block
+    pub fn add_function(&mut self, signature: FunctionSignature) -> &mut Self {
+        self.functions.push(signature);
+        self
+    }
+
+    /// Add a foreign function declaration to this block
+    pub fn with_function(mut self, signature: FunctionSignature) -> Self {
+        self.add_function(signature);
+        self
+    }
+
+    /// Add a foreign static declaration to this block
+    pub fn add_static(&mut self, stat1c: ForeignStatic) -> &mut Self {
+        self.statics.push(stat1c);
+        self
+    }
+
+    /// Add a foreign static declaration to this block
+    pub fn with_static(mut self, stat1c: ForeignStatic) -> Self {
+        self.add_static(stat1c);
+        self
+    }
+
+    /// Add an opaque foreign type declaration to this block
+    pub fn add_type(&mut self, ty: ForeignType) -> &mut Self {
+        self.types.push(ty);
+        self
+    }
+
+    /// Add an opaque foreign type declaration to this block
+    pub fn with_type(mut self, ty: ForeignType) -> Self {
+        self.add_type(ty);
+        self
+    }
+
+    /// The ABI of this block, ie. `C`
+    pub fn abi(&self) -> &str {
+        self.abi.as_str()
+    }
+
+    /// The foreign functions of this block
+    pub fn functions(&self) -> &[FunctionSignature] {
+        self.functions.as_slice()
+    }
+
+    /// The foreign statics of this block
+    pub fn statics(&self) -> &[ForeignStatic] {
+        self.statics.as_slice()
+    }
+
+    /// The foreign types of this block
+    pub fn types(&self) -> &[ForeignType] {
+        self.types.as_slice()
+    }
+
+    /// The documentation lines of this block
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this block
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this block
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for ExternBlock {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for ExternBlock {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for ExternBlock {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for ExternBlock {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        extern "{{ self.abi }}"
+        {
+            {% for ty in types %}{{ ty }}{% endfor %}
+            {% for stat1c in statics %}{{ stat1c }}{% endfor %}
+            {% for function in functions %}{{ function }};
+            {% endfor %}
+        }
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("types", &self.types.to_src_vec());
+        context.insert("statics", &self.statics.to_src_vec());
+        context.insert("functions", &self.functions.to_src_vec());
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
+
+/// Represents a `static` declared within an `ExternBlock`, which has no value.
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ForeignStatic {
+    name: String,
+    is_pub: bool,
+    is_mut: bool,
+    ty: String,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl ForeignStatic {
+    /// Create a new `ForeignStatic`
+    pub fn new(name: impl ToString, ty: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set if this static is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this static is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// Set if this is a `static mut`
+    pub fn set_is_mut(&mut self, is_mut: bool) -> &mut Self {
+        self.is_mut = is_mut;
+        self
+    }
+
+    /// Set if this is a `static mut`
+    pub fn with_is_mut(mut self, is_mut: bool) -> Self {
+        self.set_is_mut(is_mut);
+        self
+    }
+
+    /// The name of this static
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// If this static is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// If this static is `mut`
+    pub fn is_mut(&self) -> bool {
+        self.is_mut
+    }
+
+    /// The type of this static
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// The documentation lines of this static
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this static
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+}
+
+impl internal::Attributes for ForeignStatic {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Docs for ForeignStatic {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for ForeignStatic {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}static {% if self.is_mut %}mut {% endif %}{{ self.name }}: {{ self.ty }};
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("attributes", &self.attributes.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
+
+/// Represents an opaque `type` declared within an `ExternBlock`.
+///
+/// Note foreign types currently require the nightly `extern_types` feature.
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ForeignType {
+    name: String,
+    is_pub: bool,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+}
+
+impl ForeignType {
+    /// Create a new `ForeignType`
+    pub fn new(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set if this type is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this type is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this type
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// If this type is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The documentation lines of this type
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this type
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+}
+
+impl internal::Attributes for ForeignType {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Docs for ForeignType {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for ForeignType {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}type {{ self.name }};
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("attributes", &self.attributes.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
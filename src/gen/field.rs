@@ -21,12 +21,13 @@ use crate::*;
 ///
 /// assert_eq!(norm_whitespace(&field), norm_whitespace(expected));
 /// ```
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Field {
     name: String,
     is_pub: bool,
     ty: String,
     attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
     docs: Vec<String>,
 }
 
@@ -40,11 +41,90 @@ impl Field {
         }
     }
 
+    /// Create a new `Field`, escaping `name` if it isn't a valid identifier. If the name
+    /// had to be changed, the policy's rename attribute is added to the field.
+    pub fn new_escaped(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        let ident = policy.escape(name);
+        let mut field = Self::new(&ident, ty);
+        field.attributes.extend(policy.rename_attribute(&ident));
+        field
+    }
+
+    /// Create a new `Field`, converting `name` into `snake_case` and escaping it if it
+    /// isn't a valid identifier. If the name had to be changed, the policy's rename
+    /// attribute is added to the field.
+    pub fn new_from_external_name(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        let ident = policy.convert(name, Case::Snake);
+        let mut field = Self::new(&ident, ty);
+        field.attributes.extend(policy.rename_attribute(&ident));
+        field
+    }
+
     /// Set if this is public
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
+
+    /// Set if this is public
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this field
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this field
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this field
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this field is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The type of this field
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// Set the type of this field
+    pub fn set_ty(&mut self, ty: impl ToString) -> &mut Self {
+        self.ty = ty.to_string();
+        self
+    }
+
+    /// Set the type of this field
+    pub fn with_ty(mut self, ty: impl ToString) -> Self {
+        self.set_ty(ty);
+        self
+    }
+
+    /// The documentation lines of this field
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this field
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this field
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
 }
 
 impl internal::Attributes for Field {
@@ -53,6 +133,12 @@ impl internal::Attributes for Field {
     }
 }
 
+impl internal::Comments for Field {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
 impl internal::Docs for Field {
     fn docs_mut(&mut self) -> &mut Vec<String> {
         &mut self.docs
@@ -62,6 +148,8 @@ impl internal::Docs for Field {
 impl SrcCode for Field {
     fn generate(&self) -> String {
         let template = r#"
+            {{ comments | join(sep="
+            ") }}
             {{ field.docs | join(sep="
             ") }}
             {% for attribute in attributes %}{{ attribute }}{% endfor %}
@@ -70,6 +158,7 @@ impl SrcCode for Field {
         let mut context = Context::new();
         context.insert("field", &self);
         context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &context, false).unwrap()
     }
 }
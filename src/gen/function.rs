@@ -9,9 +9,10 @@
 use serde::Serialize;
 use tera::{Context, Tera};
 
-use crate::internal::Generics;
 use crate::traits::SrcCode;
-use crate::{internal, Attribute, AttributeExt, Generic, SrcCodeVec};
+use crate::{
+    internal, Attribute, AttributeExt, Block, Case, Comment, Generic, IdentPolicy, SrcCodeVec,
+};
 
 /// Represents a function or method.
 ///
@@ -37,22 +38,24 @@ use crate::{internal, Attribute, AttributeExt, Generic, SrcCodeVec};
 ///     .set_body("bar + 2")
 ///     .to_owned();
 /// ```
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Function {
-    signature: FunctionSignature,
+    pub(crate) signature: FunctionSignature,
     body: FunctionBody,
 }
 
 /// Represents a function/method signature in source code
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FunctionSignature {
     name: String,
     is_pub: bool,
     is_async: bool,
-    parameters: Vec<Parameter>,
+    is_variadic: bool,
+    pub(crate) parameters: Vec<Parameter>,
     generics: Vec<Generic>,
     return_ty: Option<String>,
     attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
     docs: Vec<String>,
 }
 
@@ -71,29 +74,141 @@ impl FunctionSignature {
         self
     }
 
+    /// Set this function as `async`
+    pub fn with_is_async(mut self, is_async: bool) -> Self {
+        self.set_is_async(is_async);
+        self
+    }
+
+    /// Set this function as C-variadic, ie. `fn printf(format: *const c_char, ...)`.
+    /// Only foreign functions within an `ExternBlock` may be variadic, a variadic
+    /// `Function` is rejected by `generate_checked`.
+    pub fn set_is_variadic(&mut self, is_variadic: bool) -> &mut Self {
+        self.is_variadic = is_variadic;
+        self
+    }
+
+    /// Set this function as C-variadic, ie. `fn printf(format: *const c_char, ...)`.
+    /// Only foreign functions within an `ExternBlock` may be variadic, a variadic
+    /// `Function` is rejected by `generate_checked`.
+    pub fn with_is_variadic(mut self, is_variadic: bool) -> Self {
+        self.set_is_variadic(is_variadic);
+        self
+    }
+
     /// Add a parameter to this signature
     pub fn add_parameter(&mut self, param: Parameter) -> &mut Self {
         self.parameters.push(param);
         self
     }
 
+    /// Add a parameter to this signature
+    pub fn with_parameter(mut self, param: Parameter) -> Self {
+        self.add_parameter(param);
+        self
+    }
+
     /// Set a return type, if `None` will result in `()` type.
     pub fn set_return_ty(&mut self, ty: Option<impl ToString>) -> &mut Self {
         self.return_ty = ty.map(|s| s.to_string());
         self
     }
 
+    /// Set a return type, if `None` will result in `()` type.
+    pub fn with_return_ty(mut self, ty: Option<impl ToString>) -> Self {
+        self.set_return_ty(ty);
+        self
+    }
+
     /// Set if this signature should be prefixed with `pub`
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
 
+    /// Set if this signature should be prefixed with `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
     /// Set the name of this function.
     pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
         self.name = name.to_string();
         self
     }
+
+    /// Set the name of this function.
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// The name of this function
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// If this function is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// If this function is `async`
+    pub fn is_async(&self) -> bool {
+        self.is_async
+    }
+
+    /// If this function is variadic
+    pub fn is_variadic(&self) -> bool {
+        self.is_variadic
+    }
+
+    /// The parameters of this function
+    pub fn parameters(&self) -> &[Parameter] {
+        self.parameters.as_slice()
+    }
+
+    /// The generics of this function
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The return type of this function, `None` for `()`
+    pub fn return_ty(&self) -> Option<&str> {
+        self.return_ty.as_deref()
+    }
+
+    /// The documentation lines of this function
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this function
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this function
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+
+    /// Get a reference to the parameter named `name`
+    pub fn get_parameter(&self, name: &str) -> Option<&Parameter> {
+        self.parameters.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the parameter named `name`
+    pub fn get_parameter_mut(&mut self, name: &str) -> Option<&mut Parameter> {
+        self.parameters.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the parameter named `name`, returning it if it existed
+    pub fn remove_parameter(&mut self, name: &str) -> Option<Parameter> {
+        let idx = self.parameters.iter().position(|v| v.name() == name)?;
+        Some(self.parameters.remove(idx))
+    }
 }
 
 impl internal::Attributes for FunctionSignature {
@@ -102,6 +217,12 @@ impl internal::Attributes for FunctionSignature {
     }
 }
 
+impl internal::Comments for FunctionSignature {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
 impl internal::Generics for FunctionSignature {
     fn generics_mut(&mut self) -> &mut Vec<Generic> {
         &mut self.generics
@@ -111,9 +232,17 @@ impl internal::Generics for FunctionSignature {
     }
 }
 
+impl internal::Docs for FunctionSignature {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl SrcCode for FunctionSignature {
     fn generate(&self) -> String {
         let template = r#"
+        {{ comments | join(sep="
+        ") }}
         {{ self.docs | join(sep="
         ") }}
         {{ attributes | join(sep="
@@ -139,13 +268,18 @@ impl SrcCode for FunctionSignature {
                 .collect::<Vec<&str>>(),
         );
         context.insert("attributes", &self.attributes.to_src_vec());
-        context.insert("parameters", &self.parameters.to_src_vec());
+        let mut parameters = self.parameters.to_src_vec();
+        if self.is_variadic {
+            parameters.push("...".to_string());
+        }
+        context.insert("parameters", &parameters);
+        context.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &context, false).unwrap()
     }
 }
 
 /// Represents the function/method's body
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FunctionBody {
     body: Vec<String>,
     attributes: Vec<Attribute>,
@@ -187,31 +321,85 @@ impl Function {
             ..Self::default()
         }
     }
+    /// Create a new function, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+    /// Create a new function, converting `name` into `snake_case` and escaping it if it
+    /// isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::Snake))
+    }
+    /// Set the name of this function
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.signature.set_name(name);
+        self
+    }
+    /// Set the name of this function
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
     /// Add a new parameter to this function
     pub fn add_parameter(&mut self, param: Parameter) -> &mut Self {
         self.signature.parameters.push(param);
         self
     }
+    /// Add a new parameter to this function
+    pub fn with_parameter(mut self, param: Parameter) -> Self {
+        self.add_parameter(param);
+        self
+    }
     /// Set the return type of this function
     pub fn set_return_ty(&mut self, ty: impl ToString) -> &mut Self {
         self.signature.return_ty = Some(ty.to_string());
         self
     }
+    /// Set the return type of this function
+    pub fn with_return_ty(mut self, ty: impl ToString) -> Self {
+        self.set_return_ty(ty);
+        self
+    }
     /// Set if this function is public
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.signature.set_is_pub(is_pub);
         self
     }
+    /// Set if this function is public
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
     /// Set if this function is async
     pub fn set_is_async(&mut self, is_async: bool) -> &mut Self {
         self.signature.set_is_async(is_async);
         self
     }
+    /// Set if this function is async
+    pub fn with_is_async(mut self, is_async: bool) -> Self {
+        self.set_is_async(is_async);
+        self
+    }
     /// Set the body of the function, this should be valid Rust source code syntax.
     pub fn set_body(&mut self, body: impl SrcCode) -> &mut Self {
         self.body.body = vec![body.generate()];
         self
     }
+    /// Set the body of the function, this should be valid Rust source code syntax.
+    pub fn with_body(mut self, body: impl SrcCode) -> Self {
+        self.set_body(body);
+        self
+    }
+    /// Set the body of the function to the statements of `block`
+    pub fn set_block(&mut self, block: Block) -> &mut Self {
+        self.body.body = block.generate_stmts();
+        self
+    }
+    /// Set the body of the function to the statements of `block`
+    pub fn with_block(mut self, block: Block) -> Self {
+        self.set_block(block);
+        self
+    }
     /// Push anything which implements `SrcCode` into the body of the function
     pub fn push_into_body(&mut self, src: impl SrcCode) -> &mut Self {
         self.body.body.push(src.generate());
@@ -222,6 +410,77 @@ impl Function {
         self.body.add_attribute(attribute);
         self
     }
+    /// Add an attribute before the body of the function
+    pub fn with_body_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.add_body_attribute(attribute);
+        self
+    }
+    /// The signature of this function
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+    /// The name of this function
+    pub fn name(&self) -> &str {
+        self.signature.name()
+    }
+    /// If this function is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.signature.is_pub
+    }
+    /// If this function is `async`
+    pub fn is_async(&self) -> bool {
+        self.signature.is_async
+    }
+    /// The parameters of this function
+    pub fn parameters(&self) -> &[Parameter] {
+        self.signature.parameters()
+    }
+    /// The generics of this function
+    pub fn generics(&self) -> &[Generic] {
+        self.signature.generics.as_slice()
+    }
+    /// The return type of this function, `None` for `()`
+    pub fn return_ty(&self) -> Option<&str> {
+        self.signature.return_ty()
+    }
+    /// The body of this function, as lines of source code
+    pub fn body(&self) -> &[String] {
+        self.body.body.as_slice()
+    }
+    /// The documentation lines of this function
+    pub fn docs(&self) -> &[String] {
+        self.signature.docs()
+    }
+    /// The attributes of this function
+    pub fn attributes(&self) -> &[Attribute] {
+        self.signature.attributes()
+    }
+    /// The comments placed before this function
+    pub fn comments(&self) -> &[Comment] {
+        self.signature.comments()
+    }
+    /// Get a reference to the parameter named `name`
+    pub fn get_parameter(&self, name: &str) -> Option<&Parameter> {
+        self.signature.get_parameter(name)
+    }
+    /// Get a mutable reference to the parameter named `name`
+    pub fn get_parameter_mut(&mut self, name: &str) -> Option<&mut Parameter> {
+        self.signature.get_parameter_mut(name)
+    }
+    /// Remove the parameter named `name`, returning it if it existed
+    pub fn remove_parameter(&mut self, name: &str) -> Option<Parameter> {
+        self.signature.remove_parameter(name)
+    }
+}
+
+impl From<FunctionSignature> for Function {
+    /// Create a function with an empty body from a `FunctionSignature`
+    fn from(signature: FunctionSignature) -> Self {
+        Self {
+            signature,
+            ..Self::default()
+        }
+    }
 }
 
 impl internal::Attributes for Function {
@@ -239,6 +498,12 @@ impl internal::Generics for Function {
     }
 }
 
+impl internal::Comments for Function {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.signature.comments
+    }
+}
+
 impl internal::Docs for Function {
     fn docs_mut(&mut self) -> &mut Vec<String> {
         &mut self.signature.docs
@@ -246,7 +511,7 @@ impl internal::Docs for Function {
 }
 
 /// Represents a single parameter to a `Function`
-#[derive(Serialize, Default, Clone)]
+#[derive(Serialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Parameter {
     name: String,
     ty: String,
@@ -272,6 +537,44 @@ impl Parameter {
             ..Self::default()
         }
     }
+
+    /// Create a new parameter, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name), ty)
+    }
+
+    /// Create a new parameter, converting `name` into `snake_case` and escaping it if it
+    /// isn't a valid identifier
+    pub fn new_from_external_name(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::Snake), ty)
+    }
+
+    /// The name of this parameter
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this parameter
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this parameter
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// The type of this parameter
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// The attributes of this parameter
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
 }
 
 impl internal::Attributes for Parameter {
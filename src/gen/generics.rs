@@ -19,7 +19,7 @@ use crate::traits::SrcCode;
 ///
 /// let generic = Generic::new("T").add_trait_bound("ToString");
 /// ```
-#[derive(Serialize, Deserialize, Default, Clone)]
+#[derive(Serialize, Deserialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Generic {
     name: String,
     traits: Vec<String>,
@@ -38,6 +38,11 @@ impl Generic {
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
+
+    /// The trait bounds of this generic
+    pub fn traits(&self) -> &[String] {
+        self.traits.as_slice()
+    }
 }
 
 impl internal::TraitBounds for Generic {
@@ -0,0 +1,272 @@
+//!
+//!
+//! Create headers for generated source files, ie. a license banner, an `@generated`
+//! marker and inner attributes, optionally with a hash of the file's contents to
+//! detect hand edits.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let header = FileHeader::new("schema-gen")
+//!     .set_input(Some("schema.json"))
+//!     .set_license(Some("SPDX-License-Identifier: MIT"))
+//!     .set_hashed(true)
+//!     .add_attribute("#![allow(clippy::all)]")
+//!     .to_owned();
+//!
+//! let src_code = Module::new("lib")
+//!     .set_header(Some(header))
+//!     .add_struct(Struct::new("User"))
+//!     .generate_file();
+//!
+//! assert!(src_code.starts_with("// SPDX-License-Identifier: MIT\n// @generated by schema-gen from schema.json — DO NOT EDIT\n"));
+//! assert_eq!(FileHeader::check(&src_code), HashStatus::Valid);
+//!
+//! let edited = src_code.replace("User", "Account");
+//! assert_eq!(FileHeader::check(&edited), HashStatus::Modified);
+//! ```
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::{internal, Attribute, SrcCode, SrcCodeVec, TemplateError};
+
+/// Default template of the `@generated` marker
+const DEFAULT_TEMPLATE: &str =
+    "// @generated by {{ tool }}{% if input %} from {{ input }}{% endif %} — DO NOT EDIT";
+
+/// Prefix of the line holding the content hash
+const HASH_PREFIX: &str = "// @generated-hash: ";
+
+/// Represents the header of a generated source file
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileHeader {
+    tool: String,
+    input: Option<String>,
+    license: Option<String>,
+    template: String,
+    is_hashed: bool,
+    attributes: Vec<Attribute>,
+}
+
+/// Result of checking the content hash of a generated file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HashStatus {
+    /// The file has no content hash
+    Missing,
+    /// The contents match the hash
+    Valid,
+    /// The contents were changed after being generated
+    Modified,
+}
+
+impl FileHeader {
+    /// Create a new header for files generated by `tool`. Control characters, ie. a
+    /// newline, are escaped so `tool` can't end the comment it's written in.
+    pub fn new(tool: impl ToString) -> Self {
+        Self {
+            tool: escape_control(&tool.to_string()),
+            input: None,
+            license: None,
+            template: DEFAULT_TEMPLATE.to_string(),
+            is_hashed: false,
+            attributes: vec![],
+        }
+    }
+
+    /// Set the input the file was generated from, ie. a schema file. Control characters
+    /// are escaped, as within `tool`.
+    pub fn set_input(&mut self, input: Option<impl ToString>) -> &mut Self {
+        self.input = input.map(|i| escape_control(&i.to_string()));
+        self
+    }
+
+    /// Set the input the file was generated from, ie. a schema file. Control characters
+    /// are escaped, as within `tool`.
+    pub fn with_input(mut self, input: Option<impl ToString>) -> Self {
+        self.set_input(input);
+        self
+    }
+
+    /// Set the license banner, given as plain text which may span multiple lines
+    pub fn set_license(&mut self, license: Option<impl ToString>) -> &mut Self {
+        self.license = license.map(|l| l.to_string());
+        self
+    }
+
+    /// Set the license banner, given as plain text which may span multiple lines
+    pub fn with_license(mut self, license: Option<impl ToString>) -> Self {
+        self.set_license(license);
+        self
+    }
+
+    /// Set the template of the `@generated` marker, rendering as `//` comment(s).
+    /// `tool` and `input` are available to the template, `input` may be unset.
+    ///
+    /// Fails if the template can't be rendered or renders anything but comments.
+    pub fn set_template(&mut self, template: impl ToString) -> Result<&mut Self, TemplateError> {
+        let template = template.to_string();
+        for input in &[None, Some("input")] {
+            let generated = render_template(&template, &self.tool, *input)?;
+            if generated
+                .lines()
+                .any(|line| !line.is_empty() && !line.trim_start().starts_with("//"))
+            {
+                return Err(TemplateError::new(
+                    template,
+                    "expected to render `//` comments only",
+                ));
+            }
+        }
+        self.template = template;
+        Ok(self)
+    }
+
+    /// Set the template of the `@generated` marker, rendering as `//` comment(s).
+    /// `tool` and `input` are available to the template, `input` may be unset.
+    ///
+    /// Fails if the template can't be rendered or renders anything but comments.
+    pub fn with_template(mut self, template: impl ToString) -> Result<Self, TemplateError> {
+        self.set_template(template)?;
+        Ok(self)
+    }
+
+    /// Set if a hash of the contents following the header is included
+    pub fn set_hashed(&mut self, is_hashed: bool) -> &mut Self {
+        self.is_hashed = is_hashed;
+        self
+    }
+
+    /// Set if a hash of the contents following the header is included
+    pub fn with_hashed(mut self, is_hashed: bool) -> Self {
+        self.set_hashed(is_hashed);
+        self
+    }
+
+    /// Render this header followed by `contents`
+    pub fn apply(&self, contents: &str) -> String {
+        let template = r#"{% if license %}{{ license }}
+{% endif %}{{ generated }}
+"#;
+        let generated = render_template(&self.template, &self.tool, self.input.as_deref())
+            .expect("header template is validated when set");
+        let license = self.license.as_ref().map(|l| {
+            l.lines()
+                .map(|line| format!("// {}", line).trim_end().to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        });
+
+        let mut ctx = Context::new();
+        ctx.insert("license", &license);
+        ctx.insert("generated", &generated);
+        let mut src = Tera::one_off(template, &ctx, false).unwrap();
+
+        let mut body = self.attributes.to_src_vec().join("\n");
+        if !body.is_empty() {
+            body.push('\n');
+        }
+        body.push_str(contents);
+        if self.is_hashed {
+            src.push_str(&format!("{}{:016x}\n", HASH_PREFIX, fnv1a(&body)));
+        }
+        src.push_str(&body);
+        src
+    }
+
+    /// Check the content hash of a generated file, detecting if it was edited.
+    pub fn check(src: &str) -> HashStatus {
+        let start = match src.find(HASH_PREFIX) {
+            Some(start) => start + HASH_PREFIX.len(),
+            None => return HashStatus::Missing,
+        };
+        let (hash, body) = match src[start..].split_once('\n') {
+            Some(split) => split,
+            None => (&src[start..], ""),
+        };
+        match u64::from_str_radix(hash.trim(), 16) {
+            Ok(hash) if hash == fnv1a(body) => HashStatus::Valid,
+            _ => HashStatus::Modified,
+        }
+    }
+
+    /// The tool generating the file
+    pub fn tool(&self) -> &str {
+        self.tool.as_str()
+    }
+
+    /// The input the file was generated from
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    /// The license banner, as plain text
+    pub fn license(&self) -> Option<&str> {
+        self.license.as_deref()
+    }
+
+    /// The template of the `@generated` marker
+    pub fn template(&self) -> &str {
+        self.template.as_str()
+    }
+
+    /// If a hash of the contents is included
+    pub fn is_hashed(&self) -> bool {
+        self.is_hashed
+    }
+
+    /// The inner attributes of this header
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+}
+
+impl internal::Attributes for FileHeader {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+    fn inner_attributes(&self) -> bool {
+        true
+    }
+}
+
+impl SrcCode for FileHeader {
+    fn generate(&self) -> String {
+        self.apply("")
+    }
+}
+
+/// Render the `@generated` marker `template`
+fn render_template(
+    template: &str,
+    tool: &str,
+    input: Option<&str>,
+) -> Result<String, TemplateError> {
+    let mut ctx = Context::new();
+    ctx.insert("tool", tool);
+    ctx.insert("input", &input);
+    Tera::one_off(template, &ctx, false).map_err(|err| TemplateError::new(template, err))
+}
+
+/// Escape the control characters of `text`, ie. a newline as `\n`, so it stays on a
+/// single line
+fn escape_control(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_control() {
+                c.escape_default().to_string()
+            } else {
+                c.to_string()
+            }
+        })
+        .collect()
+}
+
+/// 64 bit FNV-1a hash, stable across platforms and releases unlike `DefaultHasher`
+fn fnv1a(src: &str) -> u64 {
+    src.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
@@ -4,9 +4,10 @@
 
 use serde::Serialize;
 
-use crate::internal::Generics;
 use crate::traits::SrcCode;
-use crate::{internal, AssociatedTypeDefinition, Function, Generic, SrcCodeVec, Trait};
+use crate::{
+    internal, AssociatedTypeDefinition, Attribute, Comment, Function, Generic, SrcCodeVec, Trait,
+};
 use tera::{Context, Tera};
 
 /// Represents an `impl` block
@@ -31,13 +32,17 @@ use tera::{Context, Tera};
 ///             .to_owned()
 ///     );
 /// ```
-#[derive(Serialize, Default, Clone)]
+#[derive(Serialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Impl {
     generics: Vec<Generic>,
     impl_trait: Option<Trait>,
-    functions: Vec<Function>,
+    pub(crate) functions: Vec<Function>,
     obj_name: String,
-    associated_types: Vec<AssociatedTypeDefinition>,
+    pub(crate) associated_types: Vec<AssociatedTypeDefinition>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+    pub(crate) anchored: Vec<internal::Anchored>,
 }
 
 impl Impl {
@@ -55,17 +60,100 @@ impl Impl {
         self
     }
 
+    /// Set if this `impl` is implementing a `Trait` for an object.
+    pub fn with_impl_trait(mut self, impl_trait: Option<Trait>) -> Self {
+        self.set_impl_trait(impl_trait);
+        self
+    }
+
     /// Add a function to this `Impl` block
     pub fn add_function(&mut self, func: Function) -> &mut Self {
         self.functions.push(func);
+        internal::anchor_pending(&mut self.anchored, ("fn", self.functions.len() - 1));
+        self
+    }
+
+    /// Add a function to this `Impl` block
+    pub fn with_function(mut self, func: Function) -> Self {
+        self.add_function(func);
         self
     }
 
     /// Add a associated type to this `Impl` block
     pub fn add_associated_type(&mut self, associated_type: AssociatedTypeDefinition) -> &mut Self {
         self.associated_types.push(associated_type);
+        let idx = self.associated_types.len() - 1;
+        internal::anchor_pending(&mut self.anchored, ("type", idx));
         self
     }
+
+    /// Add a associated type to this `Impl` block
+    pub fn with_associated_type(mut self, associated_type: AssociatedTypeDefinition) -> Self {
+        self.add_associated_type(associated_type);
+        self
+    }
+
+    /// The name of the type this is implemented for
+    pub fn obj_name(&self) -> &str {
+        self.obj_name.as_str()
+    }
+
+    /// The trait being implemented, if any
+    pub fn impl_trait(&self) -> Option<&Trait> {
+        self.impl_trait.as_ref()
+    }
+
+    /// The generics of this impl
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The functions of this impl
+    pub fn functions(&self) -> &[Function] {
+        self.functions.as_slice()
+    }
+
+    /// The associated types of this impl
+    pub fn associated_types(&self) -> &[AssociatedTypeDefinition] {
+        self.associated_types.as_slice()
+    }
+
+    /// The documentation lines of this impl
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this impl
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this impl
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+
+    /// Get a reference to the function named `name`
+    pub fn get_function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the function named `name`
+    pub fn get_function_mut(&mut self, name: &str) -> Option<&mut Function> {
+        self.functions.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the function named `name`, returning it if it existed
+    pub fn remove_function(&mut self, name: &str) -> Option<Function> {
+        let idx = self.functions.iter().position(|v| v.name() == name)?;
+        let removed = self.functions.remove(idx);
+        let counts = [
+            ("type", self.associated_types.len()),
+            ("fn", self.functions.len()),
+        ];
+        internal::remove_anchor(&mut self.anchored, "fn", idx, &counts);
+        Some(removed)
+    }
 }
 
 impl internal::Generics for Impl {
@@ -77,9 +165,59 @@ impl internal::Generics for Impl {
     }
 }
 
+impl internal::Attributes for Impl {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Impl {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl Impl {
+    /// Render the members of this impl in order, along with their keys.
+    fn rendered_items(&self) -> Vec<(internal::ItemKey, String)> {
+        let mut rendered: Vec<(internal::ItemKey, String)> = vec![];
+        rendered.extend(
+            self.associated_types
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("type", i), v.generate())),
+        );
+        rendered.extend(
+            self.functions
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("fn", i), v.generate())),
+        );
+        rendered
+    }
+}
+
+impl internal::Anchors for Impl {
+    fn anchored_mut(&mut self) -> &mut Vec<internal::Anchored> {
+        &mut self.anchored
+    }
+}
+
+impl internal::Docs for Impl {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl SrcCode for Impl {
     fn generate(&self) -> String {
         let template = r#"
+            {{ comments | join(sep="
+            ") }}
+            {{ self.docs | join(sep="
+            ") }}
+            {{ attributes | join(sep="
+            ") }}
             impl{% if has_generics %}<{{ generic_keys | join(sep=", ") }}>{% endif %} {% if has_trait %}{{ trait_name }} for {% endif %}{{ self.obj_name }}{% if has_generics %}<{{ generic_keys | join(sep=", ") }}>{% endif %}
                 {% if has_generics %}
                 where
@@ -87,9 +225,8 @@ impl SrcCode for Impl {
                     {% endfor %}
                 {% endif %}
             {
-                {% for associated_type in associated_types %}{{ associated_type }}{% endfor %}
-                {% for function in functions %}
-                    {{ function }}
+                {% for item in items %}
+                    {{ item }}
                 {% endfor %}
             }
         "#;
@@ -110,8 +247,12 @@ impl SrcCode for Impl {
                 .map(|g| g.name())
                 .collect::<Vec<&str>>(),
         );
-        context.insert("functions", &self.functions.to_src_vec());
-        context.insert("associated_types", &self.associated_types.to_src_vec());
+        context.insert(
+            "items",
+            &internal::interleave(self.rendered_items(), &self.anchored),
+        );
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &context, false).unwrap()
     }
 }
@@ -0,0 +1,317 @@
+//!
+//!
+//! Create `macro_rules!` definitions and item position macro invocations.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents a `macro_rules!` definition.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let m = MacroRules::new("square")
+///     .set_is_exported(true)
+///     .add_rule(MacroRule::new("$x:expr", "$x * $x"))
+///     .to_owned();
+///
+/// let expected = r#"
+///     #[macro_export]
+///     macro_rules! square
+///     {
+///         ($x:expr) => { $x * $x };
+///     }
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MacroRules {
+    name: String,
+    is_exported: bool,
+    rules: Vec<MacroRule>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+/// Represents a single `matcher => transcriber` rule of a `macro_rules!` definition.
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MacroRule {
+    matcher: String,
+    transcriber: String,
+}
+
+impl MacroRules {
+    /// Create a new `MacroRules` definition
+    pub fn new(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set if this macro is marked with `#[macro_export]`
+    pub fn set_is_exported(&mut self, is_exported: bool) -> &mut Self {
+        self.is_exported = is_exported;
+        self
+    }
+
+    /// Set if this macro is marked with `#[macro_export]`
+    pub fn with_is_exported(mut self, is_exported: bool) -> Self {
+        self.set_is_exported(is_exported);
+        self
+    }
+
+    /// Add a rule to this macro, rules are matched in the order they are added.
+    pub fn add_rule(&mut self, rule: MacroRule) -> &mut Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Add a rule to this macro, rules are matched in the order they are added.
+    pub fn with_rule(mut self, rule: MacroRule) -> Self {
+        self.add_rule(rule);
+        self
+    }
+
+    /// The name of this macro
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// If this macro is `#[macro_export]`ed
+    pub fn is_exported(&self) -> bool {
+        self.is_exported
+    }
+
+    /// The rules of this macro
+    pub fn rules(&self) -> &[MacroRule] {
+        self.rules.as_slice()
+    }
+
+    /// The documentation lines of this macro
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this macro
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this macro
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl MacroRule {
+    /// Create a new rule; `matcher` and `transcriber` are expected to be valid
+    /// macro source, without their surrounding delimiters.
+    pub fn new(matcher: impl ToString, transcriber: impl ToString) -> Self {
+        Self {
+            matcher: matcher.to_string(),
+            transcriber: transcriber.to_string(),
+        }
+    }
+    /// The matcher of this rule
+    pub fn matcher(&self) -> &str {
+        self.matcher.as_str()
+    }
+    /// The transcriber of this rule
+    pub fn transcriber(&self) -> &str {
+        self.transcriber.as_str()
+    }
+}
+
+impl internal::Attributes for MacroRules {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for MacroRules {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for MacroRules {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for MacroRule {
+    fn generate(&self) -> String {
+        format!("({}) => {{ {} }};", self.matcher, self.transcriber)
+    }
+}
+
+impl SrcCode for MacroRules {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_exported %}#[macro_export]{% endif %}
+        macro_rules! {{ self.name }}
+        {
+            {% for rule in rules %}{{ rule }}
+            {% endfor %}
+        }
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("rules", &self.rules.to_src_vec());
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
+
+/// The delimiter surrounding the tokens of a `MacroInvocation`
+#[derive(Serialize, Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
+pub enum MacroDelimiter {
+    /// `foo!( ... );`
+    Paren,
+    /// `foo![ ... ];`
+    Bracket,
+    /// `foo! { ... }`
+    #[default]
+    Brace,
+}
+
+/// Represents a macro invocation in item position, ie. `lazy_static! { ... }`
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let m = MacroInvocation::new("include")
+///     .set_delimiter(MacroDelimiter::Paren)
+///     .set_tokens("concat!(env!(\"OUT_DIR\"), \"/gen.rs\")")
+///     .to_owned();
+///
+/// let expected = r#"
+///     include!(concat!(env!("OUT_DIR"), "/gen.rs"));
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MacroInvocation {
+    path: String,
+    delimiter: MacroDelimiter,
+    tokens: String,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl MacroInvocation {
+    /// Create a new invocation of the macro at `path`, without the trailing `!`
+    pub fn new(path: impl ToString) -> Self {
+        Self {
+            path: path.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the delimiter used around the tokens, defaults to `MacroDelimiter::Brace`
+    pub fn set_delimiter(&mut self, delimiter: MacroDelimiter) -> &mut Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Set the delimiter used around the tokens, defaults to `MacroDelimiter::Brace`
+    pub fn with_delimiter(mut self, delimiter: MacroDelimiter) -> Self {
+        self.set_delimiter(delimiter);
+        self
+    }
+
+    /// Set the tokens passed to the macro, expected to be valid Rust source code.
+    pub fn set_tokens(&mut self, tokens: impl SrcCode) -> &mut Self {
+        self.tokens = tokens.generate();
+        self
+    }
+
+    /// Set the tokens passed to the macro, expected to be valid Rust source code.
+    pub fn with_tokens(mut self, tokens: impl SrcCode) -> Self {
+        self.set_tokens(tokens);
+        self
+    }
+
+    /// The path of the invoked macro
+    pub fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    /// The delimiter of this invocation
+    pub fn delimiter(&self) -> MacroDelimiter {
+        self.delimiter
+    }
+
+    /// The tokens passed to the macro
+    pub fn tokens(&self) -> &str {
+        self.tokens.as_str()
+    }
+
+    /// The attributes of this invocation
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this invocation
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for MacroInvocation {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for MacroInvocation {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl SrcCode for MacroInvocation {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {{ self.path }}!{{ invocation }}
+        "#;
+        let invocation = match self.delimiter {
+            MacroDelimiter::Paren => format!("({});", self.tokens),
+            MacroDelimiter::Bracket => format!("[{}];", self.tokens),
+            MacroDelimiter::Brace => format!(
+                "
+        {{
+            {}
+        }}",
+                self.tokens
+            ),
+        };
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("invocation", &invocation);
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
@@ -2,22 +2,42 @@
 
 pub mod associated_types;
 pub mod attribute;
+pub mod block;
+pub mod comment;
+pub mod r#const;
 pub mod r#enum;
+pub mod expr;
+pub mod extern_block;
 pub mod field;
 pub mod function;
 pub mod generics;
+pub mod header;
 pub mod r#impl;
+pub mod macros;
 pub mod module;
+pub mod r#static;
 pub mod r#struct;
 pub mod r#trait;
+pub mod type_alias;
+pub mod union;
 
 pub use associated_types::*;
 pub use attribute::*;
+pub use block::*;
+pub use comment::*;
+pub use expr::*;
+pub use extern_block::*;
 pub use field::*;
 pub use function::*;
 pub use generics::*;
+pub use header::*;
+pub use macros::*;
 pub use module::*;
+pub use r#const::*;
 pub use r#enum::*;
 pub use r#impl::*;
+pub use r#static::*;
 pub use r#struct::*;
 pub use r#trait::*;
+pub use type_alias::*;
+pub use union::*;
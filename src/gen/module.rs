@@ -56,7 +56,7 @@ use tera::{Context, Tera};
 use crate::*;
 use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::hash::Hash;
+use std::hash::{Hash, Hasher};
 
 /// Represent a module of code
 ///
@@ -76,21 +76,39 @@ use std::hash::Hash;
 ///        .add_doc("//! Module level docs")
 ///        .add_use_statement("use super::*;")
 ///        .add_enum(Enum::new("EnumThingy"))
+///        .add_const(Const::new("VERSION", "&str", "\"1.0.0\""))
+///        .add_static(Static::new("COUNTER", "usize", "0").set_is_mut(true).to_owned())
+///        .add_type_alias(TypeAlias::new("Id", "u64"))
+///        .add_union(Union::new("IntOrFloat"))
+///        .add_macro(MacroRules::new("noop").add_rule(MacroRule::new("", "")).to_owned())
+///        .add_macro_invocation(MacroInvocation::new("noop"))
+///        .add_extern_block(ExternBlock::new("C"))
 ///        .to_owned();
 /// ```
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq)]
 pub struct Module {
     name: String,
-    is_pub: bool,
-    traits: Vec<Trait>,
-    functions: Vec<Function>,
-    structs: Vec<Struct>,
-    impls: Vec<Impl>,
-    enums: Vec<Enum>,
-    docs: Vec<String>,
-    sub_modules: HashMap<String, Module>,
-    attributes: Vec<Attribute>,
-    use_stmts: Vec<String>,
+    pub(crate) is_pub: bool,
+    pub(crate) traits: Vec<Trait>,
+    pub(crate) functions: Vec<Function>,
+    pub(crate) structs: Vec<Struct>,
+    pub(crate) impls: Vec<Impl>,
+    pub(crate) enums: Vec<Enum>,
+    pub(crate) unions: Vec<Union>,
+    pub(crate) macros: Vec<MacroRules>,
+    pub(crate) macro_invocations: Vec<MacroInvocation>,
+    pub(crate) consts: Vec<Const>,
+    pub(crate) statics: Vec<Static>,
+    pub(crate) type_aliases: Vec<TypeAlias>,
+    pub(crate) extern_blocks: Vec<ExternBlock>,
+    pub(crate) docs: Vec<String>,
+    pub(crate) sub_modules: HashMap<String, Module>,
+    pub(crate) attributes: Vec<Attribute>,
+    pub(crate) comments: Vec<Comment>,
+    pub(crate) use_stmts: Vec<String>,
+    pub(crate) anchored: Vec<internal::Anchored>,
+    pub(crate) default_derives: Vec<String>,
+    pub(crate) header: Option<FileHeader>,
 }
 
 impl Module {
@@ -101,16 +119,103 @@ impl Module {
             ..Self::default()
         }
     }
+    /// Create a new `Module`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+    /// Create a new `Module`, converting `name` into `snake_case` and escaping it if
+    /// it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::Snake))
+    }
+    /// Add traits derived by every struct, enum and union directly within this module,
+    /// ahead of the traits they derive themselves. Submodules are not affected.
+    ///
+    /// Example
+    /// -------
+    /// ```
+    /// use proffer::*;
+    ///
+    /// let m = Module::new("models")
+    ///     .add_default_derives(["Debug", "Clone"])
+    ///     .add_struct(Struct::new("User").add_derive("Clone").add_derive("Hash").to_owned())
+    ///     .to_owned();
+    ///
+    /// let expected = r#"
+    ///     mod models
+    ///     {
+    ///         #[derive(Debug, Clone, Hash)]
+    ///         struct User
+    ///         {
+    ///         }
+    ///     }
+    /// "#;
+    /// assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+    /// ```
+    pub fn add_default_derives(
+        &mut self,
+        derives: impl IntoIterator<Item = impl ToString>,
+    ) -> &mut Self {
+        self.default_derives
+            .extend(derives.into_iter().map(|d| d.to_string()));
+        self
+    }
+    /// Add traits derived by every struct, enum and union directly within this module,
+    /// ahead of the traits they derive themselves. Submodules are not affected.
+    pub fn with_default_derives(
+        mut self,
+        derives: impl IntoIterator<Item = impl ToString>,
+    ) -> Self {
+        self.add_default_derives(derives);
+        self
+    }
+    /// Set the header rendered at the top of this module when rendered as a file, see
+    /// `Module::generate_file`.
+    pub fn set_header(&mut self, header: Option<FileHeader>) -> &mut Self {
+        self.header = header;
+        self
+    }
+    /// Set the header rendered at the top of this module when rendered as a file, see
+    /// `Module::generate_file`.
+    pub fn with_header(mut self, header: Option<FileHeader>) -> Self {
+        self.set_header(header);
+        self
+    }
+    /// The name of this module
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+    /// Set the name of this module
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+    /// Set the name of this module
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
     /// Set if this module is public
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
-    /// Add submodule
+    /// Set if this module is public
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+    /// Add submodule, replacing any submodule of the same name. Use `Module::merge` to
+    /// combine them instead.
     pub fn add_submodule(&mut self, module: Module) -> &mut Self {
         self.sub_modules.insert(module.name.clone(), module);
         self
     }
+    /// Add submodule
+    pub fn with_submodule(mut self, module: Module) -> Self {
+        self.add_submodule(module);
+        self
+    }
     /// Get a mutable reference to a submodule of this module
     pub fn get_submodule_mut<Q>(&mut self, name: &Q) -> Option<&mut Module>
     where
@@ -130,21 +235,45 @@ impl Module {
     /// Add a function to the module
     pub fn add_function(&mut self, func: Function) -> &mut Self {
         self.functions.push(func);
+        internal::anchor_pending(&mut self.anchored, ("fn", self.functions.len() - 1));
+        self
+    }
+    /// Add a function to the module
+    pub fn with_function(mut self, func: Function) -> Self {
+        self.add_function(func);
         self
     }
     /// Add a trait to the module
     pub fn add_trait(&mut self, tr8t: Trait) -> &mut Self {
         self.traits.push(tr8t);
+        internal::anchor_pending(&mut self.anchored, ("trait", self.traits.len() - 1));
+        self
+    }
+    /// Add a trait to the module
+    pub fn with_trait(mut self, tr8t: Trait) -> Self {
+        self.add_trait(tr8t);
         self
     }
     /// Add a struct to the module
     pub fn add_struct(&mut self, stct: Struct) -> &mut Self {
         self.structs.push(stct);
+        internal::anchor_pending(&mut self.anchored, ("struct", self.structs.len() - 1));
+        self
+    }
+    /// Add a struct to the module
+    pub fn with_struct(mut self, stct: Struct) -> Self {
+        self.add_struct(stct);
         self
     }
     /// Add an impl block to the module
     pub fn add_impl(&mut self, iml: Impl) -> &mut Self {
         self.impls.push(iml);
+        internal::anchor_pending(&mut self.anchored, ("impl", self.impls.len() - 1));
+        self
+    }
+    /// Add an impl block to the module
+    pub fn with_impl(mut self, iml: Impl) -> Self {
+        self.add_impl(iml);
         self
     }
     /// Add a `use` statement or similar module level statements
@@ -152,11 +281,381 @@ impl Module {
         self.use_stmts.push(stmt.to_string());
         self
     }
+    /// Add a `use` statement or similar module level statements
+    pub fn with_use_statement(mut self, stmt: impl ToString) -> Self {
+        self.add_use_statement(stmt);
+        self
+    }
+    /// Add source code rendered verbatim as an item of this module, ie. an item
+    /// which can't be represented otherwise.
+    ///
+    /// Items are rendered grouped by their kind rather than in the order they were
+    /// added, so the raw item is placed before the item added next and moves along with
+    /// it. It is rendered last if no item is added after it.
+    ///
+    /// Example
+    /// -------
+    /// ```
+    /// use proffer::*;
+    ///
+    /// let m = Module::new("foo")
+    ///     .with_struct(Struct::new("Foo"))
+    ///     .with_raw_item("global_asm!(\"nop\");")
+    ///     .with_function(Function::new("bar"))
+    ///     .with_struct(Struct::new("Baz"));
+    ///
+    /// let expected = r#"
+    ///     mod foo
+    ///     {
+    ///         global_asm!("nop");
+    ///         fn bar() -> ()
+    ///         {
+    ///         }
+    ///         struct Foo
+    ///         {
+    ///         }
+    ///         struct Baz
+    ///         {
+    ///         }
+    ///     }
+    /// "#;
+    /// assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+    /// ```
+    pub fn add_raw_item(&mut self, src: impl ToString) -> &mut Self {
+        self.anchored.push(internal::Anchored {
+            before: None,
+            entry: internal::AnchoredEntry::Raw(src.to_string()),
+        });
+        self
+    }
+    /// Add source code rendered verbatim as an item of this module, see
+    /// `Module::add_raw_item`
+    pub fn with_raw_item(mut self, src: impl ToString) -> Self {
+        self.add_raw_item(src);
+        self
+    }
     /// Add an enum to the module
     pub fn add_enum(&mut self, enumm: Enum) -> &mut Self {
         self.enums.push(enumm);
+        internal::anchor_pending(&mut self.anchored, ("enum", self.enums.len() - 1));
+        self
+    }
+    /// Add an enum to the module
+    pub fn with_enum(mut self, enumm: Enum) -> Self {
+        self.add_enum(enumm);
+        self
+    }
+    /// Add a union to the module
+    pub fn add_union(&mut self, union: Union) -> &mut Self {
+        self.unions.push(union);
+        internal::anchor_pending(&mut self.anchored, ("union", self.unions.len() - 1));
+        self
+    }
+    /// Add a union to the module
+    pub fn with_union(mut self, union: Union) -> Self {
+        self.add_union(union);
+        self
+    }
+    /// Add a `macro_rules!` definition to the module
+    pub fn add_macro(&mut self, mac: MacroRules) -> &mut Self {
+        self.macros.push(mac);
+        internal::anchor_pending(&mut self.anchored, ("macro", self.macros.len() - 1));
+        self
+    }
+    /// Add a `macro_rules!` definition to the module
+    pub fn with_macro(mut self, mac: MacroRules) -> Self {
+        self.add_macro(mac);
+        self
+    }
+    /// Add an item position macro invocation to the module
+    pub fn add_macro_invocation(&mut self, invocation: MacroInvocation) -> &mut Self {
+        self.macro_invocations.push(invocation);
+        internal::anchor_pending(
+            &mut self.anchored,
+            ("macro_invocation", self.macro_invocations.len() - 1),
+        );
+        self
+    }
+    /// Add an item position macro invocation to the module
+    pub fn with_macro_invocation(mut self, invocation: MacroInvocation) -> Self {
+        self.add_macro_invocation(invocation);
         self
     }
+    /// Add a `const` item to the module
+    pub fn add_const(&mut self, constant: Const) -> &mut Self {
+        self.consts.push(constant);
+        internal::anchor_pending(&mut self.anchored, ("const", self.consts.len() - 1));
+        self
+    }
+    /// Add a `const` item to the module
+    pub fn with_const(mut self, constant: Const) -> Self {
+        self.add_const(constant);
+        self
+    }
+    /// Add a `static` item to the module
+    pub fn add_static(&mut self, stat1c: Static) -> &mut Self {
+        self.statics.push(stat1c);
+        internal::anchor_pending(&mut self.anchored, ("static", self.statics.len() - 1));
+        self
+    }
+    /// Add a `static` item to the module
+    pub fn with_static(mut self, stat1c: Static) -> Self {
+        self.add_static(stat1c);
+        self
+    }
+    /// Add an `extern` block to the module
+    pub fn add_extern_block(&mut self, block: ExternBlock) -> &mut Self {
+        self.extern_blocks.push(block);
+        internal::anchor_pending(&mut self.anchored, ("extern", self.extern_blocks.len() - 1));
+        self
+    }
+    /// Add an `extern` block to the module
+    pub fn with_extern_block(mut self, block: ExternBlock) -> Self {
+        self.add_extern_block(block);
+        self
+    }
+    /// Add a `type` alias to the module
+    pub fn add_type_alias(&mut self, alias: TypeAlias) -> &mut Self {
+        self.type_aliases.push(alias);
+        internal::anchor_pending(&mut self.anchored, ("type", self.type_aliases.len() - 1));
+        self
+    }
+    /// Add a `type` alias to the module
+    pub fn with_type_alias(mut self, alias: TypeAlias) -> Self {
+        self.add_type_alias(alias);
+        self
+    }
+    /// If this module is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+    /// The traits of this module
+    pub fn traits(&self) -> &[Trait] {
+        self.traits.as_slice()
+    }
+    /// The functions of this module
+    pub fn functions(&self) -> &[Function] {
+        self.functions.as_slice()
+    }
+    /// The structs of this module
+    pub fn structs(&self) -> &[Struct] {
+        self.structs.as_slice()
+    }
+    /// The impls of this module
+    pub fn impls(&self) -> &[Impl] {
+        self.impls.as_slice()
+    }
+    /// The enums of this module
+    pub fn enums(&self) -> &[Enum] {
+        self.enums.as_slice()
+    }
+    /// The unions of this module
+    pub fn unions(&self) -> &[Union] {
+        self.unions.as_slice()
+    }
+    /// The macros of this module
+    pub fn macros(&self) -> &[MacroRules] {
+        self.macros.as_slice()
+    }
+    /// The macro invocations of this module
+    pub fn macro_invocations(&self) -> &[MacroInvocation] {
+        self.macro_invocations.as_slice()
+    }
+    /// The consts of this module
+    pub fn consts(&self) -> &[Const] {
+        self.consts.as_slice()
+    }
+    /// The statics of this module
+    pub fn statics(&self) -> &[Static] {
+        self.statics.as_slice()
+    }
+    /// The type aliases of this module
+    pub fn type_aliases(&self) -> &[TypeAlias] {
+        self.type_aliases.as_slice()
+    }
+    /// The extern blocks of this module
+    pub fn extern_blocks(&self) -> &[ExternBlock] {
+        self.extern_blocks.as_slice()
+    }
+    /// The submodules of this module, sorted by name
+    pub fn submodules(&self) -> Vec<&Module> {
+        let mut sub_modules = self.sub_modules.values().collect::<Vec<&Module>>();
+        sub_modules.sort_by(|a, b| a.name.cmp(&b.name));
+        sub_modules
+    }
+    /// Remove the submodule named `name`, returning it if it existed
+    pub fn remove_submodule(&mut self, name: &str) -> Option<Module> {
+        self.sub_modules.remove(name)
+    }
+    /// The use statements of this module
+    pub fn use_statements(&self) -> &[String] {
+        self.use_stmts.as_slice()
+    }
+    /// The source code rendered verbatim, see `Module::add_raw_item`
+    pub fn raw_items(&self) -> Vec<&str> {
+        self.anchored
+            .iter()
+            .filter_map(|a| match &a.entry {
+                internal::AnchoredEntry::Raw(src) => Some(src.as_str()),
+                internal::AnchoredEntry::Separator(_) => None,
+            })
+            .collect()
+    }
+    /// The traits derived by default, see `Module::add_default_derives`
+    pub fn default_derives(&self) -> &[String] {
+        self.default_derives.as_slice()
+    }
+    /// The header rendered when rendering this module as a file
+    pub fn header(&self) -> Option<&FileHeader> {
+        self.header.as_ref()
+    }
+    /// The documentation lines of this module
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+    /// The attributes of this module
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+    /// The comments placed before this module
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+    /// Get a reference to the trait named `name`
+    pub fn get_trait(&self, name: &str) -> Option<&Trait> {
+        self.traits.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the trait named `name`
+    pub fn get_trait_mut(&mut self, name: &str) -> Option<&mut Trait> {
+        self.traits.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the trait named `name`, returning it if it existed
+    pub fn remove_trait(&mut self, name: &str) -> Option<Trait> {
+        let idx = self.traits.iter().position(|v| v.name() == name)?;
+        let removed = self.traits.remove(idx);
+        self.remove_anchor("trait", idx);
+        Some(removed)
+    }
+    /// Get a reference to the function named `name`
+    pub fn get_function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the function named `name`
+    pub fn get_function_mut(&mut self, name: &str) -> Option<&mut Function> {
+        self.functions.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the function named `name`, returning it if it existed
+    pub fn remove_function(&mut self, name: &str) -> Option<Function> {
+        let idx = self.functions.iter().position(|v| v.name() == name)?;
+        let removed = self.functions.remove(idx);
+        self.remove_anchor("fn", idx);
+        Some(removed)
+    }
+    /// Get a reference to the struct named `name`
+    pub fn get_struct(&self, name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the struct named `name`
+    pub fn get_struct_mut(&mut self, name: &str) -> Option<&mut Struct> {
+        self.structs.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the struct named `name`, returning it if it existed
+    pub fn remove_struct(&mut self, name: &str) -> Option<Struct> {
+        let idx = self.structs.iter().position(|v| v.name() == name)?;
+        let removed = self.structs.remove(idx);
+        self.remove_anchor("struct", idx);
+        Some(removed)
+    }
+    /// Get a reference to the enum named `name`
+    pub fn get_enum(&self, name: &str) -> Option<&Enum> {
+        self.enums.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the enum named `name`
+    pub fn get_enum_mut(&mut self, name: &str) -> Option<&mut Enum> {
+        self.enums.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the enum named `name`, returning it if it existed
+    pub fn remove_enum(&mut self, name: &str) -> Option<Enum> {
+        let idx = self.enums.iter().position(|v| v.name() == name)?;
+        let removed = self.enums.remove(idx);
+        self.remove_anchor("enum", idx);
+        Some(removed)
+    }
+    /// Get a reference to the union named `name`
+    pub fn get_union(&self, name: &str) -> Option<&Union> {
+        self.unions.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the union named `name`
+    pub fn get_union_mut(&mut self, name: &str) -> Option<&mut Union> {
+        self.unions.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the union named `name`, returning it if it existed
+    pub fn remove_union(&mut self, name: &str) -> Option<Union> {
+        let idx = self.unions.iter().position(|v| v.name() == name)?;
+        let removed = self.unions.remove(idx);
+        self.remove_anchor("union", idx);
+        Some(removed)
+    }
+    /// Get a reference to the macro named `name`
+    pub fn get_macro(&self, name: &str) -> Option<&MacroRules> {
+        self.macros.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the macro named `name`
+    pub fn get_macro_mut(&mut self, name: &str) -> Option<&mut MacroRules> {
+        self.macros.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the macro named `name`, returning it if it existed
+    pub fn remove_macro(&mut self, name: &str) -> Option<MacroRules> {
+        let idx = self.macros.iter().position(|v| v.name() == name)?;
+        let removed = self.macros.remove(idx);
+        self.remove_anchor("macro", idx);
+        Some(removed)
+    }
+    /// Get a reference to the const named `name`
+    pub fn get_const(&self, name: &str) -> Option<&Const> {
+        self.consts.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the const named `name`
+    pub fn get_const_mut(&mut self, name: &str) -> Option<&mut Const> {
+        self.consts.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the const named `name`, returning it if it existed
+    pub fn remove_const(&mut self, name: &str) -> Option<Const> {
+        let idx = self.consts.iter().position(|v| v.name() == name)?;
+        let removed = self.consts.remove(idx);
+        self.remove_anchor("const", idx);
+        Some(removed)
+    }
+    /// Get a reference to the static named `name`
+    pub fn get_static(&self, name: &str) -> Option<&Static> {
+        self.statics.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the static named `name`
+    pub fn get_static_mut(&mut self, name: &str) -> Option<&mut Static> {
+        self.statics.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the static named `name`, returning it if it existed
+    pub fn remove_static(&mut self, name: &str) -> Option<Static> {
+        let idx = self.statics.iter().position(|v| v.name() == name)?;
+        let removed = self.statics.remove(idx);
+        self.remove_anchor("static", idx);
+        Some(removed)
+    }
+    /// Get a reference to the type_alias named `name`
+    pub fn get_type_alias(&self, name: &str) -> Option<&TypeAlias> {
+        self.type_aliases.iter().find(|v| v.name() == name)
+    }
+    /// Get a mutable reference to the type_alias named `name`
+    pub fn get_type_alias_mut(&mut self, name: &str) -> Option<&mut TypeAlias> {
+        self.type_aliases.iter_mut().find(|v| v.name() == name)
+    }
+    /// Remove the type_alias named `name`, returning it if it existed
+    pub fn remove_type_alias(&mut self, name: &str) -> Option<TypeAlias> {
+        let idx = self.type_aliases.iter().position(|v| v.name() == name)?;
+        let removed = self.type_aliases.remove(idx);
+        self.remove_anchor("type", idx);
+        Some(removed)
+    }
 }
 
 impl internal::Attributes for Module {
@@ -165,42 +664,202 @@ impl internal::Attributes for Module {
     }
 }
 
+impl internal::Comments for Module {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Anchors for Module {
+    fn anchored_mut(&mut self) -> &mut Vec<internal::Anchored> {
+        &mut self.anchored
+    }
+}
+
 impl internal::Docs for Module {
     fn docs_mut(&mut self) -> &mut Vec<String> {
         &mut self.docs
     }
+    fn inner_docs(&self) -> bool {
+        true
+    }
 }
 
-impl SrcCode for Module {
-    fn generate(&self) -> String {
+/// Consistent with the derived `PartialEq`, submodules are hashed in order of their
+/// names rather than in `HashMap` iteration order.
+impl Hash for Module {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Module {
+            name,
+            is_pub,
+            traits,
+            functions,
+            structs,
+            impls,
+            enums,
+            unions,
+            macros,
+            macro_invocations,
+            consts,
+            statics,
+            type_aliases,
+            extern_blocks,
+            docs,
+            sub_modules: _,
+            attributes,
+            comments,
+            use_stmts,
+            anchored,
+            default_derives,
+            header,
+        } = self;
+        name.hash(state);
+        is_pub.hash(state);
+        traits.hash(state);
+        functions.hash(state);
+        structs.hash(state);
+        impls.hash(state);
+        enums.hash(state);
+        unions.hash(state);
+        macros.hash(state);
+        macro_invocations.hash(state);
+        consts.hash(state);
+        statics.hash(state);
+        type_aliases.hash(state);
+        extern_blocks.hash(state);
+        docs.hash(state);
+        self.submodules().hash(state);
+        attributes.hash(state);
+        comments.hash(state);
+        use_stmts.hash(state);
+        anchored.hash(state);
+        default_derives.hash(state);
+        header.hash(state);
+    }
+}
+
+impl Module {
+    /// A copy of `item` which also derives the default derives of this module.
+    fn derived_with_defaults<T: internal::Derives + Clone>(&self, item: &T) -> T {
+        let mut item = item.clone();
+        if !self.default_derives.is_empty() {
+            let attributes = merge_derives(item.attributes(), &self.default_derives);
+            *item.attributes_mut() = attributes;
+        }
+        item
+    }
+
+    /// The number of items of each kind, in the order they're rendered
+    fn item_counts(&self) -> [(&'static str, usize); 12] {
+        [
+            ("macro", self.macros.len()),
+            ("macro_invocation", self.macro_invocations.len()),
+            ("const", self.consts.len()),
+            ("static", self.statics.len()),
+            ("type", self.type_aliases.len()),
+            ("extern", self.extern_blocks.len()),
+            ("trait", self.traits.len()),
+            ("fn", self.functions.len()),
+            ("struct", self.structs.len()),
+            ("impl", self.impls.len()),
+            ("enum", self.enums.len()),
+            ("union", self.unions.len()),
+        ]
+    }
+
+    /// Keep the entries anchored before the items of `kind` in place once the item at
+    /// `idx` was removed.
+    fn remove_anchor(&mut self, kind: &'static str, idx: usize) {
+        let counts = self.item_counts();
+        internal::remove_anchor(&mut self.anchored, kind, idx, &counts);
+    }
+
+    /// Render the items of this module in order, along with their keys.
+    fn rendered_items(&self) -> Vec<(internal::ItemKey, String)> {
+        let mut rendered: Vec<(internal::ItemKey, String)> = vec![];
+        // `macro_rules!` are textually scoped, so keep them ahead of anything using them.
+        rendered.extend(
+            self.macros
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("macro", i), v.generate())),
+        );
+        rendered.extend(
+            self.macro_invocations
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("macro_invocation", i), v.generate())),
+        );
+        rendered.extend(
+            self.consts
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("const", i), v.generate())),
+        );
+        rendered.extend(
+            self.statics
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("static", i), v.generate())),
+        );
+        rendered.extend(
+            self.type_aliases
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("type", i), v.generate())),
+        );
+        rendered.extend(
+            self.extern_blocks
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("extern", i), v.generate())),
+        );
+        rendered.extend(
+            self.traits
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("trait", i), v.generate())),
+        );
+        rendered.extend(
+            self.functions
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("fn", i), v.generate())),
+        );
+        rendered.extend(self.structs.iter().enumerate().map(|(i, v)| {
+            let key = ("struct", i);
+            (key, self.derived_with_defaults(v).generate())
+        }));
+        rendered.extend(
+            self.impls
+                .iter()
+                .enumerate()
+                .map(|(i, v)| (("impl", i), v.generate())),
+        );
+        rendered.extend(self.enums.iter().enumerate().map(|(i, v)| {
+            let key = ("enum", i);
+            (key, self.derived_with_defaults(v).generate())
+        }));
+        rendered.extend(self.unions.iter().enumerate().map(|(i, v)| {
+            let key = ("union", i);
+            (key, self.derived_with_defaults(v).generate())
+        }));
+        rendered
+    }
+
+    /// Render the contents of this module, without the surrounding `mod` item.
+    fn generate_contents(&self) -> String {
         let template = r#"
-        {{ item_attributes | join(sep="
-        ") }}
-        {% if self.is_pub %}pub {% endif %}mod {{ self.name }}
-        {
             {{ scope_attributes | join(sep="
             ") }}
-            {% for doc in self.docs %}{{ doc }}{% endfor %}
+            {{ self.docs | join(sep="
+            ") }}
 
-            {% for stmt in self.use_stmts %}{{ stmt }}{% endfor %}
-            {% for obj in objs %}{{ obj }}{% endfor %}
-            {% for sub_mod in submodules %}{{ sub_mod }}{% endfor %}
-        }
+            {{ items }}
         "#;
 
         let mut ctx = Context::new();
         ctx.insert("self", &self);
-        ctx.insert(
-            "item_attributes",
-            &self
-                .attributes
-                .iter()
-                .filter_map(|ann| match ann {
-                    Attribute::ItemAttr(a) => Some(a),
-                    Attribute::ScopeAttr(_) => None,
-                })
-                .collect::<Vec<&String>>(),
-        );
         ctx.insert(
             "scope_attributes",
             &self
@@ -212,22 +871,100 @@ impl SrcCode for Module {
                 })
                 .collect::<Vec<&String>>(),
         );
-        let mut objs: Vec<String> = vec![];
-        self.traits.iter().for_each(|v| objs.push(v.generate()));
-        self.functions.iter().for_each(|v| objs.push(v.generate()));
-        self.structs.iter().for_each(|v| objs.push(v.generate()));
-        self.impls.iter().for_each(|v| objs.push(v.generate()));
-        self.enums.iter().for_each(|v| objs.push(v.generate()));
+        ctx.insert("items", &self.generate_items());
+        Tera::one_off(template, &ctx, false).unwrap()
+    }
+
+    /// Render the use statements, items and submodules of this module, without its
+    /// inner attributes and docs.
+    pub(crate) fn generate_items(&self) -> String {
+        let template = r#"
+            {{ self.use_stmts | join(sep="
+            ") }}
+            {% for obj in objs %}{{ obj }}{% endfor %}
+            {% for sub_mod in submodules %}{{ sub_mod }}{% endfor %}
+        "#;
+
+        let mut ctx = Context::new();
+        ctx.insert("self", &self);
+        let objs = internal::interleave(self.rendered_items(), &self.anchored);
         ctx.insert("objs", &objs);
 
+        // Sorted by name so the output doesn't depend on `HashMap` iteration order.
+        let mut sub_modules = self.sub_modules.values().collect::<Vec<&Module>>();
+        sub_modules.sort_by(|a, b| a.name.cmp(&b.name));
         ctx.insert(
             "submodules",
-            &self
-                .sub_modules
-                .values()
+            &sub_modules
+                .iter()
                 .map(|m| m.generate())
                 .collect::<Vec<String>>(),
         );
         Tera::one_off(template, &ctx, false).unwrap()
     }
+
+    /// Render this module as the contents of a source file, ie. `lib.rs` or `foo.rs`,
+    /// rather than as an inline `mod` item.
+    ///
+    /// Item attributes (`#[...]`) of the module itself belong to the `mod` declaration
+    /// in the parent module and are not rendered, scope attributes (`#![...]`) are
+    /// rendered at the top of the file, after the header if one is set.
+    ///
+    /// Example
+    /// -------
+    /// ```
+    /// use proffer::*;
+    ///
+    /// let m = Module::new("lib")
+    ///     .add_attribute("#![allow(dead_code)]")
+    ///     .add_struct(Struct::new("Foo"))
+    ///     .to_owned();
+    ///
+    /// let expected = r#"
+    ///     #![allow(dead_code)]
+    ///     struct Foo
+    ///     {
+    ///     }
+    /// "#;
+    /// assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate_file()));
+    /// ```
+    pub fn generate_file(&self) -> String {
+        let contents = self.generate_contents();
+        match &self.header {
+            Some(header) => header.apply(&contents),
+            None => contents,
+        }
+    }
+}
+
+impl SrcCode for Module {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ item_attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}mod {{ self.name }}
+        {
+            {{ contents }}
+        }
+        "#;
+
+        let mut ctx = Context::new();
+        ctx.insert("self", &self);
+        ctx.insert(
+            "item_attributes",
+            &self
+                .attributes
+                .iter()
+                .filter_map(|ann| match ann {
+                    Attribute::ItemAttr(a) => Some(a),
+                    Attribute::ScopeAttr(_) => None,
+                })
+                .collect::<Vec<&String>>(),
+        );
+        ctx.insert("contents", &self.generate_contents());
+        ctx.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &ctx, false).unwrap()
+    }
 }
@@ -0,0 +1,173 @@
+//!
+//!
+//! Create a `static` item.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents a `static` item in source code, optionally `static mut`.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let s = Static::new("COUNTER", "usize", "0")
+///     .set_is_mut(true)
+///     .to_owned();
+///
+/// let expected = r#"
+///     static mut COUNTER: usize = 0;
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&s.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Static {
+    name: String,
+    is_pub: bool,
+    is_mut: bool,
+    ty: String,
+    value: String,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl Static {
+    /// Create a new `Static`, `value` is expected to be valid Rust source code.
+    pub fn new(name: impl ToString, ty: impl ToString, value: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            value: value.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a new `Static`, converting `name` into `SCREAMING_SNAKE_CASE` and escaping it
+    /// if it isn't a valid identifier
+    pub fn new_from_external_name(
+        name: &str,
+        ty: impl ToString,
+        value: impl ToString,
+        policy: &IdentPolicy,
+    ) -> Self {
+        Self::new(policy.convert(name, Case::ScreamingSnake), ty, value)
+    }
+
+    /// Set if this static is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this static is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// Set if this is a `static mut`
+    pub fn set_is_mut(&mut self, is_mut: bool) -> &mut Self {
+        self.is_mut = is_mut;
+        self
+    }
+
+    /// Set if this is a `static mut`
+    pub fn with_is_mut(mut self, is_mut: bool) -> Self {
+        self.set_is_mut(is_mut);
+        self
+    }
+
+    /// The name of this static
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this static
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this static
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this static is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// If this static is `mut`
+    pub fn is_mut(&self) -> bool {
+        self.is_mut
+    }
+
+    /// The type of this static
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// The value of this static, as source code
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+
+    /// The documentation lines of this static
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this static
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this static
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for Static {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Static {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for Static {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for Static {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}static {% if self.is_mut %}mut {% endif %}{{ self.name }}: {{ self.ty }} = {{ self.value }};
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
@@ -24,7 +24,7 @@ use crate::*;
 ///     .to_owned();
 /// ```
 ///
-#[derive(Default, Serialize, Clone)]
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Struct {
     is_pub: bool,
     name: String,
@@ -32,6 +32,7 @@ pub struct Struct {
     generics: Vec<Generic>,
     docs: Vec<String>,
     attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
 }
 
 impl Struct {
@@ -43,11 +44,91 @@ impl Struct {
         }
     }
 
+    /// Create a new `Struct`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+
+    /// Create a new `Struct`, converting `name` into `UpperCamelCase` and escaping it if
+    /// it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::UpperCamel))
+    }
+
     /// Set if this struct is `pub`
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
+
+    /// Set if this struct is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this struct
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this struct
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this struct
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this struct is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The fields of this struct
+    pub fn fields(&self) -> &[Field] {
+        self.fields.as_slice()
+    }
+
+    /// The generics of this struct
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The documentation lines of this struct
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this struct
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this struct
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+
+    /// Get a reference to the field named `name`
+    pub fn get_field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the field named `name`
+    pub fn get_field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the field named `name`, returning it if it existed
+    pub fn remove_field(&mut self, name: &str) -> Option<Field> {
+        let idx = self.fields.iter().position(|v| v.name() == name)?;
+        Some(self.fields.remove(idx))
+    }
 }
 
 impl internal::Fields for Struct {
@@ -62,6 +143,18 @@ impl internal::Attributes for Struct {
     }
 }
 
+impl internal::Comments for Struct {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Derives for Struct {
+    fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
 impl internal::Generics for Struct {
     fn generics_mut(&mut self) -> &mut Vec<Generic> {
         &mut self.generics
@@ -80,6 +173,8 @@ impl internal::Docs for Struct {
 impl SrcCode for Struct {
     fn generate(&self) -> String {
         let template = r#"
+        {{ comments | join(sep="
+        ") }}
         {{ struct.docs | join(sep="
         ") }}
         {{ attributes | join(sep="
@@ -93,7 +188,11 @@ impl SrcCode for Struct {
         context.insert("struct", &self);
         context.insert("fields", &self.fields.to_src_vec());
         context.insert("generics", &self.generics.generate());
-        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert(
+            "attributes",
+            &merge_derives(&self.attributes, &[]).to_src_vec(),
+        );
+        context.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &context, false).unwrap()
     }
 }
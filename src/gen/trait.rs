@@ -5,7 +5,10 @@
 use serde::Serialize;
 
 use crate::traits::SrcCode;
-use crate::{internal, AssociatedTypeDeclaration, FunctionSignature, Generic, SrcCodeVec};
+use crate::{
+    internal, AssociatedTypeDeclaration, Attribute, Case, Comment, Function, FunctionSignature,
+    Generic, IdentPolicy, SrcCodeVec,
+};
 use tera::{Context, Tera};
 
 /// Represents a `trait` block.
@@ -28,13 +31,17 @@ use tera::{Context, Tera};
 ///     norm_whitespace(tr8t.generate().as_str())
 /// )
 /// ```
-#[derive(Serialize, Default, Clone)]
+#[derive(Serialize, Default, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Trait {
     name: String,
     is_pub: bool,
     generics: Vec<Generic>,
-    signatures: Vec<FunctionSignature>,
-    associated_types: Vec<AssociatedTypeDeclaration>,
+    pub(crate) signatures: Vec<FunctionSignature>,
+    pub(crate) associated_types: Vec<AssociatedTypeDeclaration>,
+    pub(crate) functions: Vec<Function>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
 }
 
 impl Trait {
@@ -46,28 +53,153 @@ impl Trait {
         }
     }
 
+    /// Create a new `Trait`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+
+    /// Create a new `Trait`, converting `name` into `UpperCamelCase` and escaping it if
+    /// it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::UpperCamel))
+    }
+
     /// Get the trait name
     pub fn name(&self) -> &str {
         self.name.as_str()
     }
 
+    /// Set the name of this trait
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this trait
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
     /// Add a new signature requirement to this trait.
     pub fn add_signature(&mut self, signature: FunctionSignature) -> &mut Self {
         self.signatures.push(signature);
         self
     }
 
+    /// Add a new signature requirement to this trait.
+    pub fn with_signature(mut self, signature: FunctionSignature) -> Self {
+        self.add_signature(signature);
+        self
+    }
+
+    /// Add a provided method to this trait, ie. one with a default implementation.
+    pub fn add_function(&mut self, function: Function) -> &mut Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Add a provided method to this trait, ie. one with a default implementation.
+    pub fn with_function(mut self, function: Function) -> Self {
+        self.add_function(function);
+        self
+    }
+
     /// Set if this is a `pub` trait
     pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
         self.is_pub = is_pub;
         self
     }
 
+    /// Set if this is a `pub` trait
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
     /// Add a associated type to this trait.
     pub fn add_associated_type(&mut self, associated_type: AssociatedTypeDeclaration) -> &mut Self {
         self.associated_types.push(associated_type);
         self
     }
+
+    /// Add a associated type to this trait.
+    pub fn with_associated_type(mut self, associated_type: AssociatedTypeDeclaration) -> Self {
+        self.add_associated_type(associated_type);
+        self
+    }
+
+    /// If this trait is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The generics of this trait
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The required functions of this trait
+    pub fn signatures(&self) -> &[FunctionSignature] {
+        self.signatures.as_slice()
+    }
+
+    /// The provided functions of this trait
+    pub fn functions(&self) -> &[Function] {
+        self.functions.as_slice()
+    }
+
+    /// The associated types of this trait
+    pub fn associated_types(&self) -> &[AssociatedTypeDeclaration] {
+        self.associated_types.as_slice()
+    }
+
+    /// The documentation lines of this trait
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this trait
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this trait
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+
+    /// Get a reference to the signature named `name`
+    pub fn get_signature(&self, name: &str) -> Option<&FunctionSignature> {
+        self.signatures.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the signature named `name`
+    pub fn get_signature_mut(&mut self, name: &str) -> Option<&mut FunctionSignature> {
+        self.signatures.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the signature named `name`, returning it if it existed
+    pub fn remove_signature(&mut self, name: &str) -> Option<FunctionSignature> {
+        let idx = self.signatures.iter().position(|v| v.name() == name)?;
+        Some(self.signatures.remove(idx))
+    }
+
+    /// Get a reference to the function named `name`
+    pub fn get_function(&self, name: &str) -> Option<&Function> {
+        self.functions.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the function named `name`
+    pub fn get_function_mut(&mut self, name: &str) -> Option<&mut Function> {
+        self.functions.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the function named `name`, returning it if it existed
+    pub fn remove_function(&mut self, name: &str) -> Option<Function> {
+        let idx = self.functions.iter().position(|v| v.name() == name)?;
+        Some(self.functions.remove(idx))
+    }
 }
 
 impl internal::Generics for Trait {
@@ -79,21 +211,49 @@ impl internal::Generics for Trait {
     }
 }
 
+impl internal::Attributes for Trait {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Trait {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Docs for Trait {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
 impl SrcCode for Trait {
     fn generate(&self) -> String {
         let template = r#"
+            {{ comments | join(sep="
+            ") }}
+            {{ self.docs | join(sep="
+            ") }}
+            {{ attributes | join(sep="
+            ") }}
             {% if self.is_pub %}pub {% endif %}trait {{ self.name }}{% if has_generics %}{{ generic_bounds }}{% endif %}
             {
                 {% for associated_type in associated_types %}{{ associated_type }}{% endfor %}
                 {% for signature in signatures %}{{ signature }};{% endfor %}
+                {% for function in functions %}{{ function }}{% endfor %}
             }
         "#;
         let mut context = Context::new();
         context.insert("self", &self);
         context.insert("signatures", &self.signatures.to_src_vec());
+        context.insert("functions", &self.functions.to_src_vec());
+        context.insert("attributes", &self.attributes.to_src_vec());
         context.insert("associated_types", &self.associated_types.to_src_vec());
         context.insert("has_generics", &!self.generics.is_empty());
         context.insert("generic_bounds", &self.generics.generate());
+        context.insert("comments", &self.comments.to_src_vec());
         Tera::one_off(template, &context, false).unwrap()
     }
 }
@@ -0,0 +1,175 @@
+//!
+//!
+//! Create a `type` alias item.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents a `type` alias in source code.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let alias = TypeAlias::new("Lookup", "HashMap<String, T>")
+///     .set_is_pub(true)
+///     .add_generic(Generic::new("T"))
+///     .to_owned();
+///
+/// let expected = r#"
+///     pub type Lookup<T> = HashMap<String, T>;
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&alias.generate()));
+/// ```
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeAlias {
+    name: String,
+    is_pub: bool,
+    ty: String,
+    generics: Vec<Generic>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl TypeAlias {
+    /// Create a new `TypeAlias` of `name` to the type `ty`
+    pub fn new(name: impl ToString, ty: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ty: ty.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a new `TypeAlias`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name), ty)
+    }
+
+    /// Create a new `TypeAlias`, converting `name` into `UpperCamelCase` and escaping it
+    /// if it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, ty: impl ToString, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::UpperCamel), ty)
+    }
+
+    /// Set if this type alias is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this type alias is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this type alias
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this type alias
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this type alias
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this type alias is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The aliased type
+    pub fn ty(&self) -> &str {
+        self.ty.as_str()
+    }
+
+    /// The generics of this type alias
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The documentation lines of this type alias
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this type alias
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this type alias
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+}
+
+impl internal::Attributes for TypeAlias {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for TypeAlias {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Generics for TypeAlias {
+    fn generics_mut(&mut self) -> &mut Vec<Generic> {
+        &mut self.generics
+    }
+    fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+}
+
+impl internal::Docs for TypeAlias {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for TypeAlias {
+    fn generate(&self) -> String {
+        // Bounds on type aliases are not enforced by the compiler, so only the
+        // generic parameter names are rendered.
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ self.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if self.is_pub %}pub {% endif %}type {{ self.name }}{% if has_generics %}<{{ generic_keys | join(sep=", ") }}>{% endif %} = {{ self.ty }};
+        "#;
+        let mut context = Context::new();
+        context.insert("self", &self);
+        context.insert("has_generics", &!self.generics.is_empty());
+        context.insert(
+            "generic_keys",
+            &self
+                .generics
+                .iter()
+                .map(|g| g.name())
+                .collect::<Vec<&str>>(),
+        );
+        context.insert("attributes", &self.attributes.to_src_vec());
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
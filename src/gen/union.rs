@@ -0,0 +1,207 @@
+//!
+//!
+//! Create a `union` object.
+//!
+
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::*;
+
+/// Represents a `union` in source code.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let u = Union::new("IntOrFloat")
+///     .set_is_pub(true)
+///     .add_attribute("#[repr(C)]")
+///     .add_field(Field::new("i", "u32"))
+///     .add_field(Field::new("f", "f32"))
+///     .to_owned();
+///
+/// let expected = r#"
+///     #[repr(C)]
+///     pub union IntOrFloat
+///     {
+///         i: u32,
+///         f: f32,
+///     }
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&u.generate()));
+/// ```
+///
+#[derive(Default, Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Union {
+    is_pub: bool,
+    name: String,
+    fields: Vec<Field>,
+    generics: Vec<Generic>,
+    docs: Vec<String>,
+    attributes: Vec<Attribute>,
+    comments: Vec<Comment>,
+}
+
+impl Union {
+    /// Create a new `Union`
+    pub fn new(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Create a new `Union`, escaping `name` if it isn't a valid identifier
+    pub fn new_escaped(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.escape(name))
+    }
+
+    /// Create a new `Union`, converting `name` into `UpperCamelCase` and escaping it if
+    /// it isn't a valid identifier
+    pub fn new_from_external_name(name: &str, policy: &IdentPolicy) -> Self {
+        Self::new(policy.convert(name, Case::UpperCamel))
+    }
+
+    /// Set if this union is `pub`
+    pub fn set_is_pub(&mut self, is_pub: bool) -> &mut Self {
+        self.is_pub = is_pub;
+        self
+    }
+
+    /// Set if this union is `pub`
+    pub fn with_is_pub(mut self, is_pub: bool) -> Self {
+        self.set_is_pub(is_pub);
+        self
+    }
+
+    /// The name of this union
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Set the name of this union
+    pub fn set_name(&mut self, name: impl ToString) -> &mut Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Set the name of this union
+    pub fn with_name(mut self, name: impl ToString) -> Self {
+        self.set_name(name);
+        self
+    }
+
+    /// If this union is `pub`
+    pub fn is_pub(&self) -> bool {
+        self.is_pub
+    }
+
+    /// The fields of this union
+    pub fn fields(&self) -> &[Field] {
+        self.fields.as_slice()
+    }
+
+    /// The generics of this union
+    pub fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+
+    /// The documentation lines of this union
+    pub fn docs(&self) -> &[String] {
+        self.docs.as_slice()
+    }
+
+    /// The attributes of this union
+    pub fn attributes(&self) -> &[Attribute] {
+        self.attributes.as_slice()
+    }
+
+    /// The comments placed before this union
+    pub fn comments(&self) -> &[Comment] {
+        self.comments.as_slice()
+    }
+
+    /// Get a reference to the field named `name`
+    pub fn get_field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|v| v.name() == name)
+    }
+
+    /// Get a mutable reference to the field named `name`
+    pub fn get_field_mut(&mut self, name: &str) -> Option<&mut Field> {
+        self.fields.iter_mut().find(|v| v.name() == name)
+    }
+
+    /// Remove the field named `name`, returning it if it existed
+    pub fn remove_field(&mut self, name: &str) -> Option<Field> {
+        let idx = self.fields.iter().position(|v| v.name() == name)?;
+        Some(self.fields.remove(idx))
+    }
+}
+
+impl internal::Fields for Union {
+    fn fields_mut(&mut self) -> &mut Vec<Field> {
+        &mut self.fields
+    }
+}
+
+impl internal::Attributes for Union {
+    fn attributes_mut(&mut self) -> &mut Vec<Attribute> {
+        &mut self.attributes
+    }
+}
+
+impl internal::Comments for Union {
+    fn comments_mut(&mut self) -> &mut Vec<Comment> {
+        &mut self.comments
+    }
+}
+
+impl internal::Derives for Union {
+    fn attributes(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
+impl internal::Generics for Union {
+    fn generics_mut(&mut self) -> &mut Vec<Generic> {
+        &mut self.generics
+    }
+    fn generics(&self) -> &[Generic] {
+        self.generics.as_slice()
+    }
+}
+
+impl internal::Docs for Union {
+    fn docs_mut(&mut self) -> &mut Vec<String> {
+        &mut self.docs
+    }
+}
+
+impl SrcCode for Union {
+    fn generate(&self) -> String {
+        let template = r#"
+        {{ comments | join(sep="
+        ") }}
+        {{ union.docs | join(sep="
+        ") }}
+        {{ attributes | join(sep="
+        ") }}
+        {% if union.is_pub %}pub {% endif %}union {{ union.name }}{{ generics }}
+        {
+            {% for field in fields %}{{ field }}{% endfor %}
+        }
+        "#;
+        let mut context = Context::new();
+        context.insert("union", &self);
+        context.insert("fields", &self.fields.to_src_vec());
+        context.insert("generics", &self.generics.generate());
+        context.insert(
+            "attributes",
+            &merge_derives(&self.attributes, &[]).to_src_vec(),
+        );
+        context.insert("comments", &self.comments.to_src_vec());
+        Tera::one_off(template, &context, false).unwrap()
+    }
+}
@@ -0,0 +1,314 @@
+//!
+//! Validate identifiers and escape names which aren't valid Rust identifiers,
+//! ie. field names coming from JSON such as `type`, `self` or `content-type`.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let policy = IdentPolicy::sanitize()
+//!     .with_rename_attribute(Some("#[serde(rename = \"{{ name }}\")]"))
+//!     .unwrap();
+//!
+//! let field = Field::new_escaped("content-type", "String", &policy).generate();
+//! let expected = r#"
+//!     #[serde(rename = "content-type")]
+//!     content_type: String,
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&field));
+//!
+//! let raw = IdentPolicy::raw().escape("type");
+//! assert_eq!(raw.ident(), "r#type");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use tera::{Context, Tera};
+
+use crate::{Attribute, Case};
+
+/// Strict and reserved keywords of the 2018 edition, none of which can be used as a
+/// plain identifier.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// Keywords which can't be used as raw identifiers either.
+const NON_RAW_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+/// Check if `name` is a strict or reserved keyword
+pub fn is_keyword(name: &str) -> bool {
+    KEYWORDS.contains(&name)
+}
+
+/// Reason a name isn't a valid identifier
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentError {
+    /// The name is empty
+    Empty,
+    /// The name is `_`, which is a pattern rather than an identifier
+    Underscore,
+    /// The name is a keyword
+    Keyword(String),
+    /// The name starts with a character which can't start an identifier, ie. a digit
+    InvalidStart(char),
+    /// The name contains a character which can't be part of an identifier, ie. `-`
+    InvalidCharacter(char),
+}
+
+impl fmt::Display for IdentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdentError::Empty => write!(f, "identifier is empty"),
+            IdentError::Underscore => write!(f, "`_` is not a valid identifier"),
+            IdentError::Keyword(k) => write!(f, "`{}` is a keyword", k),
+            IdentError::InvalidStart(c) => write!(f, "identifier can't start with {:?}", c),
+            IdentError::InvalidCharacter(c) => {
+                write!(f, "identifier can't contain {:?}", c)
+            }
+        }
+    }
+}
+
+impl Error for IdentError {}
+
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_alphabetic()
+}
+
+fn is_ident_continue(c: char) -> bool {
+    c == '_' || c.is_alphanumeric()
+}
+
+/// Check `name` can be used as a plain identifier. Raw identifiers (`r#type`) are
+/// accepted if the keyword they escape can be used as a raw identifier.
+///
+/// Unicode identifiers are approximated with `char::is_alphabetic` and
+/// `char::is_alphanumeric` rather than the full `XID_Start`/`XID_Continue` tables.
+pub fn validate_ident(name: &str) -> Result<(), IdentError> {
+    let (name, is_raw) = match name.strip_prefix("r#") {
+        Some(stripped) => (stripped, true),
+        None => (name, false),
+    };
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return Err(IdentError::Empty),
+        Some(c) if !is_ident_start(c) => return Err(IdentError::InvalidStart(c)),
+        _ => (),
+    }
+    if let Some(c) = chars.find(|c| !is_ident_continue(*c)) {
+        return Err(IdentError::InvalidCharacter(c));
+    }
+    if name == "_" {
+        return Err(IdentError::Underscore);
+    }
+    if (is_keyword(name) && !is_raw) || (is_raw && NON_RAW_KEYWORDS.contains(&name)) {
+        return Err(IdentError::Keyword(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Error returned when a template can't be rendered into what it's used for
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateError {
+    template: String,
+    reason: String,
+}
+
+impl TemplateError {
+    pub(crate) fn new(template: impl ToString, reason: impl ToString) -> Self {
+        Self {
+            template: template.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// The offending template
+    pub fn template(&self) -> &str {
+        self.template.as_str()
+    }
+
+    /// Why the template was rejected
+    pub fn reason(&self) -> &str {
+        self.reason.as_str()
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid template `{}`: {}", self.template, self.reason)
+    }
+}
+
+impl Error for TemplateError {}
+
+/// How names which aren't valid identifiers are escaped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EscapeStyle {
+    /// Use a raw identifier for keywords, ie. `r#type`, falling back to
+    /// `EscapeStyle::Sanitize` for names which can't be raw identifiers.
+    Raw,
+    /// Rename to a valid identifier; invalid characters become `_`, names starting
+    /// with a digit are prefixed with `_` and keywords are suffixed with `_`.
+    Sanitize,
+}
+
+/// Policy to escape names which aren't valid identifiers, optionally attaching
+/// an attribute which records the original name when it had to be renamed.
+#[derive(Debug, Clone)]
+pub struct IdentPolicy {
+    style: EscapeStyle,
+    rename_attribute: Option<String>,
+}
+
+impl IdentPolicy {
+    /// Escape keywords with raw identifiers, ie. `r#type`
+    pub fn raw() -> Self {
+        Self {
+            style: EscapeStyle::Raw,
+            rename_attribute: None,
+        }
+    }
+
+    /// Rename invalid names to valid identifiers, ie. `type_` or `content_type`
+    pub fn sanitize() -> Self {
+        Self {
+            style: EscapeStyle::Sanitize,
+            rename_attribute: None,
+        }
+    }
+
+    /// Set a template for the attribute added to fields and variants whose name had
+    /// to be changed, ie. `#[serde(rename = "{{ name }}")]`, where `name` is the
+    /// original name. Raw identifiers aren't considered renamed.
+    ///
+    /// Fails if the template can't be rendered or doesn't render an item attribute.
+    pub fn set_rename_attribute(
+        &mut self,
+        template: Option<impl ToString>,
+    ) -> Result<&mut Self, TemplateError> {
+        let template = template.map(|t| t.to_string());
+        if let Some(template) = &template {
+            let attribute = render_rename_attribute(template, "name")?;
+            if !attribute.starts_with("#[") {
+                return Err(TemplateError::new(
+                    template,
+                    "expected an attribute starting with `#[`",
+                ));
+            }
+        }
+        self.rename_attribute = template;
+        Ok(self)
+    }
+
+    /// Set a template for the attribute added to fields and variants whose name had
+    /// to be changed, ie. `#[serde(rename = "{{ name }}")]`, where `name` is the
+    /// original name. Raw identifiers aren't considered renamed.
+    ///
+    /// Fails if the template can't be rendered or doesn't render an item attribute.
+    pub fn with_rename_attribute(
+        mut self,
+        template: Option<impl ToString>,
+    ) -> Result<Self, TemplateError> {
+        self.set_rename_attribute(template)?;
+        Ok(self)
+    }
+
+    /// Escape `name` according to this policy
+    pub fn escape(&self, name: &str) -> EscapedIdent {
+        if validate_ident(name).is_ok() {
+            return EscapedIdent::new(name, None);
+        }
+        if self.style == EscapeStyle::Raw
+            && is_keyword(name)
+            && validate_ident(&format!("r#{}", name)).is_ok()
+        {
+            return EscapedIdent::new(format!("r#{}", name), None);
+        }
+        EscapedIdent::new(sanitize(name), Some(name.to_string()))
+    }
+
+    /// Convert `name` into `case` and escape the result according to this policy.
+    /// The converted name counts as renamed if it differs from `name`.
+    pub fn convert(&self, name: &str, case: Case) -> EscapedIdent {
+        let mut ident = self.escape(&case.convert(name));
+        let unraw = ident.ident().trim_start_matches("r#");
+        if unraw != name {
+            ident.original = Some(name.to_string());
+        }
+        ident
+    }
+
+    /// The rename attribute for `ident`, if it was renamed and a template is set.
+    pub fn rename_attribute(&self, ident: &EscapedIdent) -> Option<Attribute> {
+        let template = self.rename_attribute.as_ref()?;
+        let original = ident.original()?;
+        let attribute = render_rename_attribute(template, original)
+            .expect("rename attribute template is validated when set");
+        Some(Attribute::ItemAttr(attribute))
+    }
+}
+
+/// Render the rename attribute `template` for the `original` name, trimmed.
+fn render_rename_attribute(template: &str, original: &str) -> Result<String, TemplateError> {
+    let mut ctx = Context::new();
+    // Escape the name so it can be placed within a string literal.
+    let escaped = format!("{:?}", original);
+    ctx.insert("name", &escaped[1..escaped.len() - 1]);
+    Tera::one_off(template, &ctx, false)
+        .map(|attribute| attribute.trim().to_string())
+        .map_err(|err| TemplateError::new(template, err))
+}
+
+/// Rename `name` into a valid identifier
+fn sanitize(name: &str) -> String {
+    let mut ident = name
+        .chars()
+        .map(|c| if is_ident_continue(c) { c } else { '_' })
+        .collect::<String>();
+    if !ident.chars().next().is_some_and(is_ident_start) {
+        ident.insert(0, '_');
+    }
+    if is_keyword(&ident) || ident == "_" {
+        ident.push('_');
+    }
+    ident
+}
+
+/// The result of escaping a name with an `IdentPolicy`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EscapedIdent {
+    ident: String,
+    original: Option<String>,
+}
+
+impl EscapedIdent {
+    fn new(ident: impl ToString, original: Option<String>) -> Self {
+        Self {
+            ident: ident.to_string(),
+            original,
+        }
+    }
+
+    /// The escaped identifier
+    pub fn ident(&self) -> &str {
+        self.ident.as_str()
+    }
+
+    /// The original name, if it had to be renamed
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+}
+
+impl fmt::Display for EscapedIdent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.ident)
+    }
+}
@@ -1,9 +1,29 @@
-use crate::{Attribute, Field, Generic};
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{Attribute, Comment, Field, Generic, SrcCode};
 
 /// Internal trait to get access to the container storing the attributes.
 /// Used for the generic implementation of `AttributeExt`
 pub trait Attributes {
     fn attributes_mut(&mut self) -> &mut Vec<Attribute>;
+    /// If the attributes apply to the enclosing scope, ie. `#![...]` rather than `#[...]`
+    fn inner_attributes(&self) -> bool {
+        false
+    }
+}
+
+/// Internal trait to read the attributes of items which can derive traits.
+/// Used for the generic implementation of `DeriveExt`
+pub trait Derives: Attributes {
+    fn attributes(&self) -> &[Attribute];
+}
+
+/// Internal trait to get access to the container storing the comments.
+/// Used for the generic implementation of `CommentExt`
+pub trait Comments {
+    fn comments_mut(&mut self) -> &mut Vec<Comment>;
 }
 
 /// Internal trait to get access to the container storing the fields.
@@ -26,7 +46,130 @@ pub trait TraitBounds {
 }
 
 /// Internal trait to get access to the container storing the documentation.
-/// Used for the generic implementation of `TraitBoundExt`
+/// Used for the generic implementation of `DocExt`
 pub trait Docs {
     fn docs_mut(&mut self) -> &mut Vec<String>;
+    /// If the docs document the enclosing item, ie. `//!` rather than `///`
+    fn inner_docs(&self) -> bool {
+        false
+    }
+}
+
+/// Internal trait to get access to the entries placed among the items of a container.
+/// Used for the generic implementation of `SeparatorExt`
+pub trait Anchors {
+    fn anchored_mut(&mut self) -> &mut Vec<Anchored>;
+}
+
+/// Key of a rendered item, its category and index among the items of that category,
+/// ie. `("impl", 1)` for the second impl block. Names aren't unique, ie. several
+/// inherent `impl User` blocks share one.
+pub type ItemKey = (&'static str, usize);
+
+/// Anchor the entries added since the last item was added before `key`, the key of
+/// the item just added.
+pub fn anchor_pending(anchored: &mut [Anchored], key: ItemKey) {
+    for a in anchored.iter_mut().rev().take_while(|a| a.before.is_none()) {
+        a.before = Some(key);
+    }
+}
+
+/// Keep the entries anchored before the items of `kind` in place once the item at `idx`
+/// is removed. Entries anchored before the removed item move to the item rendered after
+/// it, given `counts`, the number of items of each kind in render order once removed.
+pub fn remove_anchor(
+    anchored: &mut Vec<Anchored>,
+    kind: &'static str,
+    idx: usize,
+    counts: &[(&'static str, usize)],
+) {
+    let next = match counts.iter().position(|(k, _)| *k == kind) {
+        Some(pos) if counts[pos].1 > idx => Some((kind, idx)),
+        Some(pos) => counts[pos + 1..]
+            .iter()
+            .find(|(_, count)| *count > 0)
+            .map(|(k, _)| (*k, 0)),
+        None => None,
+    };
+    let (moved, mut rest): (Vec<Anchored>, Vec<Anchored>) = anchored
+        .drain(..)
+        .partition(|a| a.before == Some((kind, idx)));
+    for a in &mut rest {
+        match &mut a.before {
+            Some((k, i)) if *k == kind && *i > idx => *i -= 1,
+            _ => {}
+        }
+    }
+    // The moved entries precede those already anchored before `next`, as the removed
+    // item did, and entries without an item following them stay last.
+    let pos = rest
+        .iter()
+        .position(|a| a.before == next || a.before.is_none())
+        .unwrap_or(rest.len());
+    rest.splice(
+        pos..pos,
+        moved.into_iter().map(|a| Anchored { before: next, ..a }),
+    );
+    *anchored = rest;
+}
+
+/// An entry rendered before the item which was added next, so it keeps its position
+/// while items are rendered grouped by their kind. Entries without an item added after
+/// them are rendered last.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Anchored {
+    pub before: Option<ItemKey>,
+    pub entry: AnchoredEntry,
+}
+
+/// The content of an `Anchored` entry
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum AnchoredEntry {
+    /// Source code of an item rendered verbatim
+    Raw(String),
+    /// A comment or blank line separating items
+    Separator(Comment),
+}
+
+impl SrcCode for AnchoredEntry {
+    fn generate(&self) -> String {
+        match self {
+            AnchoredEntry::Raw(src) => src.clone(),
+            AnchoredEntry::Separator(comment) => comment.generate(),
+        }
+    }
+}
+
+/// Render `items` in order, each preceded by the entries anchored before it. Entries
+/// without an item following them are rendered last.
+pub fn interleave(items: Vec<(ItemKey, String)>, anchored: &[Anchored]) -> Vec<String> {
+    let mut before: HashMap<ItemKey, Vec<&AnchoredEntry>> = HashMap::new();
+    for a in anchored {
+        if let Some(key) = a.before {
+            before.entry(key).or_default().push(&a.entry);
+        }
+    }
+    // Keep a comment from running into the item following it.
+    let render = |entry: &AnchoredEntry| format!("{}\n", entry.generate());
+    let mut rendered = vec![];
+    for (key, src) in items {
+        rendered.extend(
+            before
+                .remove(&key)
+                .unwrap_or_default()
+                .into_iter()
+                .map(render),
+        );
+        rendered.push(src);
+    }
+    rendered.extend(
+        anchored
+            .iter()
+            .filter(|a| match a.before {
+                Some(key) => before.contains_key(&key),
+                None => true,
+            })
+            .map(|a| render(&a.entry)),
+    );
+    rendered
 }
@@ -45,16 +45,107 @@
 //! )
 //! ```
 //!
+//! Builders
+//! --------
+//!
+//! Each `add_*` / `set_*` method taking and returning `&mut Self` has a `with_*`
+//! counterpart taking and returning `self` by value, `remove_derive` and `clear_docs`
+//! have `without_derive` and `without_docs`. Chaining by value needs no trailing
+//! `.to_owned()`, which clones the whole item.
+//!
+//! ```
+//! use proffer::*;
+//!
+//! let m = Module::new("models")
+//!     .with_is_pub(true)
+//!     .with_struct(
+//!         Struct::new("User")
+//!             .with_derive("Debug")
+//!             .with_doc("A user")
+//!             .with_field(Field::new("id", "u64").with_is_pub(true))
+//!             .with_generic(Generic::new("T").with_trait_bound("Clone")),
+//!     );
+//!
+//! let expected = r#"
+//!     pub mod models
+//!     {
+//!         /// A user
+//!         #[derive(Debug)]
+//!         struct User<T>
+//!             where
+//!                 T: Clone,
+//!         {
+//!             pub id: u64,
+//!         }
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+//! ```
+//!
+//! Cargo features
+//! --------------
+//!
+//! - `tokens`: implements `quote::ToTokens` for the code generation objects, so they
+//!   can be used directly within `quote!` in procedural macros, along with a fallible
+//!   `try_to_tokens`.
+//! - `parse`: converts items parsed with `syn` into the code generation objects, to
+//!   load existing source code, modify it and render it again.
+//! - `check`: adds `generate_checked` to the code generation objects, which parses the
+//!   generated source code with `syn` and reports where it is invalid.
 //!
 
 mod internal;
 
 pub mod gen;
+// `gen::generics` and `traits::generics` share a name; the items are re-exported
+// individually and neither module is meant to be reached from the crate root.
+#[allow(ambiguous_glob_reexports)]
 pub use gen::*;
 
 pub mod traits;
 pub use traits::*;
 
+pub mod case;
+pub use case::*;
+
+pub mod ident;
+pub use ident::*;
+
+pub mod region;
+pub use region::*;
+
+pub mod merge;
+pub use merge::*;
+
+pub mod diff;
+pub use diff::*;
+
+pub mod semver;
+pub use semver::*;
+
+pub mod resolve;
+pub use resolve::*;
+
+pub mod recursion;
+pub use recursion::*;
+
+pub mod visit;
+
+#[cfg(feature = "tokens")]
+pub mod tokens;
+#[cfg(feature = "tokens")]
+pub use tokens::{TokenizeError, TryToTokens};
+
+#[cfg(feature = "parse")]
+pub mod parse;
+#[cfg(feature = "parse")]
+pub use parse::ParseError;
+
+#[cfg(feature = "check")]
+pub mod check;
+#[cfg(feature = "check")]
+pub use check::{CheckError, CheckedSrcCode};
+
 /// Helper function throughout tests and documentation
 /// for comparing expected source code generated.
 #[must_use]
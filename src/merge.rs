@@ -0,0 +1,742 @@
+//!
+//! Merge module trees, ie. output generated from several sources into a single crate.
+//!
+//! Submodules of the same name are merged recursively, other items are matched by
+//! name and impl blocks by the type, generics and trait they implement. Items sharing
+//! a name, ie. several inherent `impl User` blocks, are matched in order. Identical
+//! items are kept once, while differing items of the same name are conflicts, resolved
+//! according to the `MergePolicy`. Items of different kinds sharing a namespace, ie. a
+//! struct and an enum, or a const and a function, of the same name are conflicts too.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let mut first = Module::new("api")
+//!     .with_submodule(Module::new("models").with_struct(Struct::new("User")))
+//!     .with_impl(Impl::new("User").with_function(Function::new("id")));
+//! let second = Module::new("api")
+//!     .with_submodule(Module::new("models").with_struct(Struct::new("Group")))
+//!     .with_impl(Impl::new("User").with_function(Function::new("name")));
+//!
+//! let err = first.clone().merge(second.clone(), MergePolicy::Error).unwrap_err();
+//! assert_eq!(err.to_string(), "conflicting items: impl `User`");
+//!
+//! let report = first.merge(second, MergePolicy::MergeImpls).unwrap();
+//! assert_eq!(report.conflicts()[0].to_string(), "impl `User`");
+//!
+//! let models = first.get_submodule("models").unwrap();
+//! assert!(models.get_struct("User").is_some() && models.get_struct("Group").is_some());
+//! assert_eq!(first.impls()[0].functions().len(), 2);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::*;
+
+/// How conflicting items are resolved when merging modules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MergePolicy {
+    /// Fail on any conflict, leaving the module untouched
+    Error,
+    /// Keep the item of the module merged into
+    KeepFirst,
+    /// Replace the item with the one of the module being merged
+    Replace,
+    /// Combine the functions and associated types of conflicting impl blocks, failing
+    /// on any other conflict including functions of the same name within them
+    MergeImpls,
+}
+
+/// Kind of an item within a module, ie. involved in a conflict or a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ItemKind {
+    /// A module
+    Module,
+    /// A struct
+    Struct,
+    /// An enum
+    Enum,
+    /// A union
+    Union,
+    /// A field of a struct or union
+    Field,
+    /// An enum variant
+    Variant,
+    /// A trait
+    Trait,
+    /// A function, free or within an impl block
+    Function,
+    /// An impl block
+    Impl,
+    /// An associated type within a trait or impl block
+    AssociatedType,
+    /// A `macro_rules!` definition
+    Macro,
+    /// A const
+    Const,
+    /// A static
+    Static,
+    /// A type alias
+    TypeAlias,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            ItemKind::Module => "mod",
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::Union => "union",
+            ItemKind::Field => "field",
+            ItemKind::Variant => "variant",
+            ItemKind::Trait => "trait",
+            ItemKind::Function => "fn",
+            ItemKind::Impl => "impl",
+            ItemKind::AssociatedType => "type",
+            ItemKind::Macro => "macro",
+            ItemKind::Const => "const",
+            ItemKind::Static => "static",
+            ItemKind::TypeAlias => "type",
+        };
+        f.write_str(kind)
+    }
+}
+
+/// Two differing items of the same name found while merging modules
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    path: Vec<String>,
+    kind: ItemKind,
+    name: String,
+}
+
+impl Conflict {
+    /// Names of the submodules containing the item, relative to the merged module
+    pub fn path(&self) -> &[String] {
+        self.path.as_slice()
+    }
+
+    /// Kind of the item
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Name of the item, ie. `Display for User` for an impl block or `User::id` for a
+    /// function within an impl block
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `", self.kind)?;
+        for module in &self.path {
+            write!(f, "{}::", module)?;
+        }
+        write!(f, "{}`", self.name)
+    }
+}
+
+/// Conflicts resolved while merging modules
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    conflicts: Vec<Conflict>,
+}
+
+impl MergeReport {
+    /// The resolved conflicts, in the order they were found
+    pub fn conflicts(&self) -> &[Conflict] {
+        self.conflicts.as_slice()
+    }
+
+    /// If the modules were merged without any conflict
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Error merging modules, holding the conflicts the `MergePolicy` can't resolve
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeError {
+    conflicts: Vec<Conflict>,
+}
+
+impl MergeError {
+    /// The unresolved conflicts, in the order they were found
+    pub fn conflicts(&self) -> &[Conflict] {
+        self.conflicts.as_slice()
+    }
+}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let conflicts = self
+            .conflicts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        write!(f, "conflicting items: {}", conflicts.join(", "))
+    }
+}
+
+impl Error for MergeError {}
+
+impl Module {
+    /// Merge `other` into this module. Submodules of the same name are merged
+    /// recursively, use statements, attributes and default derives are combined, and
+    /// docs, comments and the header of `other` are only used if this module has none.
+    ///
+    /// Returns the conflicts resolved by `policy`. If `policy` can't resolve all of
+    /// them, this module is left untouched and the unresolved conflicts are returned.
+    pub fn merge(&mut self, other: Module, policy: MergePolicy) -> Result<MergeReport, MergeError> {
+        let mut conflicts = vec![];
+        find_conflicts(self, &other, &mut vec![], policy, &mut conflicts);
+
+        let unresolved = match policy {
+            MergePolicy::Error => conflicts.clone(),
+            MergePolicy::MergeImpls => conflicts
+                .iter()
+                .filter(|c| c.kind != ItemKind::Impl)
+                .cloned()
+                .collect(),
+            MergePolicy::KeepFirst | MergePolicy::Replace => vec![],
+        };
+        if !unresolved.is_empty() {
+            return Err(MergeError {
+                conflicts: unresolved,
+            });
+        }
+
+        merge_module(self, other, policy);
+        Ok(MergeReport { conflicts })
+    }
+}
+
+/// Name of an impl block, ie. `Display for User` or `Wrapper<T>`
+pub(crate) fn impl_name(ipl: &Impl) -> String {
+    let obj_name = if ipl.generics().is_empty() {
+        ipl.obj_name().to_string()
+    } else {
+        let keys = ipl
+            .generics()
+            .iter()
+            .map(Generic::name)
+            .collect::<Vec<&str>>();
+        format!("{}<{}>", ipl.obj_name(), keys.join(", "))
+    };
+    match ipl.impl_trait() {
+        Some(tr8t) => format!("{} for {}", tr8t.name(), obj_name),
+        None => obj_name,
+    }
+}
+
+/// Pair each item of `other` with the index of the item of `first` it corresponds to,
+/// if any. Items sharing a name, ie. several inherent `impl User` blocks, are paired
+/// in order: the second of `other` with the second of `first`.
+pub(crate) fn pair_named<'a, T>(
+    first: &[T],
+    other: &'a [T],
+    name: impl Fn(&T) -> String,
+) -> Vec<(Option<usize>, &'a T)> {
+    let first_names = first.iter().map(&name).collect::<Vec<String>>();
+    let mut seen: Vec<String> = vec![];
+    other
+        .iter()
+        .map(|item| {
+            let item_name = name(item);
+            let nth = seen.iter().filter(|n| **n == item_name).count();
+            let idx = first_names
+                .iter()
+                .enumerate()
+                .filter(|(_, n)| **n == item_name)
+                .nth(nth)
+                .map(|(idx, _)| idx);
+            seen.push(item_name);
+            (idx, item)
+        })
+        .collect()
+}
+
+/// Push a conflict for each item of `other` differing from the item of the same name
+/// within `first`
+fn named_conflicts<T: PartialEq>(
+    first: &[T],
+    other: &[T],
+    name: impl Fn(&T) -> String,
+    kind: ItemKind,
+    path: &[String],
+    conflicts: &mut Vec<Conflict>,
+) {
+    for (idx, item) in pair_named(first, other, &name) {
+        if idx.is_some_and(|idx| first[idx] != *item) {
+            conflicts.push(Conflict {
+                path: path.to_vec(),
+                kind,
+                name: name(item),
+            });
+        }
+    }
+}
+
+/// Names of the items of `module` within the type namespace and within the value
+/// namespace, along with their kinds
+fn namespaces(module: &Module) -> [Vec<(ItemKind, &str)>; 2] {
+    let mut types = vec![];
+    types.extend(
+        module
+            .structs()
+            .iter()
+            .map(|s| (ItemKind::Struct, s.name())),
+    );
+    types.extend(module.enums().iter().map(|e| (ItemKind::Enum, e.name())));
+    types.extend(module.unions().iter().map(|u| (ItemKind::Union, u.name())));
+    types.extend(module.traits().iter().map(|t| (ItemKind::Trait, t.name())));
+    types.extend(
+        module
+            .type_aliases()
+            .iter()
+            .map(|t| (ItemKind::TypeAlias, t.name())),
+    );
+    types.extend(
+        module
+            .submodules()
+            .into_iter()
+            .map(|m| (ItemKind::Module, m.name())),
+    );
+    let mut values = vec![];
+    values.extend(module.consts().iter().map(|c| (ItemKind::Const, c.name())));
+    values.extend(
+        module
+            .statics()
+            .iter()
+            .map(|s| (ItemKind::Static, s.name())),
+    );
+    values.extend(
+        module
+            .functions()
+            .iter()
+            .map(|f| (ItemKind::Function, f.name())),
+    );
+    [types, values]
+}
+
+/// Items of `other` sharing their name with an item of another kind of `first` within
+/// the same namespace, ie. a struct and an enum named `User`, as the kind of the item
+/// of `first`, the kind of the item of `other` and their name
+fn namespace_clashes(first: &Module, other: &Module) -> Vec<(ItemKind, ItemKind, String)> {
+    let mut clashes = vec![];
+    for (first_names, other_names) in namespaces(first).iter().zip(namespaces(other).iter()) {
+        for (kind, name) in other_names {
+            let first_kind = first_names
+                .iter()
+                .find(|(k, n)| n == name && k != kind)
+                .map(|(k, _)| *k);
+            if let Some(first_kind) = first_kind {
+                clashes.push((first_kind, *kind, name.to_string()));
+            }
+        }
+    }
+    clashes
+}
+
+/// Remove the item of `kind` named `name` from `module`
+fn remove_item(module: &mut Module, kind: ItemKind, name: &str) {
+    match kind {
+        ItemKind::Struct => {
+            module.remove_struct(name);
+        }
+        ItemKind::Enum => {
+            module.remove_enum(name);
+        }
+        ItemKind::Union => {
+            module.remove_union(name);
+        }
+        ItemKind::Trait => {
+            module.remove_trait(name);
+        }
+        ItemKind::TypeAlias => {
+            module.remove_type_alias(name);
+        }
+        ItemKind::Module => {
+            module.remove_submodule(name);
+        }
+        ItemKind::Const => {
+            module.remove_const(name);
+        }
+        ItemKind::Static => {
+            module.remove_static(name);
+        }
+        ItemKind::Function => {
+            module.remove_function(name);
+        }
+        _ => (),
+    }
+}
+
+fn find_conflicts(
+    first: &Module,
+    other: &Module,
+    path: &mut Vec<String>,
+    policy: MergePolicy,
+    conflicts: &mut Vec<Conflict>,
+) {
+    for (_, kind, name) in namespace_clashes(first, other) {
+        conflicts.push(Conflict {
+            path: path.to_vec(),
+            kind,
+            name,
+        });
+    }
+    named_conflicts(
+        &first.macros,
+        &other.macros,
+        |m| m.name().to_string(),
+        ItemKind::Macro,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.consts,
+        &other.consts,
+        |c| c.name().to_string(),
+        ItemKind::Const,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.statics,
+        &other.statics,
+        |s| s.name().to_string(),
+        ItemKind::Static,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.type_aliases,
+        &other.type_aliases,
+        |t| t.name().to_string(),
+        ItemKind::TypeAlias,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.traits,
+        &other.traits,
+        |t| t.name().to_string(),
+        ItemKind::Trait,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.functions,
+        &other.functions,
+        |f| f.name().to_string(),
+        ItemKind::Function,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.structs,
+        &other.structs,
+        |s| s.name().to_string(),
+        ItemKind::Struct,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.impls,
+        &other.impls,
+        impl_name,
+        ItemKind::Impl,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.enums,
+        &other.enums,
+        |e| e.name().to_string(),
+        ItemKind::Enum,
+        path,
+        conflicts,
+    );
+    named_conflicts(
+        &first.unions,
+        &other.unions,
+        |u| u.name().to_string(),
+        ItemKind::Union,
+        path,
+        conflicts,
+    );
+
+    if policy == MergePolicy::MergeImpls {
+        for (idx, ipl) in pair_named(&first.impls, &other.impls, impl_name) {
+            if let Some(first_ipl) = idx.map(|idx| &first.impls[idx]) {
+                named_conflicts(
+                    &first_ipl.functions,
+                    &ipl.functions,
+                    |f| format!("{}::{}", ipl.obj_name(), f.name()),
+                    ItemKind::Function,
+                    path,
+                    conflicts,
+                );
+                named_conflicts(
+                    &first_ipl.associated_types,
+                    &ipl.associated_types,
+                    |t| format!("{}::{}", ipl.obj_name(), t.name()),
+                    ItemKind::AssociatedType,
+                    path,
+                    conflicts,
+                );
+            }
+        }
+    }
+
+    for sub_module in other.submodules() {
+        if let Some(first_sub_module) = first.get_submodule(sub_module.name()) {
+            path.push(sub_module.name().to_string());
+            find_conflicts(first_sub_module, sub_module, path, policy, conflicts);
+            path.pop();
+        }
+    }
+}
+
+/// Add the items of `other` to `first`, resolving conflicts according to `policy`.
+/// Returns the index within `first` each item of `other` was merged into.
+fn merge_named<T: PartialEq>(
+    first: &mut Vec<T>,
+    other: Vec<T>,
+    name: impl Fn(&T) -> String,
+    policy: MergePolicy,
+) -> Vec<usize> {
+    let pairs = pair_named(first, &other, name)
+        .into_iter()
+        .map(|(idx, _)| idx)
+        .collect::<Vec<Option<usize>>>();
+    let mut positions = vec![];
+    for (idx, item) in pairs.into_iter().zip(other) {
+        match idx {
+            None => {
+                positions.push(first.len());
+                first.push(item);
+            }
+            Some(idx) => {
+                if policy == MergePolicy::Replace {
+                    first[idx] = item;
+                }
+                positions.push(idx);
+            }
+        }
+    }
+    positions
+}
+
+/// Add the items of `other` to `first` which `first` doesn't contain yet. Returns the
+/// index within `first` of each item of `other`.
+fn merge_distinct<T: PartialEq>(first: &mut Vec<T>, other: Vec<T>) -> Vec<usize> {
+    other
+        .into_iter()
+        .map(|item| match first.iter().position(|f| *f == item) {
+            Some(idx) => idx,
+            None => {
+                first.push(item);
+                first.len() - 1
+            }
+        })
+        .collect()
+}
+
+/// Add the entries anchored among the items of `other` to `first`, anchoring them
+/// before the items of `first` the items of `other` were merged into. `positions` are
+/// the indices returned by `merge_named` for each kind of item.
+fn merge_anchored(
+    first: &mut Vec<internal::Anchored>,
+    other: Vec<internal::Anchored>,
+    positions: &[(&'static str, Vec<usize>)],
+) {
+    let other = other
+        .into_iter()
+        .map(|mut a| {
+            if let Some((kind, idx)) = &mut a.before {
+                if let Some(merged) = positions
+                    .iter()
+                    .find(|(k, _)| k == kind)
+                    .and_then(|(_, merged)| merged.get(*idx))
+                {
+                    *idx = *merged;
+                }
+            }
+            a
+        })
+        .collect();
+    merge_distinct(first, other);
+    // Entries without an item following them stay last, see `internal::anchor_pending`.
+    first.sort_by_key(|a| a.before.is_none());
+}
+
+fn merge_module(first: &mut Module, mut other: Module, policy: MergePolicy) {
+    // Items of different kinds sharing a name can't both be kept
+    for (first_kind, kind, name) in namespace_clashes(first, &other) {
+        if policy == MergePolicy::Replace {
+            remove_item(first, first_kind, &name);
+        } else {
+            remove_item(&mut other, kind, &name);
+        }
+    }
+    let Module {
+        is_pub,
+        traits,
+        functions,
+        structs,
+        impls,
+        enums,
+        unions,
+        macros,
+        macro_invocations,
+        consts,
+        statics,
+        type_aliases,
+        extern_blocks,
+        docs,
+        sub_modules,
+        attributes,
+        comments,
+        use_stmts,
+        anchored,
+        default_derives,
+        header,
+        ..
+    } = other;
+
+    first.is_pub |= is_pub;
+    // Where each item of `other` ends up, to keep the entries anchored before it there
+    let mut positions = vec![
+        (
+            "macro",
+            merge_named(&mut first.macros, macros, |m| m.name().to_string(), policy),
+        ),
+        (
+            "const",
+            merge_named(&mut first.consts, consts, |c| c.name().to_string(), policy),
+        ),
+        (
+            "static",
+            merge_named(
+                &mut first.statics,
+                statics,
+                |s| s.name().to_string(),
+                policy,
+            ),
+        ),
+        (
+            "type",
+            merge_named(
+                &mut first.type_aliases,
+                type_aliases,
+                |t| t.name().to_string(),
+                policy,
+            ),
+        ),
+        (
+            "trait",
+            merge_named(&mut first.traits, traits, |t| t.name().to_string(), policy),
+        ),
+        (
+            "fn",
+            merge_named(
+                &mut first.functions,
+                functions,
+                |f| f.name().to_string(),
+                policy,
+            ),
+        ),
+        (
+            "struct",
+            merge_named(
+                &mut first.structs,
+                structs,
+                |s| s.name().to_string(),
+                policy,
+            ),
+        ),
+        (
+            "enum",
+            merge_named(&mut first.enums, enums, |e| e.name().to_string(), policy),
+        ),
+        (
+            "union",
+            merge_named(&mut first.unions, unions, |u| u.name().to_string(), policy),
+        ),
+        (
+            "macro_invocation",
+            merge_distinct(&mut first.macro_invocations, macro_invocations),
+        ),
+        (
+            "extern",
+            merge_distinct(&mut first.extern_blocks, extern_blocks),
+        ),
+    ];
+
+    if policy == MergePolicy::MergeImpls {
+        let pairs = pair_named(&first.impls, &impls, impl_name)
+            .into_iter()
+            .map(|(idx, _)| idx)
+            .collect::<Vec<Option<usize>>>();
+        let mut impl_positions = vec![];
+        for (idx, ipl) in pairs.into_iter().zip(impls) {
+            match idx {
+                Some(idx) => {
+                    let first_ipl = &mut first.impls[idx];
+                    let ipl_positions = [
+                        (
+                            "fn",
+                            merge_distinct(&mut first_ipl.functions, ipl.functions),
+                        ),
+                        (
+                            "type",
+                            merge_distinct(&mut first_ipl.associated_types, ipl.associated_types),
+                        ),
+                    ];
+                    merge_anchored(&mut first_ipl.anchored, ipl.anchored, &ipl_positions);
+                    impl_positions.push(idx);
+                }
+                None => {
+                    impl_positions.push(first.impls.len());
+                    first.impls.push(ipl);
+                }
+            }
+        }
+        positions.push(("impl", impl_positions));
+    } else {
+        positions.push((
+            "impl",
+            merge_named(&mut first.impls, impls, impl_name, policy),
+        ));
+    }
+
+    merge_distinct(&mut first.use_stmts, use_stmts);
+    merge_anchored(&mut first.anchored, anchored, &positions);
+    merge_distinct(&mut first.attributes, attributes);
+    merge_distinct(&mut first.default_derives, default_derives);
+    if first.docs.is_empty() {
+        first.docs = docs;
+    }
+    if first.comments.is_empty() {
+        first.comments = comments;
+    }
+    if first.header.is_none() {
+        first.header = header;
+    }
+
+    for (name, sub_module) in sub_modules {
+        match first.sub_modules.get_mut(&name) {
+            Some(first_sub_module) => merge_module(first_sub_module, sub_module, policy),
+            None => {
+                first.sub_modules.insert(name, sub_module);
+            }
+        }
+    }
+}
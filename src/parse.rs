@@ -0,0 +1,937 @@
+//!
+//! Import existing Rust source, parsed with `syn`, into the code generation objects,
+//! enabled with the `parse` feature.
+//!
+//! Items are converted with `TryFrom`, ie. `Struct::try_from(&item_struct)`, and fail
+//! with a `ParseError` when they use syntax these objects can't represent, such as tuple
+//! structs or `pub(crate)` visibility. Function bodies, expressions and patterns are kept
+//! as raw source code.
+//!
+//! When importing a whole file with `Module::from_file`, items which can't be represented
+//! are reported and kept verbatim, rendered before the item following them in the file,
+//! see `Module::add_raw_item`.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let file = syn::parse_file(r#"
+//!     //! Users
+//!     use std::fmt;
+//!
+//!     /// A user
+//!     pub struct User {
+//!         pub id: u64,
+//!     }
+//! "#).unwrap();
+//!
+//! let (mut m, unsupported) = Module::from_file(&file);
+//! assert!(unsupported.is_empty());
+//! m.set_name("users");
+//! m.add_struct(Struct::new("Group").add_field(Field::new("users", "Vec<User>")).to_owned());
+//!
+//! let expected = r#"
+//!     mod users
+//!     {
+//!         //! Users
+//!         use std::fmt;
+//!         /// A user
+//!         pub struct User
+//!         {
+//!             pub id: u64,
+//!         }
+//!         struct Group
+//!         {
+//!             users: Vec<User>,
+//!         }
+//!     }
+//! "#;
+//! assert_eq!(norm_whitespace(expected), norm_whitespace(&m.generate()));
+//! ```
+
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+
+use crate::*;
+
+/// Error returned when a `syn` item can't be represented by the code generation objects.
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    item: String,
+    reason: String,
+}
+
+impl ParseError {
+    fn new(item: impl ToString, reason: impl ToString) -> Self {
+        Self {
+            item: item.to_string(),
+            reason: reason.to_string(),
+        }
+    }
+
+    /// Name of the item which failed to import
+    pub fn item(&self) -> &str {
+        self.item.as_str()
+    }
+
+    /// Why the item failed to import
+    pub fn reason(&self) -> &str {
+        self.reason.as_str()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unable to import `{}`: {}", self.item, self.reason)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Render tokens as source code.
+fn raw(tokens: &impl ToTokens) -> String {
+    tokens.to_token_stream().to_string()
+}
+
+/// Render tokens of a type, path, trait bound or attribute as source code, tidying
+/// up the spacing `proc_macro2` puts between every token. String and char literals are
+/// left as is.
+fn tidy(tokens: &impl ToTokens) -> String {
+    let src = raw(tokens).chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(src.len());
+    let mut in_literal = false;
+    let mut escaped = false;
+    let mut segment = String::new();
+    let mut idx = 0;
+    while idx < src.len() {
+        let c = src[idx];
+        if in_literal {
+            result.push(c);
+            match c {
+                '\\' => escaped = !escaped,
+                '"' if !escaped => in_literal = false,
+                _ => escaped = false,
+            }
+        } else if c == '"' {
+            result.push_str(&tidy_segment(&segment));
+            segment.clear();
+            result.push(c);
+            in_literal = true;
+        } else if let Some(len) = char_literal_len(&src[idx..]) {
+            result.push_str(&tidy_segment(&segment));
+            segment.clear();
+            result.extend(&src[idx..idx + len]);
+            idx += len;
+            continue;
+        } else {
+            segment.push(c);
+        }
+        idx += 1;
+    }
+    result.push_str(&tidy_segment(&segment));
+    if result.starts_with(":: ") {
+        result.replace_range(..3, "::");
+    }
+    result
+}
+
+/// Length of the char literal `src` starts with, ie. `'"'` or `'\''`, if any. A
+/// lifetime is never followed by a closing quote.
+fn char_literal_len(src: &[char]) -> Option<usize> {
+    match src {
+        ['\'', '\\', _, rest @ ..] => rest.iter().position(|c| *c == '\'').map(|end| end + 4),
+        ['\'', _, '\'', ..] => Some(3),
+        _ => None,
+    }
+}
+
+fn tidy_segment(segment: &str) -> String {
+    let mut s = segment.to_string();
+    for (from, to) in &[
+        (" :: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("* const ", "*const "),
+        ("* mut ", "*mut "),
+        ("? ", "?"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+    ] {
+        s = s.replace(from, to);
+    }
+    s
+}
+
+/// Check the visibility can be represented by an `is_pub` flag.
+fn is_pub(vis: &syn::Visibility, item: &str) -> Result<bool, ParseError> {
+    match vis {
+        syn::Visibility::Public(_) => Ok(true),
+        syn::Visibility::Inherited => Ok(false),
+        _ => Err(ParseError::new(
+            item,
+            format!("restricted visibility `{}` is not supported", raw(vis)),
+        )),
+    }
+}
+
+/// Documentation and attributes of an item, with `#[doc = "..."]` attributes
+/// converted back into `///` or `//!` lines.
+struct Attrs {
+    docs: Vec<String>,
+    outer: Vec<Attribute>,
+    inner: Vec<Attribute>,
+}
+
+impl Attrs {
+    fn new(attrs: &[syn::Attribute]) -> Self {
+        let mut docs = vec![];
+        let mut outer = vec![];
+        let mut inner = vec![];
+        for attr in attrs {
+            let is_inner = matches!(attr.style, syn::AttrStyle::Inner(_));
+            if attr.path.is_ident("doc") {
+                if let Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                    lit: syn::Lit::Str(s),
+                    ..
+                })) = attr.parse_meta()
+                {
+                    let (prefix, doc_attr) = if is_inner {
+                        ("//!", "#!")
+                    } else {
+                        ("///", "#")
+                    };
+                    // Keep each line of a multi-line value a doc line of its own.
+                    for line in s.value().split('\n') {
+                        if line.chars().any(|c| c.is_control() && c != '\t') {
+                            docs.push(format!("{}[doc = {:?}]", doc_attr, line));
+                        } else {
+                            docs.push(format!("{}{}", prefix, line));
+                        }
+                    }
+                    continue;
+                }
+            }
+            let src = format!(
+                "#{}[{}{}]",
+                if is_inner { "!" } else { "" },
+                tidy(&attr.path),
+                tidy(&attr.tokens)
+            );
+            if is_inner {
+                inner.push(Attribute::from(src));
+            } else {
+                outer.push(Attribute::from(src));
+            }
+        }
+        Self { docs, outer, inner }
+    }
+
+    /// Apply the docs and outer attributes to `obj`, inner attributes are an error.
+    fn apply<T: AttributeExt + DocExt>(self, obj: &mut T, item: &str) -> Result<(), ParseError> {
+        if !self.inner.is_empty() {
+            return Err(ParseError::new(item, "inner attributes are not supported"));
+        }
+        obj.add_docs(self.docs).add_attributes(self.outer);
+        Ok(())
+    }
+}
+
+/// Convert generic parameters and their `where` clause into `Generic`s.
+fn generics(generics: &syn::Generics, item: &str) -> Result<Vec<Generic>, ParseError> {
+    let mut result = vec![];
+    for param in &generics.params {
+        match param {
+            syn::GenericParam::Type(ty) => {
+                if ty.default.is_some() {
+                    return Err(ParseError::new(
+                        item,
+                        format!("default for generic `{}` is not supported", ty.ident),
+                    ));
+                }
+                result.push(
+                    Generic::new(&ty.ident)
+                        .add_trait_bounds(ty.bounds.iter().map(tidy))
+                        .to_owned(),
+                );
+            }
+            syn::GenericParam::Lifetime(lt) => result.push(
+                Generic::new(&lt.lifetime)
+                    .add_trait_bounds(lt.bounds.iter().map(tidy))
+                    .to_owned(),
+            ),
+            syn::GenericParam::Const(c) => {
+                return Err(ParseError::new(
+                    item,
+                    format!("const generic `{}` is not supported", c.ident),
+                ))
+            }
+        }
+    }
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            let (name, bounds) = match predicate {
+                syn::WherePredicate::Type(p) if p.lifetimes.is_none() => {
+                    (tidy(&p.bounded_ty), p.bounds.iter().map(tidy).collect())
+                }
+                syn::WherePredicate::Lifetime(p) => {
+                    (tidy(&p.lifetime), p.bounds.iter().map(tidy).collect())
+                }
+                _ => (String::new(), vec![]),
+            };
+            match result.iter_mut().find(|g| g.name() == name) {
+                Some(generic) => {
+                    generic.add_trait_bounds(bounds);
+                }
+                None => {
+                    return Err(ParseError::new(
+                        item,
+                        format!("where predicate `{}` is not supported", raw(predicate)),
+                    ))
+                }
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Convert a function signature; the visibility and attributes of the item
+/// holding it are passed separately.
+fn signature(
+    sig: &syn::Signature,
+    vis: &syn::Visibility,
+    attrs: &[syn::Attribute],
+) -> Result<FunctionSignature, ParseError> {
+    let item = sig.ident.to_string();
+    if sig.constness.is_some() || sig.unsafety.is_some() || sig.abi.is_some() {
+        return Err(ParseError::new(
+            item,
+            "`const`, `unsafe` and `extern` functions are not supported",
+        ));
+    }
+    let mut signature = FunctionSignature::new(&sig.ident);
+    signature
+        .set_is_pub(is_pub(vis, &item)?)
+        .set_is_async(sig.asyncness.is_some())
+        .set_is_variadic(sig.variadic.is_some())
+        .add_generics(&generics(&sig.generics, &item)?);
+    for input in &sig.inputs {
+        let param = match input {
+            syn::FnArg::Receiver(receiver) => {
+                let ty = match &receiver.reference {
+                    Some((_, lifetime)) => format!(
+                        "&{}{}Self",
+                        lifetime
+                            .as_ref()
+                            .map(|l| format!("{} ", l))
+                            .unwrap_or_default(),
+                        if receiver.mutability.is_some() {
+                            "mut "
+                        } else {
+                            ""
+                        }
+                    ),
+                    None => "Self".to_string(),
+                };
+                let name = if receiver.reference.is_none() && receiver.mutability.is_some() {
+                    "mut self"
+                } else {
+                    "self"
+                };
+                Parameter::new(name, ty)
+                    .add_attributes(parameter_attributes(&receiver.attrs, &item)?)
+                    .to_owned()
+            }
+            syn::FnArg::Typed(pat) => Parameter::new(raw(&pat.pat), tidy(&pat.ty))
+                .add_attributes(parameter_attributes(&pat.attrs, &item)?)
+                .to_owned(),
+        };
+        signature.add_parameter(param);
+    }
+    if let syn::ReturnType::Type(_, ty) = &sig.output {
+        signature.set_return_ty(Some(tidy(ty)));
+    }
+    Attrs::new(attrs).apply(&mut signature, &item)?;
+    Ok(signature)
+}
+
+/// Convert a function with a body, inner attributes become body attributes.
+fn function(
+    sig: &syn::Signature,
+    vis: &syn::Visibility,
+    attrs: &[syn::Attribute],
+    block: &syn::Block,
+) -> Result<Function, ParseError> {
+    let outer = attrs
+        .iter()
+        .filter(|a| matches!(a.style, syn::AttrStyle::Outer))
+        .cloned()
+        .collect::<Vec<syn::Attribute>>();
+    let mut function = Function::from(signature(sig, vis, &outer)?);
+    let inner = Attrs::new(attrs).inner;
+    for attribute in inner {
+        function.add_body_attribute(attribute);
+    }
+    for stmt in &block.stmts {
+        function.push_into_body(raw(stmt));
+    }
+    Ok(function)
+}
+
+/// The attributes of a parameter, which can't be documented
+fn parameter_attributes(
+    attrs: &[syn::Attribute],
+    item: &str,
+) -> Result<Vec<Attribute>, ParseError> {
+    let attrs = Attrs::new(attrs);
+    if !attrs.docs.is_empty() {
+        return Err(ParseError::new(
+            item,
+            "doc comments on parameters are not supported",
+        ));
+    }
+    Ok(attrs.outer)
+}
+
+fn field(field: &syn::Field, item: &str) -> Result<Field, ParseError> {
+    let name = field
+        .ident
+        .as_ref()
+        .ok_or_else(|| ParseError::new(item, "tuple fields are not supported"))?;
+    let mut f = Field::new(name, tidy(&field.ty));
+    f.set_is_pub(is_pub(&field.vis, item)?);
+    Attrs::new(&field.attrs).apply(&mut f, item)?;
+    Ok(f)
+}
+
+impl TryFrom<&syn::ItemStruct> for Struct {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemStruct) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let fields = match &item.fields {
+            syn::Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(ParseError::new(
+                    name,
+                    "only structs with named fields are supported",
+                ))
+            }
+        };
+        let mut s = Struct::new(&name);
+        s.set_is_pub(is_pub(&item.vis, &name)?)
+            .add_generics(&generics(&item.generics, &name)?);
+        for f in fields {
+            s.add_field(field(f, &name)?);
+        }
+        Attrs::new(&item.attrs).apply(&mut s, &name)?;
+        Ok(s)
+    }
+}
+
+impl TryFrom<&syn::ItemUnion> for Union {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemUnion) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let mut u = Union::new(&name);
+        u.set_is_pub(is_pub(&item.vis, &name)?)
+            .add_generics(&generics(&item.generics, &name)?);
+        for f in &item.fields.named {
+            u.add_field(field(f, &name)?);
+        }
+        Attrs::new(&item.attrs).apply(&mut u, &name)?;
+        Ok(u)
+    }
+}
+
+impl TryFrom<&syn::Variant> for Variant {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::Variant) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let field = |f: &syn::Field| {
+            let attrs = Attrs::new(&f.attrs);
+            // Doc comments run to the end of their line
+            let mut src = attrs
+                .docs
+                .iter()
+                .map(|d| format!("{}\n", d))
+                .chain(attrs.outer.iter().map(|a| format!("{} ", a.generate())))
+                .collect::<String>();
+            if let Some(ident) = &f.ident {
+                src.push_str(&format!("{}: ", ident));
+            }
+            src.push_str(&tidy(&f.ty));
+            src
+        };
+        let mut inner = match &item.fields {
+            syn::Fields::Named(fields) => format!(
+                " {{ {} }}",
+                fields
+                    .named
+                    .iter()
+                    .map(field)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            syn::Fields::Unnamed(fields) => format!(
+                "({})",
+                fields
+                    .unnamed
+                    .iter()
+                    .map(field)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            syn::Fields::Unit => String::new(),
+        };
+        if let Some((_, discriminant)) = &item.discriminant {
+            inner.push_str(&format!(" = {}", raw(discriminant)));
+        }
+        let mut v = Variant::new(&name);
+        if !inner.is_empty() {
+            v.set_inner(Some(inner));
+        }
+        Attrs::new(&item.attrs).apply(&mut v, &name)?;
+        Ok(v)
+    }
+}
+
+impl TryFrom<&syn::ItemEnum> for Enum {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemEnum) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let mut e = Enum::new(&name);
+        e.set_is_pub(is_pub(&item.vis, &name)?)
+            .add_generics(&generics(&item.generics, &name)?);
+        for variant in &item.variants {
+            e.add_variant(Variant::try_from(variant)?);
+        }
+        Attrs::new(&item.attrs).apply(&mut e, &name)?;
+        Ok(e)
+    }
+}
+
+impl TryFrom<&syn::ItemFn> for Function {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemFn) -> Result<Self, Self::Error> {
+        function(&item.sig, &item.vis, &item.attrs, &item.block)
+    }
+}
+
+impl TryFrom<&syn::ItemImpl> for Impl {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemImpl) -> Result<Self, Self::Error> {
+        let name = format!("impl {}", tidy(&item.self_ty));
+        if item.unsafety.is_some() || item.defaultness.is_some() {
+            return Err(ParseError::new(
+                name,
+                "`unsafe` and `default` impls are not supported",
+            ));
+        }
+        let generics = generics(&item.generics, &name)?;
+
+        // `Impl` renders the generic parameters after the object name itself,
+        // so they have to be split off the type being implemented.
+        let obj_name = match &*item.self_ty {
+            _ if generics.is_empty() => tidy(&item.self_ty),
+            syn::Type::Path(ty) if ty.qself.is_none() => {
+                let mut path = ty.path.clone();
+                let last = path.segments.last_mut().unwrap();
+                let args = match &last.arguments {
+                    syn::PathArguments::AngleBracketed(args) => {
+                        args.args.iter().map(tidy).collect::<Vec<String>>()
+                    }
+                    _ => vec![],
+                };
+                if args != generics.iter().map(|g| g.name()).collect::<Vec<&str>>() {
+                    return Err(ParseError::new(
+                        name,
+                        "type arguments must match the impl generics",
+                    ));
+                }
+                last.arguments = syn::PathArguments::None;
+                tidy(&path)
+            }
+            _ => {
+                return Err(ParseError::new(
+                    name,
+                    "type arguments must match the impl generics",
+                ))
+            }
+        };
+
+        let mut ipl = Impl::new(obj_name);
+        ipl.add_generics(&generics);
+        if let Some((bang, path, _)) = &item.trait_ {
+            if bang.is_some() {
+                return Err(ParseError::new(name, "negative impls are not supported"));
+            }
+            ipl.set_impl_trait(Some(Trait::new(tidy(path))));
+        }
+        for impl_item in &item.items {
+            match impl_item {
+                syn::ImplItem::Method(m) => {
+                    if m.defaultness.is_some() {
+                        return Err(ParseError::new(
+                            &m.sig.ident,
+                            "`default` functions are not supported",
+                        ));
+                    }
+                    ipl.add_function(function(&m.sig, &m.vis, &m.attrs, &m.block)?);
+                }
+                syn::ImplItem::Type(t) if t.generics.params.is_empty() => {
+                    let mut ty = AssociatedTypeDefinition::new(&t.ident, tidy(&t.ty));
+                    Attrs::new(&t.attrs).apply(&mut ty, &name)?;
+                    ipl.add_associated_type(ty);
+                }
+                other => {
+                    return Err(ParseError::new(
+                        name,
+                        format!("impl item `{}` is not supported", raw(other)),
+                    ))
+                }
+            }
+        }
+        Attrs::new(&item.attrs).apply(&mut ipl, &name)?;
+        Ok(ipl)
+    }
+}
+
+impl TryFrom<&syn::ItemTrait> for Trait {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemTrait) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        if item.unsafety.is_some() || item.auto_token.is_some() {
+            return Err(ParseError::new(
+                name,
+                "`unsafe` and `auto` traits are not supported",
+            ));
+        }
+        if !item.supertraits.is_empty() {
+            return Err(ParseError::new(name, "supertraits are not supported"));
+        }
+        let mut t = Trait::new(&name);
+        t.set_is_pub(is_pub(&item.vis, &name)?)
+            .add_generics(&generics(&item.generics, &name)?);
+        for trait_item in &item.items {
+            match trait_item {
+                syn::TraitItem::Method(m) => match &m.default {
+                    Some(block) => {
+                        t.add_function(function(
+                            &m.sig,
+                            &syn::Visibility::Inherited,
+                            &m.attrs,
+                            block,
+                        )?);
+                    }
+                    None => {
+                        t.add_signature(signature(&m.sig, &syn::Visibility::Inherited, &m.attrs)?);
+                    }
+                },
+                syn::TraitItem::Type(ty)
+                    if ty.default.is_none() && ty.generics.params.is_empty() =>
+                {
+                    let mut declaration = AssociatedTypeDeclaration::new(&ty.ident);
+                    declaration.add_trait_bounds(ty.bounds.iter().map(tidy));
+                    Attrs::new(&ty.attrs).apply(&mut declaration, &name)?;
+                    t.add_associated_type(declaration);
+                }
+                other => {
+                    return Err(ParseError::new(
+                        name,
+                        format!("trait item `{}` is not supported", raw(other)),
+                    ))
+                }
+            }
+        }
+        Attrs::new(&item.attrs).apply(&mut t, &name)?;
+        Ok(t)
+    }
+}
+
+impl TryFrom<&syn::ItemConst> for Const {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemConst) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let mut c = Const::new(&name, tidy(&item.ty), raw(&item.expr));
+        c.set_is_pub(is_pub(&item.vis, &name)?);
+        Attrs::new(&item.attrs).apply(&mut c, &name)?;
+        Ok(c)
+    }
+}
+
+impl TryFrom<&syn::ItemStatic> for Static {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemStatic) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let mut s = Static::new(&name, tidy(&item.ty), raw(&item.expr));
+        s.set_is_pub(is_pub(&item.vis, &name)?)
+            .set_is_mut(item.mutability.is_some());
+        Attrs::new(&item.attrs).apply(&mut s, &name)?;
+        Ok(s)
+    }
+}
+
+impl TryFrom<&syn::ItemType> for TypeAlias {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemType) -> Result<Self, Self::Error> {
+        let name = item.ident.to_string();
+        let mut t = TypeAlias::new(&name, tidy(&item.ty));
+        t.set_is_pub(is_pub(&item.vis, &name)?)
+            .add_generics(&generics(&item.generics, &name)?);
+        Attrs::new(&item.attrs).apply(&mut t, &name)?;
+        Ok(t)
+    }
+}
+
+/// Split the tokens of a `macro_rules!` body into its rules.
+fn macro_rules(tokens: &TokenStream, item: &str) -> Result<Vec<MacroRule>, ParseError> {
+    let invalid = || ParseError::new(item, "unable to split macro into its rules");
+    let mut rules = vec![];
+    let mut tokens = tokens.clone().into_iter().peekable();
+    while tokens.peek().is_some() {
+        let matcher = match tokens.next() {
+            Some(TokenTree::Group(g)) => g.stream().to_string(),
+            _ => return Err(invalid()),
+        };
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Punct(eq)), Some(TokenTree::Punct(gt)))
+                if eq.as_char() == '=' && gt.as_char() == '>' => {}
+            _ => return Err(invalid()),
+        }
+        let transcriber = match tokens.next() {
+            Some(TokenTree::Group(g)) => g.stream().to_string(),
+            _ => return Err(invalid()),
+        };
+        if let Some(TokenTree::Punct(p)) = tokens.peek() {
+            if p.as_char() == ';' {
+                tokens.next();
+            }
+        }
+        rules.push(MacroRule::new(matcher, transcriber));
+    }
+    Ok(rules)
+}
+
+impl TryFrom<&syn::ItemMacro> for MacroRules {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemMacro) -> Result<Self, Self::Error> {
+        let name = match &item.ident {
+            Some(ident) if item.mac.path.is_ident("macro_rules") => ident.to_string(),
+            _ => {
+                return Err(ParseError::new(
+                    raw(&item.mac.path),
+                    "not a `macro_rules!` definition",
+                ))
+            }
+        };
+        let mut m = MacroRules::new(&name);
+        for rule in macro_rules(&item.mac.tokens, &name)? {
+            m.add_rule(rule);
+        }
+        let mut attrs = Attrs::new(&item.attrs);
+        let exported = attrs.outer.len();
+        attrs
+            .outer
+            .retain(|a| norm_whitespace(&a.generate()) != "#[macro_export]");
+        m.set_is_exported(exported != attrs.outer.len());
+        attrs.apply(&mut m, &name)?;
+        Ok(m)
+    }
+}
+
+impl TryFrom<&syn::ItemMacro> for MacroInvocation {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemMacro) -> Result<Self, Self::Error> {
+        let name = tidy(&item.mac.path);
+        if item.ident.is_some() {
+            return Err(ParseError::new(name, "not a macro invocation"));
+        }
+        let delimiter = match item.mac.delimiter {
+            syn::MacroDelimiter::Paren(_) => MacroDelimiter::Paren,
+            syn::MacroDelimiter::Bracket(_) => MacroDelimiter::Bracket,
+            syn::MacroDelimiter::Brace(_) => MacroDelimiter::Brace,
+        };
+        let attrs = Attrs::new(&item.attrs);
+        if !attrs.docs.is_empty() || !attrs.inner.is_empty() {
+            return Err(ParseError::new(
+                name,
+                "documentation on macro invocations is not supported",
+            ));
+        }
+        Ok(MacroInvocation::new(&name)
+            .set_delimiter(delimiter)
+            .set_tokens(raw(&item.mac.tokens))
+            .add_attributes(attrs.outer)
+            .to_owned())
+    }
+}
+
+impl TryFrom<&syn::ItemForeignMod> for ExternBlock {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemForeignMod) -> Result<Self, Self::Error> {
+        let abi = item
+            .abi
+            .name
+            .as_ref()
+            .map_or_else(|| "C".to_string(), |name| name.value());
+        let name = format!("extern \"{}\"", abi);
+        let mut block = ExternBlock::new(abi);
+        for foreign_item in &item.items {
+            match foreign_item {
+                syn::ForeignItem::Fn(f) => {
+                    block.add_function(signature(&f.sig, &f.vis, &f.attrs)?);
+                }
+                syn::ForeignItem::Static(s) => {
+                    let item = s.ident.to_string();
+                    let mut stat1c = ForeignStatic::new(&item, tidy(&s.ty));
+                    stat1c
+                        .set_is_pub(is_pub(&s.vis, &item)?)
+                        .set_is_mut(s.mutability.is_some());
+                    Attrs::new(&s.attrs).apply(&mut stat1c, &item)?;
+                    block.add_static(stat1c);
+                }
+                syn::ForeignItem::Type(t) => {
+                    let item = t.ident.to_string();
+                    let mut ty = ForeignType::new(&item);
+                    ty.set_is_pub(is_pub(&t.vis, &item)?);
+                    Attrs::new(&t.attrs).apply(&mut ty, &item)?;
+                    block.add_type(ty);
+                }
+                other => {
+                    return Err(ParseError::new(
+                        name,
+                        format!("foreign item `{}` is not supported", raw(other)),
+                    ))
+                }
+            }
+        }
+        Attrs::new(&item.attrs).apply(&mut block, &name)?;
+        Ok(block)
+    }
+}
+
+impl Module {
+    /// Create a module from the contents of a parsed source file. The module has an
+    /// empty name, set one with `Module::set_name` before rendering it as a `mod` item.
+    ///
+    /// Items which can't be represented are kept verbatim in their position, see
+    /// `Module::add_raw_item`, and returned along with the module, including those
+    /// within submodules.
+    pub fn from_file(file: &syn::File) -> (Self, Vec<ParseError>) {
+        let mut module = Module::new("");
+        let mut unsupported = vec![];
+        module.add_items(&file.attrs, &file.items, &mut unsupported);
+        (module, unsupported)
+    }
+
+    /// Add the attributes and items of a parsed module or file to this module,
+    /// pushing items which can't be represented to `unsupported`.
+    fn add_items(
+        &mut self,
+        attrs: &[syn::Attribute],
+        items: &[syn::Item],
+        unsupported: &mut Vec<ParseError>,
+    ) {
+        let attrs = Attrs::new(attrs);
+        // Module docs are always rendered within the module.
+        self.add_docs(attrs.docs.iter().map(|d| {
+            format!(
+                "//!{}",
+                d.trim_start_matches("///").trim_start_matches("//!")
+            )
+        }))
+        .add_attributes(attrs.outer)
+        .add_attributes(attrs.inner);
+        for item in items {
+            if let Err(err) = self.add_item(item, unsupported) {
+                self.add_raw_item(raw(item));
+                unsupported.push(err);
+            }
+        }
+    }
+
+    /// Convert a parsed inline module, pushing items within it which can't be
+    /// represented to `unsupported`.
+    fn from_item_mod(
+        item: &syn::ItemMod,
+        unsupported: &mut Vec<ParseError>,
+    ) -> Result<Self, ParseError> {
+        let name = item.ident.to_string();
+        let items = match &item.content {
+            Some((_, items)) => items,
+            None => {
+                return Err(ParseError::new(
+                    name,
+                    "module declarations without a body are not supported",
+                ))
+            }
+        };
+        let mut module = Module::new(&name);
+        module.set_is_pub(is_pub(&item.vis, &name)?);
+        module.add_items(&item.attrs, items, unsupported);
+        Ok(module)
+    }
+
+    fn add_item(
+        &mut self,
+        item: &syn::Item,
+        unsupported: &mut Vec<ParseError>,
+    ) -> Result<(), ParseError> {
+        match item {
+            syn::Item::Struct(i) => self.add_struct(Struct::try_from(i)?),
+            syn::Item::Enum(i) => self.add_enum(Enum::try_from(i)?),
+            syn::Item::Union(i) => self.add_union(Union::try_from(i)?),
+            syn::Item::Fn(i) => self.add_function(Function::try_from(i)?),
+            syn::Item::Impl(i) => self.add_impl(Impl::try_from(i)?),
+            syn::Item::Trait(i) => self.add_trait(Trait::try_from(i)?),
+            syn::Item::Const(i) => self.add_const(Const::try_from(i)?),
+            syn::Item::Static(i) => self.add_static(Static::try_from(i)?),
+            syn::Item::Type(i) => self.add_type_alias(TypeAlias::try_from(i)?),
+            syn::Item::ForeignMod(i) => self.add_extern_block(ExternBlock::try_from(i)?),
+            syn::Item::Macro(i) if i.ident.is_some() => self.add_macro(MacroRules::try_from(i)?),
+            syn::Item::Macro(i) => self.add_macro_invocation(MacroInvocation::try_from(i)?),
+            syn::Item::Mod(i) => self.add_submodule(Module::from_item_mod(i, unsupported)?),
+            syn::Item::Use(i) => self.add_use_statement(tidy(i)),
+            other => return Err(ParseError::new(raw(other), "item is not supported")),
+        };
+        Ok(())
+    }
+}
+
+/// Fails if any item within the module can't be represented, see `Module::from_file`
+/// to keep those verbatim instead.
+impl TryFrom<&syn::ItemMod> for Module {
+    type Error = ParseError;
+
+    fn try_from(item: &syn::ItemMod) -> Result<Self, Self::Error> {
+        let mut unsupported = vec![];
+        let module = Module::from_item_mod(item, &mut unsupported)?;
+        match unsupported.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(module),
+        }
+    }
+}
@@ -0,0 +1,642 @@
+//!
+//! Find structs, enums and unions containing themselves by value, ie. generated from a
+//! self or mutually recursive schema, and box the fields breaking the cycles so the
+//! types have a finite size.
+//!
+//! A type contains another by value through a field or variant using it directly, in
+//! a tuple, an array or as the argument of a generic type such as `Option`. Containers
+//! holding their contents on the heap, such as `Box`, `Vec`, `Rc` or `HashMap`, and
+//! references or pointers break the chain. Type names are resolved within the tree
+//! through `crate`, `self`, `super`, submodules and `use` statements.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let mut m = Module::new("ast")
+//!     .with_struct(
+//!         Struct::new("Node")
+//!             .with_field(Field::new("value", "Expr"))
+//!             .with_field(Field::new("children", "Vec<Node>"))
+//!             .with_field(Field::new("next", "Option<Node>")),
+//!     )
+//!     .with_enum(
+//!         Enum::new("Expr")
+//!             .with_variant(Variant::new("Literal").with_inner(Some("(i64)")))
+//!             .with_variant(Variant::new("Pair").with_inner(Some("(Node, i64)")))
+//!             .with_variant(Variant::new("Neg").with_inner(Some("(Expr)"))),
+//!     );
+//!
+//! assert_eq!(m.recursive_types()[0].types(), ["Node", "Expr"]);
+//!
+//! let boxed = m.box_recursive_types();
+//! assert_eq!(boxed[0].to_string(), "field `Node::next` holds `Node` by value");
+//! assert_eq!(boxed[1].to_string(), "variant `Expr::Pair` holds `Node` by value");
+//! assert_eq!(boxed[2].to_string(), "variant `Expr::Neg` holds `Expr` by value");
+//!
+//! let node = m.get_struct("Node").unwrap();
+//! assert_eq!(node.get_field("next").unwrap().ty(), "Option<Box<Node>>");
+//! let expr = m.get_enum("Expr").unwrap();
+//! assert_eq!(expr.get_variant("Pair").unwrap().inner(), Some("(Box<Node>, i64)"));
+//! assert!(m.recursive_types().is_empty());
+//! ```
+
+use std::fmt;
+
+use crate::resolve::{imports, module_at, type_paths, Span};
+use crate::*;
+
+/// Cycles through up to this many fields and variants are searched exhaustively for
+/// the fewest to box
+const MAX_EXACT_EDGES: usize = 16;
+
+/// Types of a module tree containing each other by value
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    types: Vec<String>,
+}
+
+impl Cycle {
+    /// Names of the types, qualified by their submodules relative to the module
+    /// analysed, in the order they're rendered
+    pub fn types(&self) -> &[String] {
+        self.types.as_slice()
+    }
+}
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let types = self
+            .types
+            .iter()
+            .map(|t| format!("`{}`", t))
+            .collect::<Vec<String>>();
+        write!(f, "recursive types {}", types.join(", "))
+    }
+}
+
+/// A field or variant holding a type of its own cycle by value, to box to break the
+/// cycle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RecursiveEdge {
+    path: Vec<String>,
+    kind: ItemKind,
+    name: String,
+    target: String,
+}
+
+impl RecursiveEdge {
+    /// Names of the submodules containing the field or variant
+    pub fn path(&self) -> &[String] {
+        self.path.as_slice()
+    }
+
+    /// Either `ItemKind::Field` or `ItemKind::Variant`
+    pub fn kind(&self) -> ItemKind {
+        self.kind
+    }
+
+    /// Name of the field or variant, qualified by its type, ie. `Node::next`
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Name of the type held by value, qualified by its submodules relative to the
+    /// module analysed
+    pub fn target(&self) -> &str {
+        self.target.as_str()
+    }
+}
+
+impl fmt::Display for RecursiveEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self
+            .path
+            .iter()
+            .map(|m| format!("{}::", m))
+            .collect::<String>();
+        write!(
+            f,
+            "{} `{}{}` holds `{}` by value",
+            self.kind, path, self.name, self.target
+        )
+    }
+}
+
+impl Module {
+    /// The cycles of types within this module and its submodules containing each other
+    /// by value, in the order their first type is rendered
+    pub fn recursive_types(&self) -> Vec<Cycle> {
+        let graph = Graph::new(self);
+        graph
+            .cycles()
+            .into_iter()
+            .map(|cycle| Cycle {
+                types: cycle
+                    .into_iter()
+                    .map(|t| graph.types[t].qualified())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// The fields and variants to box to break every cycle of `recursive_types`: the
+    /// fewest possible for cycles through up to 16 fields and variants. Larger cycles
+    /// box the members closing a cycle when walking types in the order they're
+    /// rendered, a sufficient set though not always the smallest.
+    pub fn recursive_edges(&self) -> Vec<RecursiveEdge> {
+        let graph = Graph::new(self);
+        graph
+            .back_edges()
+            .into_iter()
+            .map(|e| graph.recursive_edge(e))
+            .collect()
+    }
+
+    /// Wrap the fields and variants of `recursive_edges` in `Box`, returning them
+    pub fn box_recursive_types(&mut self) -> Vec<RecursiveEdge> {
+        let graph = Graph::new(self);
+        let back_edges = graph.back_edges();
+
+        // Members holding several types to box are rewritten at once, as the spans
+        // refer to the type before any rewrite
+        let mut members: Vec<(usize, &str, Vec<Span>)> = vec![];
+        for &e in &back_edges {
+            let edge = &graph.edges[e];
+            match members
+                .iter_mut()
+                .find(|(from, member, _)| *from == edge.from && *member == edge.member)
+            {
+                Some((_, _, spans)) => spans.extend(edge.spans.iter().cloned()),
+                None => members.push((edge.from, edge.member.as_str(), edge.spans.clone())),
+            }
+        }
+        for (from, member, spans) in members {
+            let def = &graph.types[from];
+            let module = def
+                .path
+                .iter()
+                .try_fold(&mut *self, |m, name| m.get_submodule_mut(name.as_str()));
+            let module = match module {
+                Some(module) => module,
+                None => continue,
+            };
+            match def.kind {
+                ItemKind::Struct => {
+                    if let Some(field) = module
+                        .get_struct_mut(&def.name)
+                        .and_then(|s| s.get_field_mut(member))
+                    {
+                        let ty = box_spans(field.ty(), &spans);
+                        field.set_ty(ty);
+                    }
+                }
+                ItemKind::Union => {
+                    if let Some(field) = module
+                        .get_union_mut(&def.name)
+                        .and_then(|u| u.get_field_mut(member))
+                    {
+                        let ty = box_spans(field.ty(), &spans);
+                        field.set_ty(ty);
+                    }
+                }
+                _ => {
+                    if let Some(variant) = module
+                        .get_enum_mut(&def.name)
+                        .and_then(|e| e.get_variant_mut(member))
+                    {
+                        let inner = variant.inner().map(|inner| box_spans(inner, &spans));
+                        variant.set_inner(inner);
+                    }
+                }
+            }
+        }
+
+        back_edges
+            .into_iter()
+            .map(|e| graph.recursive_edge(e))
+            .collect()
+    }
+}
+
+/// Wrap the spans of `ty`, in chars, in `Box<...>`
+fn box_spans(ty: &str, spans: &[Span]) -> String {
+    let mut insertions = spans
+        .iter()
+        .flat_map(|&(start, end)| vec![(end, ">"), (start, "Box<")])
+        .collect::<Vec<(usize, &str)>>();
+    insertions.sort_by_key(|&(at, _)| std::cmp::Reverse(at));
+    let mut chars = ty.chars().collect::<Vec<char>>();
+    for (at, text) in insertions {
+        chars.splice(at..at, text.chars());
+    }
+    chars.into_iter().collect()
+}
+
+/// A struct, enum or union of the tree
+struct TypeDef {
+    path: Vec<String>,
+    name: String,
+    kind: ItemKind,
+}
+
+impl TypeDef {
+    fn qualified(&self) -> String {
+        let mut qualified = self.path.clone();
+        qualified.push(self.name.clone());
+        qualified.join("::")
+    }
+}
+
+/// A field or variant of one type holding another by value
+struct Edge {
+    from: usize,
+    to: usize,
+    member: String,
+    spans: Vec<Span>,
+}
+
+/// The types of a module tree and the by-value edges between them, in render order
+struct Graph {
+    types: Vec<TypeDef>,
+    edges: Vec<Edge>,
+}
+
+/// If `name` is a struct, enum or union of `module`
+fn defines_type(module: &Module, name: &str) -> bool {
+    module.get_struct(name).is_some()
+        || module.get_enum(name).is_some()
+        || module.get_union(name).is_some()
+}
+
+/// The module path and name of the type `path` refers to within the module at
+/// `module_path`, if defined in the tree
+fn resolve_type(
+    root: &Module,
+    module_path: &[String],
+    path: &str,
+    depth: usize,
+) -> Option<(Vec<String>, String)> {
+    let segments = path.split("::").collect::<Vec<&str>>();
+    let (name, modules) = segments.split_last()?;
+    let mut current = module_path.to_vec();
+    let mut modules = modules.iter().peekable();
+    if modules.peek() == Some(&&"crate") {
+        modules.next();
+        current.clear();
+    }
+    for segment in modules {
+        match *segment {
+            "self" => (),
+            "super" => {
+                current.pop()?;
+            }
+            segment => {
+                module_at(root, &current)?.get_submodule(segment)?;
+                current.push(segment.to_string());
+            }
+        }
+    }
+    let module = module_at(root, &current)?;
+    if defines_type(module, name) {
+        return Some((current, name.to_string()));
+    }
+    // Follow imports within the tree, a few levels deep to guard against cycles
+    if depth > 8 {
+        return None;
+    }
+    for statement in module.use_statements() {
+        for import in imports(statement) {
+            let target = if import.name == *name {
+                import.path.join("::")
+            } else if import.name == "*" {
+                format!("{}::{}", import.path.join("::"), name)
+            } else {
+                continue;
+            };
+            if let Some(resolved) = resolve_type(root, &current, &target, depth + 1) {
+                return Some(resolved);
+            }
+        }
+    }
+    None
+}
+
+impl Graph {
+    fn new(root: &Module) -> Self {
+        let mut types = vec![];
+        collect_types(root, &mut vec![], &mut types);
+
+        let mut edges: Vec<Edge> = vec![];
+        for (from, def) in types.iter().enumerate() {
+            let module = match module_at(root, &def.path) {
+                Some(module) => module,
+                None => continue,
+            };
+            let (generics, members): (Vec<&str>, Vec<(&str, &str)>) = match def.kind {
+                ItemKind::Struct => {
+                    let s = module.get_struct(&def.name).expect("collected struct");
+                    (
+                        s.generics().iter().map(Generic::name).collect(),
+                        s.fields().iter().map(|f| (f.name(), f.ty())).collect(),
+                    )
+                }
+                ItemKind::Union => {
+                    let u = module.get_union(&def.name).expect("collected union");
+                    (
+                        u.generics().iter().map(Generic::name).collect(),
+                        u.fields().iter().map(|f| (f.name(), f.ty())).collect(),
+                    )
+                }
+                _ => {
+                    let e = module.get_enum(&def.name).expect("collected enum");
+                    (
+                        e.generics().iter().map(Generic::name).collect(),
+                        e.variants()
+                            .iter()
+                            .filter_map(|v| v.inner().map(|inner| (v.name(), inner)))
+                            .collect(),
+                    )
+                }
+            };
+            for (member, ty) in members {
+                for occurrence in type_paths(ty).into_iter().filter(|p| p.by_value) {
+                    if generics.contains(&occurrence.path.as_str()) {
+                        continue;
+                    }
+                    let target = resolve_type(root, &def.path, &occurrence.path, 0).and_then(
+                        |(path, name)| types.iter().position(|t| t.path == path && t.name == name),
+                    );
+                    let to = match target {
+                        Some(to) => to,
+                        None => continue,
+                    };
+                    match edges
+                        .iter_mut()
+                        .find(|e| e.from == from && e.to == to && e.member == member)
+                    {
+                        Some(edge) => edge.spans.push(occurrence.span),
+                        None => edges.push(Edge {
+                            from,
+                            to,
+                            member: member.to_string(),
+                            spans: vec![occurrence.span],
+                        }),
+                    }
+                }
+            }
+        }
+        Graph { types, edges }
+    }
+
+    /// Indices of the edges leaving each type
+    fn adjacency(&self) -> Vec<Vec<usize>> {
+        let mut adjacency = vec![vec![]; self.types.len()];
+        for (e, edge) in self.edges.iter().enumerate() {
+            adjacency[edge.from].push(e);
+        }
+        adjacency
+    }
+
+    /// The strongly connected components with a cycle, found with Tarjan's algorithm
+    fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            adjacency: self.adjacency(),
+            edges: &self.edges,
+            index: vec![None; self.types.len()],
+            low_link: vec![0; self.types.len()],
+            on_stack: vec![false; self.types.len()],
+            stack: vec![],
+            next_index: 0,
+            components: vec![],
+        };
+        for t in 0..self.types.len() {
+            if tarjan.index[t].is_none() {
+                tarjan.connect(t);
+            }
+        }
+        let mut cycles = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1
+                    || self
+                        .edges
+                        .iter()
+                        .any(|e| e.from == component[0] && e.to == component[0])
+            })
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect::<Vec<Vec<usize>>>();
+        cycles.sort();
+        cycles
+    }
+
+    /// The fewest edges to remove to leave no cycle, searched exhaustively within
+    /// each cycle of up to `MAX_EXACT_EDGES` edges. Larger cycles fall back to the
+    /// edges closing a cycle in a depth first walk of the types in render order, none
+    /// of which can be kept without leaving a cycle.
+    fn back_edges(&self) -> Vec<usize> {
+        let walked = self.walk_back_edges();
+        let mut back_edges = vec![];
+        for component in self.cycles() {
+            let internal = (0..self.edges.len())
+                .filter(|&e| {
+                    component.contains(&self.edges[e].from) && component.contains(&self.edges[e].to)
+                })
+                .collect::<Vec<usize>>();
+            let walked = walked
+                .iter()
+                .cloned()
+                .filter(|e| internal.contains(e))
+                .collect::<Vec<usize>>();
+            // The walk's edges are kept unless strictly fewer break the cycle
+            let fewest = if internal.len() <= MAX_EXACT_EDGES {
+                (1..walked.len()).find_map(|count| {
+                    first_combination(internal.len(), count, |picked| {
+                        let removed = picked.iter().map(|&i| internal[i]).collect::<Vec<usize>>();
+                        self.is_acyclic(&component, &internal, &removed)
+                    })
+                    .map(|picked| picked.into_iter().map(|i| internal[i]).collect())
+                })
+            } else {
+                None
+            };
+            back_edges.extend(fewest.unwrap_or(walked));
+        }
+        back_edges.sort_unstable();
+        back_edges
+    }
+
+    /// If the types of `component` hold no cycle through the `edges` not `removed`
+    fn is_acyclic(&self, component: &[usize], edges: &[usize], removed: &[usize]) -> bool {
+        let kept = edges
+            .iter()
+            .filter(|e| !removed.contains(e))
+            .map(|&e| &self.edges[e])
+            .collect::<Vec<&Edge>>();
+        // Repeatedly drop the types no kept edge leads to
+        let mut remaining = component.to_vec();
+        loop {
+            let reached = remaining
+                .iter()
+                .cloned()
+                .filter(|&t| {
+                    kept.iter()
+                        .any(|edge| edge.to == t && remaining.contains(&edge.from))
+                })
+                .collect::<Vec<usize>>();
+            if reached.is_empty() {
+                return true;
+            }
+            if reached.len() == remaining.len() {
+                return false;
+            }
+            remaining = reached;
+        }
+    }
+
+    /// The edges closing a cycle in a depth first walk of the types in render order
+    fn walk_back_edges(&self) -> Vec<usize> {
+        fn walk(
+            t: usize,
+            adjacency: &[Vec<usize>],
+            edges: &[Edge],
+            state: &mut [u8],
+            back_edges: &mut Vec<usize>,
+        ) {
+            state[t] = 1;
+            for &e in &adjacency[t] {
+                let to = edges[e].to;
+                match state[to] {
+                    0 => walk(to, adjacency, edges, state, back_edges),
+                    1 => back_edges.push(e),
+                    _ => (),
+                }
+            }
+            state[t] = 2;
+        }
+
+        let adjacency = self.adjacency();
+        let mut state = vec![0; self.types.len()];
+        let mut back_edges = vec![];
+        for t in 0..self.types.len() {
+            if state[t] == 0 {
+                walk(t, &adjacency, &self.edges, &mut state, &mut back_edges);
+            }
+        }
+        back_edges
+    }
+
+    fn recursive_edge(&self, e: usize) -> RecursiveEdge {
+        let edge = &self.edges[e];
+        let from = &self.types[edge.from];
+        RecursiveEdge {
+            path: from.path.clone(),
+            kind: match from.kind {
+                ItemKind::Enum => ItemKind::Variant,
+                _ => ItemKind::Field,
+            },
+            name: format!("{}::{}", from.name, edge.member),
+            target: self.types[edge.to].qualified(),
+        }
+    }
+}
+
+/// The first combination of `count` indices below `len`, in lexicographic order,
+/// satisfying `accept`
+fn first_combination(
+    len: usize,
+    count: usize,
+    mut accept: impl FnMut(&[usize]) -> bool,
+) -> Option<Vec<usize>> {
+    if count > len {
+        return None;
+    }
+    let mut picked = (0..count).collect::<Vec<usize>>();
+    loop {
+        if accept(&picked) {
+            return Some(picked);
+        }
+        // Advance the rightmost index which can still move right
+        let i = (0..count).rev().find(|&i| picked[i] < len - count + i)?;
+        picked[i] += 1;
+        for j in i + 1..count {
+            picked[j] = picked[j - 1] + 1;
+        }
+    }
+}
+
+fn collect_types(module: &Module, path: &mut Vec<String>, types: &mut Vec<TypeDef>) {
+    let mut push = |name: &str, kind| {
+        types.push(TypeDef {
+            path: path.clone(),
+            name: name.to_string(),
+            kind,
+        })
+    };
+    for s in module.structs() {
+        push(s.name(), ItemKind::Struct);
+    }
+    for e in module.enums() {
+        push(e.name(), ItemKind::Enum);
+    }
+    for u in module.unions() {
+        push(u.name(), ItemKind::Union);
+    }
+    for submodule in module.submodules() {
+        path.push(submodule.name().to_string());
+        collect_types(submodule, path, types);
+        path.pop();
+    }
+}
+
+struct Tarjan<'a> {
+    adjacency: Vec<Vec<usize>>,
+    edges: &'a [Edge],
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next_index: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl<'a> Tarjan<'a> {
+    fn connect(&mut self, t: usize) {
+        self.index[t] = Some(self.next_index);
+        self.low_link[t] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(t);
+        self.on_stack[t] = true;
+
+        for e in self.adjacency[t].clone() {
+            let to = self.edges[e].to;
+            match self.index[to] {
+                None => {
+                    self.connect(to);
+                    self.low_link[t] = self.low_link[t].min(self.low_link[to]);
+                }
+                Some(index) if self.on_stack[to] => {
+                    self.low_link[t] = self.low_link[t].min(index);
+                }
+                _ => (),
+            }
+        }
+
+        if Some(self.low_link[t]) == self.index[t] {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == t {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//!
+//! Regenerate parts of existing files, keeping hand-written code intact.
+//!
+//! Generated code lives in managed regions, between `// proffer:begin <id>` and
+//! `// proffer:end <id>` markers. Regenerating a file only replaces the contents of
+//! its regions; everything outside of them, including the markers, is kept as is.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let existing = r#"
+//! use std::fmt;
+//!
+//! // proffer:begin models
+//! struct Old;
+//! // proffer:end models
+//!
+//! impl fmt::Display for User {
+//!     // hand-written
+//! }
+//! "#;
+//!
+//! let models = Module::new("models").add_struct(Struct::new("User")).to_owned();
+//! let src_code = replace_regions(existing, &[("models", models.generate_file())]).unwrap();
+//!
+//! assert!(src_code.contains("struct User"));
+//! assert!(!src_code.contains("struct Old"));
+//! assert!(src_code.contains("// hand-written"));
+//!
+//! let err = replace_regions(existing, &[("other", String::new())]).unwrap_err();
+//! assert_eq!(err.to_string(), "region `other` not found");
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::Module;
+
+/// Prefix of the marker opening a managed region
+const BEGIN: &str = "// proffer:begin ";
+/// Prefix of the marker closing a managed region
+const END: &str = "// proffer:end ";
+
+/// Error regenerating the managed regions of a file, line numbers start at 1
+#[derive(Debug)]
+pub enum RegionError {
+    /// Reading or writing the file failed
+    Io(io::Error),
+    /// A region to regenerate has no markers in the file
+    Missing(String),
+    /// A region id is empty or contains whitespace, so it can't be used in a marker
+    InvalidId(String),
+    /// A region to regenerate is given more than once
+    Repeated(String),
+    /// A marker has no region id
+    MissingId {
+        /// Line of the marker
+        line: usize,
+    },
+    /// A region is opened more than once
+    Duplicate {
+        /// Id of the region
+        id: String,
+        /// Line of the second `begin` marker
+        line: usize,
+    },
+    /// A region is opened within another region
+    Nested {
+        /// Id of the enclosing region
+        outer: String,
+        /// Id of the region opened within it
+        inner: String,
+        /// Line of the inner `begin` marker
+        line: usize,
+    },
+    /// A region is opened but never closed
+    Unclosed {
+        /// Id of the region
+        id: String,
+        /// Line of the `begin` marker
+        line: usize,
+    },
+    /// A region is closed without being opened, or while another region is open
+    UnexpectedEnd {
+        /// Id of the `end` marker
+        id: String,
+        /// Line of the `end` marker
+        line: usize,
+    },
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegionError::Io(err) => write!(f, "{}", err),
+            RegionError::Missing(id) => write!(f, "region `{}` not found", id),
+            RegionError::InvalidId(id) => write!(f, "invalid region id `{}`", id),
+            RegionError::Repeated(id) => write!(f, "region `{}` is given more than once", id),
+            RegionError::MissingId { line } => {
+                write!(f, "line {}: marker without a region id", line)
+            }
+            RegionError::Duplicate { id, line } => {
+                write!(f, "line {}: region `{}` is opened twice", line, id)
+            }
+            RegionError::Nested { outer, inner, line } => write!(
+                f,
+                "line {}: region `{}` is opened within region `{}`",
+                line, inner, outer
+            ),
+            RegionError::Unclosed { id, line } => {
+                write!(f, "line {}: region `{}` is never closed", line, id)
+            }
+            RegionError::UnexpectedEnd { id, line } => {
+                write!(f, "line {}: unexpected end of region `{}`", line, id)
+            }
+        }
+    }
+}
+
+impl Error for RegionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RegionError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for RegionError {
+    fn from(err: io::Error) -> Self {
+        RegionError::Io(err)
+    }
+}
+
+/// The id of a marker line starting with `prefix`, ignoring indentation. The id is
+/// empty if the marker lacks one.
+fn marker<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.trim()
+        .strip_prefix(prefix.trim_end())
+        .filter(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+        .map(str::trim)
+}
+
+/// Check `id` can be used in a marker
+fn validate_id(id: &str) -> Result<(), RegionError> {
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return Err(RegionError::InvalidId(id.to_string()));
+    }
+    Ok(())
+}
+
+/// Wrap `contents` within the markers of region `id`
+pub fn wrap_region(id: &str, contents: &str) -> String {
+    format!(
+        "{}{}\n{}\n{}{}\n",
+        BEGIN,
+        id,
+        contents.trim_matches('\n'),
+        END,
+        id
+    )
+}
+
+/// Replace the contents of the managed regions of `src`, given as `(id, contents)`
+/// pairs. Regions in `src` which aren't given are kept as they are, while a given
+/// region missing from `src` is an error, as are unbalanced or nested markers, markers
+/// without an id and regions given more than once.
+pub fn replace_regions(src: &str, regions: &[(&str, String)]) -> Result<String, RegionError> {
+    for (idx, (id, _)) in regions.iter().enumerate() {
+        validate_id(id)?;
+        if regions[..idx].iter().any(|(r, _)| r == id) {
+            return Err(RegionError::Repeated(id.to_string()));
+        }
+    }
+    let mut output = String::new();
+    let mut open: Option<(&str, usize)> = None;
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+
+    for (idx, line) in src.split_inclusive('\n').enumerate() {
+        let line_no = idx + 1;
+        if [BEGIN, END]
+            .iter()
+            .any(|prefix| marker(line, prefix) == Some(""))
+        {
+            return Err(RegionError::MissingId { line: line_no });
+        }
+        if let Some(id) = marker(line, BEGIN) {
+            if let Some((outer, _)) = open {
+                return Err(RegionError::Nested {
+                    outer: outer.to_string(),
+                    inner: id.to_string(),
+                    line: line_no,
+                });
+            }
+            if seen.insert(id, line_no).is_some() {
+                return Err(RegionError::Duplicate {
+                    id: id.to_string(),
+                    line: line_no,
+                });
+            }
+            open = Some((id, line_no));
+            output.push_str(line);
+            if let Some((_, contents)) = regions.iter().find(|(r, _)| *r == id) {
+                output.push_str(contents.trim_matches('\n'));
+                output.push('\n');
+            }
+        } else if let Some(id) = marker(line, END) {
+            match open {
+                Some((open_id, _)) if open_id == id => open = None,
+                _ => {
+                    return Err(RegionError::UnexpectedEnd {
+                        id: id.to_string(),
+                        line: line_no,
+                    })
+                }
+            }
+            output.push_str(line);
+        } else {
+            let is_replaced = open.is_some_and(|(id, _)| regions.iter().any(|(r, _)| *r == id));
+            if !is_replaced {
+                output.push_str(line);
+            }
+        }
+    }
+
+    if let Some((id, line)) = open {
+        return Err(RegionError::Unclosed {
+            id: id.to_string(),
+            line,
+        });
+    }
+    if let Some((id, _)) = regions.iter().find(|(id, _)| !seen.contains_key(id)) {
+        return Err(RegionError::Missing(id.to_string()));
+    }
+    Ok(output)
+}
+
+/// Regenerate `module` into the file at `path`. The items of the module are rendered
+/// within the region named after it, if the file doesn't exist yet it's created with
+/// just that region.
+///
+/// The region sits among hand-written code, so neither the header nor the inner
+/// attributes and docs of the module are rendered, unlike `Module::generate_file`.
+///
+/// The new contents are written to a temporary file next to `path` which then
+/// replaces it, so the file is left untouched if regenerating it fails.
+pub fn regenerate_into(path: impl AsRef<Path>, module: &Module) -> Result<(), RegionError> {
+    let path = path.as_ref();
+    validate_id(module.name())?;
+    let contents = module.generate_items();
+    let src = match fs::read_to_string(path) {
+        Ok(existing) => replace_regions(&existing, &[(module.name(), contents)])?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => wrap_region(module.name(), &contents),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".proffer-tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+    fs::write(&tmp_path, src)
+        .and_then(|_| fs::rename(&tmp_path, path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&tmp_path);
+            err.into()
+        })
+}
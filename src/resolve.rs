@@ -0,0 +1,885 @@
+//!
+//! Resolve the type names used within a module tree, ie. to catch a typo in a field
+//! type or a reference to a struct which was never generated before `rustc` does.
+//!
+//! Types of fields, variants, parameters, return types, consts, statics, type aliases
+//! and associated types, as well as the targets of impl blocks, are resolved against
+//! the items defined in the module using them, its `use` statements, the generics in
+//! scope and a list of known external types. Paths starting with `crate`, `self`,
+//! `super` or a submodule are followed through the tree, including those of `use`
+//! statements, so importing an item the tree doesn't define doesn't bring it into scope.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let m = Module::new("api")
+//!     .with_use_statement("use chrono::DateTime;")
+//!     .with_struct(
+//!         Struct::new("User")
+//!             .with_field(Field::new("created", "DateTime<Utc>"))
+//!             .with_field(Field::new("group", "Option<models::Gruop>")),
+//!     )
+//!     .with_submodule(Module::new("models").with_struct(Struct::new("Group").with_is_pub(true)));
+//!
+//! let err = TypeResolver::new().validate(&m).unwrap_err();
+//! assert_eq!(
+//!     err.to_string(),
+//!     "unresolved types: `Utc` in field `User::created`, `models::Gruop` in field `User::group`"
+//! );
+//!
+//! let resolver = TypeResolver::new().with_known_type("Utc");
+//! assert_eq!(resolver.validate(&m).unwrap_err().unresolved().len(), 1);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use crate::*;
+
+/// Primitive types, the types and traits of the standard prelude and the crates always
+/// available to generated code
+const DEFAULT_KNOWN_TYPES: &[&str] = &[
+    "bool",
+    "char",
+    "str",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "usize",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "f32",
+    "f64",
+    "String",
+    "Vec",
+    "Option",
+    "Result",
+    "Box",
+    "Clone",
+    "Copy",
+    "Send",
+    "Sync",
+    "Sized",
+    "Unpin",
+    "Drop",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "Default",
+    "Eq",
+    "PartialEq",
+    "Ord",
+    "PartialOrd",
+    "AsRef",
+    "AsMut",
+    "From",
+    "Into",
+    "Iterator",
+    "IntoIterator",
+    "DoubleEndedIterator",
+    "ExactSizeIterator",
+    "Extend",
+    "ToOwned",
+    "ToString",
+    "std",
+    "core",
+    "alloc",
+];
+
+/// Resolves type names within a module tree against a list of known external types
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeResolver {
+    known_types: Vec<String>,
+}
+
+impl Default for TypeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeResolver {
+    /// Create a resolver knowing the primitive types, the standard prelude and the
+    /// `std`, `core` and `alloc` crates
+    pub fn new() -> Self {
+        Self {
+            known_types: DEFAULT_KNOWN_TYPES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+
+    /// Create a resolver knowing no types at all, not even primitives
+    pub fn empty() -> Self {
+        Self {
+            known_types: vec![],
+        }
+    }
+
+    /// Add a known external type. Either a name such as `Uuid` used as is, a path such
+    /// as `chrono::Utc`, or a crate name such as `serde_json` to know every path within it.
+    pub fn add_known_type(&mut self, ty: impl ToString) -> &mut Self {
+        self.known_types.push(ty.to_string());
+        self
+    }
+
+    /// Add a known external type. Either a name such as `Uuid` used as is, a path such
+    /// as `chrono::Utc`, or a crate name such as `serde_json` to know every path within it.
+    pub fn with_known_type(mut self, ty: impl ToString) -> Self {
+        self.add_known_type(ty);
+        self
+    }
+
+    /// Add several known external types
+    pub fn add_known_types(&mut self, types: impl IntoIterator<Item = impl ToString>) -> &mut Self {
+        self.known_types
+            .extend(types.into_iter().map(|ty| ty.to_string()));
+        self
+    }
+
+    /// Add several known external types
+    pub fn with_known_types(mut self, types: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.add_known_types(types);
+        self
+    }
+
+    /// The known external types
+    pub fn known_types(&self) -> &[String] {
+        self.known_types.as_slice()
+    }
+
+    /// Resolve every type name used within `module` and its submodules
+    pub fn validate(&self, module: &Module) -> Result<(), ResolveError> {
+        let mut validator = Validator {
+            resolver: self,
+            root: module,
+            path: vec![],
+            unresolved: vec![],
+        };
+        validator.module(module);
+        if validator.unresolved.is_empty() {
+            Ok(())
+        } else {
+            Err(ResolveError {
+                unresolved: validator.unresolved,
+            })
+        }
+    }
+
+    fn is_known(&self, path: &str) -> bool {
+        let first = path.split("::").next().unwrap_or(path);
+        self.known_types.iter().any(|ty| ty == path || ty == first)
+    }
+}
+
+/// A type name which couldn't be resolved
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Unresolved {
+    path: Vec<String>,
+    name: String,
+    location: String,
+}
+
+impl Unresolved {
+    /// Names of the submodules containing the item using the type
+    pub fn path(&self) -> &[String] {
+        self.path.as_slice()
+    }
+
+    /// The type name, ie. `models::Gruop` in `Option<models::Gruop>`
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    /// Where the type is used, ie. ``field `User::group` `` or
+    /// ``parameter `user` of fn `login` ``
+    pub fn location(&self) -> &str {
+        self.location.as_str()
+    }
+}
+
+impl fmt::Display for Unresolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` in {}", self.name, self.location)
+    }
+}
+
+/// Error returned by `TypeResolver::validate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveError {
+    unresolved: Vec<Unresolved>,
+}
+
+impl ResolveError {
+    /// The type names which couldn't be resolved, in the order items are rendered with
+    /// submodules last
+    pub fn unresolved(&self) -> &[Unresolved] {
+        self.unresolved.as_slice()
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unresolved = self
+            .unresolved
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        write!(f, "unresolved types: {}", unresolved.join(", "))
+    }
+}
+
+impl Error for ResolveError {}
+
+/// Containers holding their contents behind a pointer, so a type may contain itself
+/// through them
+const INDIRECTIONS: &[&str] = &[
+    "Box",
+    "Vec",
+    "Rc",
+    "Arc",
+    "Weak",
+    "VecDeque",
+    "LinkedList",
+    "BinaryHeap",
+    "HashMap",
+    "HashSet",
+    "BTreeMap",
+    "BTreeSet",
+    "PhantomData",
+];
+
+pub(crate) fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Start and end of a path within a type, in chars
+pub(crate) type Span = (usize, usize);
+
+/// A path naming a type, with its span in chars including any generic arguments
+pub(crate) struct TypePath {
+    pub(crate) path: String,
+    pub(crate) span: Span,
+    /// If the type is held by value rather than behind a reference, a pointer or one
+    /// of the `INDIRECTIONS`
+    pub(crate) by_value: bool,
+}
+
+/// The paths naming types within `ty`, ie. `Option`, `Node`, `Vec` and `Edge` in
+/// `Option<(Node, Vec<Edge>)>`, in the order they appear. Lifetimes, associated type
+/// bindings and field names of struct variants are skipped, as are segments following
+/// a qualified path.
+pub(crate) fn type_paths(ty: &str) -> Vec<TypePath> {
+    let mut parser = TypeParser {
+        chars: ty.chars().collect(),
+        pos: 0,
+        paths: vec![],
+    };
+    while parser.pos < parser.chars.len() {
+        let pos = parser.pos;
+        parser.ty(false);
+        if parser.pos == pos {
+            parser.pos += 1;
+        }
+    }
+    parser.paths
+}
+
+/// Walks a type, recording the paths it names
+struct TypeParser {
+    chars: Vec<char>,
+    pos: usize,
+    paths: Vec<TypePath>,
+}
+
+impl TypeParser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    fn ident(&mut self) -> String {
+        let start = self.pos;
+        while self.peek().is_some_and(is_ident_char) {
+            self.pos += 1;
+        }
+        self.chars[start..self.pos].iter().collect()
+    }
+
+    /// The identifier at the current position, without consuming it
+    fn peek_ident(&self) -> String {
+        self.chars[self.pos..]
+            .iter()
+            .take_while(|c| is_ident_char(**c))
+            .collect()
+    }
+
+    fn ty(&mut self, indirect: bool) {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('&') | Some('*') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                if self.peek() == Some('\'') {
+                    self.pos += 1;
+                    self.ident();
+                    self.skip_whitespace();
+                }
+                if matches!(self.peek_ident().as_str(), "mut" | "const") {
+                    self.ident();
+                }
+                self.ty(true);
+            }
+            Some('\'') => {
+                self.pos += 1;
+                self.ident();
+            }
+            Some('"') => {
+                self.pos += 1;
+                while self.peek().is_some_and(|c| c != '"') {
+                    self.pos += 1;
+                }
+                self.pos += 1;
+            }
+            Some('(') => self.list(')', indirect),
+            Some('{') => self.list('}', indirect),
+            Some('<') => {
+                // A qualified path, ie. `<T as Trait>::Output`
+                self.list('>', true);
+                while self.starts_with("::") {
+                    self.pos += 2;
+                    self.ident();
+                }
+            }
+            Some('[') => {
+                self.pos += 1;
+                self.ty(indirect);
+                let mut depth = 0;
+                while let Some(c) = self.peek() {
+                    self.pos += 1;
+                    match c {
+                        '[' => depth += 1,
+                        ']' if depth == 0 => break,
+                        ']' => depth -= 1,
+                        _ => (),
+                    }
+                }
+            }
+            Some(c) if c.is_numeric() => {
+                self.ident();
+            }
+            Some(c) if is_ident_char(c) || c == ':' => match self.peek_ident().as_str() {
+                "as" => {
+                    self.ident();
+                }
+                "dyn" | "impl" => {
+                    self.ident();
+                    loop {
+                        self.ty(true);
+                        self.skip_whitespace();
+                        if self.peek() != Some('+') {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                "unsafe" | "extern" | "for" | "fn" => {
+                    let keyword = self.ident();
+                    self.skip_whitespace();
+                    if keyword == "for" && self.peek() == Some('<') {
+                        self.list('>', true);
+                    }
+                    if keyword == "fn" {
+                        if self.peek() == Some('(') {
+                            self.list(')', true);
+                        }
+                        self.skip_whitespace();
+                        if self.starts_with("->") {
+                            self.pos += 2;
+                            self.ty(true);
+                        }
+                    } else {
+                        self.ty(true);
+                    }
+                }
+                _ => self.path(indirect),
+            },
+            _ => (),
+        }
+    }
+
+    fn path(&mut self, indirect: bool) {
+        let start = self.pos;
+        // Recorded ahead of the paths within its generic arguments
+        let at = self.paths.len();
+        let mut segments = vec![];
+        loop {
+            if self.starts_with("::") {
+                self.pos += 2;
+            }
+            let segment = self.ident();
+            if segment.is_empty() {
+                break;
+            }
+            if self.peek() == Some('<') {
+                let indirect = indirect || INDIRECTIONS.contains(&segment.as_str());
+                self.list('>', indirect);
+            }
+            segments.push(segment);
+            if !self.starts_with("::") {
+                break;
+            }
+        }
+        if !segments.is_empty() {
+            let path = TypePath {
+                path: segments.join("::"),
+                span: (start, self.pos),
+                by_value: !indirect,
+            };
+            self.paths.insert(at, path);
+        }
+    }
+
+    /// A list of types from an opening bracket to `close`, skipping lifetimes, bindings
+    /// such as `Item = T` and field names such as `a: T`
+    fn list(&mut self, close: char, indirect: bool) {
+        self.pos += 1;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None => break,
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(',') => self.pos += 1,
+                _ => {
+                    let ident = self.peek_ident();
+                    if !ident.is_empty() {
+                        let after = self.chars[self.pos + ident.chars().count()..]
+                            .iter()
+                            .filter(|c| !c.is_whitespace())
+                            .take(2)
+                            .collect::<String>();
+                        let binding = (after.starts_with('=') && after != "=>")
+                            || (after.starts_with(':') && after != "::");
+                        if binding {
+                            self.ident();
+                            self.skip_whitespace();
+                            self.pos += 1;
+                            continue;
+                        }
+                    }
+                    let pos = self.pos;
+                    self.ty(indirect);
+                    if self.pos == pos {
+                        self.pos += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A name brought into scope by a `use` statement
+pub(crate) struct Import {
+    /// The name in scope, `*` for a glob import
+    pub(crate) name: String,
+    /// The path imported, without the name for a glob import
+    pub(crate) path: Vec<String>,
+}
+
+/// The names brought into scope by a use statement, ie. `use std::{fmt, io::Write as W};`
+pub(crate) fn imports(statement: &str) -> Vec<Import> {
+    let tree = statement
+        .trim()
+        .trim_end_matches(';')
+        .trim_start_matches("pub(crate)")
+        .trim_start_matches("pub")
+        .trim()
+        .trim_start_matches("use")
+        .trim();
+    let mut imports = vec![];
+    use_tree(&[], tree, &mut imports);
+    imports
+}
+
+fn use_tree(prefix: &[String], tree: &str, imports: &mut Vec<Import>) {
+    let tree = tree.trim();
+    if let (Some(open), true) = (tree.find('{'), tree.ends_with('}')) {
+        let mut prefix = prefix.to_vec();
+        prefix.extend(
+            tree[..open]
+                .split("::")
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(ToString::to_string),
+        );
+        let inner = &tree[open + 1..tree.len() - 1];
+        let (mut depth, mut start) = (0, 0);
+        for (i, c) in inner.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    use_tree(&prefix, &inner[start..i], imports);
+                    start = i + 1;
+                }
+                _ => (),
+            }
+        }
+        use_tree(&prefix, &inner[start..], imports);
+        return;
+    }
+    if tree.is_empty() {
+        return;
+    }
+    let (tree, rename) = match tree.split_once(" as ") {
+        Some((tree, rename)) => (tree.trim(), Some(rename.trim())),
+        None => (tree, None),
+    };
+    let mut path = prefix.to_vec();
+    path.extend(tree.split("::").map(|s| s.trim().to_string()));
+    if path.last().map(String::as_str) == Some("self") {
+        path.pop();
+    }
+    let name = match (rename, path.last()) {
+        (Some(rename), _) => rename.to_string(),
+        (None, Some(last)) => last.clone(),
+        (None, None) => return,
+    };
+    if name == "*" {
+        path.pop();
+    }
+    imports.push(Import { name, path });
+}
+
+/// The submodule at `path`, regardless of visibility
+pub(crate) fn module_at<'a>(root: &'a Module, path: &[String]) -> Option<&'a Module> {
+    path.iter()
+        .try_fold(root, |module, name| module.get_submodule(name.as_str()))
+}
+
+/// If `name` is defined within `module`, excluding its imports
+fn defines(module: &Module, name: &str) -> bool {
+    module.get_struct(name).is_some()
+        || module.get_enum(name).is_some()
+        || module.get_union(name).is_some()
+        || module.get_trait(name).is_some()
+        || module.get_type_alias(name).is_some()
+        || module.get_const(name).is_some()
+        || module.get_static(name).is_some()
+        || module.get_submodule(name).is_some()
+        || module
+            .extern_blocks()
+            .iter()
+            .flat_map(|block| block.types())
+            .any(|ty| ty.name() == name)
+}
+
+struct Validator<'a> {
+    resolver: &'a TypeResolver,
+    root: &'a Module,
+    path: Vec<String>,
+    unresolved: Vec<Unresolved>,
+}
+
+impl<'a> Validator<'a> {
+    /// If `name` is in scope within the module at `module_path`, through its items or
+    /// its imports. Imports within the tree are followed, `visited` guarding against
+    /// glob imports of each other.
+    fn in_scope(&self, module_path: &[String], name: &str, visited: &mut Vec<Vec<String>>) -> bool {
+        let module = match module_at(self.root, module_path) {
+            Some(module) => module,
+            None => return false,
+        };
+        if defines(module, name) {
+            return true;
+        }
+        if visited.iter().any(|p| p == module_path) {
+            return false;
+        }
+        visited.push(module_path.to_vec());
+        for statement in module.use_statements() {
+            for import in imports(statement) {
+                if import.name == name {
+                    return self.import_resolves(module_path, &import.path, visited);
+                }
+                if import.name != "*" {
+                    continue;
+                }
+                match self.absolute(module_path, &import.path) {
+                    Some(target) => {
+                        if self.in_scope(&target, name, visited) {
+                            return true;
+                        }
+                    }
+                    // A glob import from another crate may bring anything into scope
+                    None => return true,
+                }
+            }
+        }
+        false
+    }
+
+    /// If the item imported by `path` within the module at `module_path` exists. Imports
+    /// from other crates are assumed to.
+    fn import_resolves(
+        &self,
+        module_path: &[String],
+        path: &[String],
+        visited: &mut Vec<Vec<String>>,
+    ) -> bool {
+        let (last, modules) = match path.split_last() {
+            Some(split) if !split.1.is_empty() => split,
+            _ => return true,
+        };
+        let in_tree = match modules[0].as_str() {
+            "crate" | "self" | "super" => true,
+            first => module_at(self.root, module_path)
+                .is_some_and(|module| module.get_submodule(first).is_some()),
+        };
+        if !in_tree {
+            return true;
+        }
+        match self.absolute(module_path, modules) {
+            Some(target) => self.in_scope(&target, last, visited),
+            None => false,
+        }
+    }
+
+    /// The module path of a path relative to the module at `module_path`, `None` if it
+    /// leads out of the tree
+    fn absolute(&self, module_path: &[String], path: &[String]) -> Option<Vec<String>> {
+        let mut segments = path.iter().peekable();
+        let mut absolute = match segments.peek().map(|s| s.as_str()) {
+            Some("crate") => {
+                segments.next();
+                vec![]
+            }
+            Some("self") | Some("super") => module_path.to_vec(),
+            Some(first) => {
+                let module = module_at(self.root, module_path)?;
+                module.get_submodule(first)?;
+                module_path.to_vec()
+            }
+            None => module_path.to_vec(),
+        };
+        for segment in segments {
+            match segment.as_str() {
+                "self" => (),
+                "super" => {
+                    absolute.pop()?;
+                }
+                name => {
+                    module_at(self.root, &absolute)?.get_submodule(name)?;
+                    absolute.push(name.to_string());
+                }
+            }
+        }
+        Some(absolute)
+    }
+
+    /// If a path naming a type resolves, given the generics in scope
+    fn resolves(&self, ty: &str, generics: &[&str]) -> bool {
+        let segments = ty.split("::").collect::<Vec<&str>>();
+        let first = segments[0];
+        if first == "Self" || generics.contains(&first) || self.resolver.is_known(ty) {
+            return true;
+        }
+        let (module_path, rest) = match first {
+            "crate" | "self" | "super" => {
+                let relative = segments
+                    .iter()
+                    .take_while(|s| matches!(**s, "crate" | "self" | "super"))
+                    .map(|s| s.to_string())
+                    .collect::<Vec<String>>();
+                match self.absolute(&self.path, &relative) {
+                    Some(module_path) => (module_path, &segments[relative.len()..]),
+                    None => return false,
+                }
+            }
+            _ => (self.path.clone(), &segments[..]),
+        };
+        self.resolves_in(module_path, rest)
+    }
+
+    fn resolves_in(&self, mut module_path: Vec<String>, segments: &[&str]) -> bool {
+        let (last, modules) = match segments.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        for segment in modules {
+            let module = match module_at(self.root, &module_path) {
+                Some(module) => module,
+                None => return false,
+            };
+            if module.get_submodule(*segment).is_some() {
+                module_path.push(segment.to_string());
+            } else {
+                // An imported module or a type followed by an associated item can't be
+                // followed further
+                return self.in_scope(&module_path, segment, &mut vec![]);
+            }
+        }
+        self.in_scope(&module_path, last, &mut vec![])
+    }
+
+    fn check(&mut self, ty: &str, generics: &[&str], location: &str) {
+        for TypePath { path: name, .. } in type_paths(ty) {
+            if !self.resolves(&name, generics) {
+                self.unresolved.push(Unresolved {
+                    path: self.path.clone(),
+                    name,
+                    location: location.to_string(),
+                });
+            }
+        }
+    }
+
+    /// `` kind `a::b::name` ``
+    fn item(&self, kind: &str, name: &str) -> String {
+        let path = self
+            .path
+            .iter()
+            .map(|m| format!("{}::", m))
+            .collect::<String>();
+        format!("{} `{}{}`", kind, path, name)
+    }
+
+    fn signature(&mut self, owner: Option<&str>, sig: &FunctionSignature, generics: &[&str]) {
+        let mut generics = generics.to_vec();
+        generics.extend(sig.generics().iter().map(Generic::name));
+        let name = match owner {
+            Some(owner) => format!("{}::{}", owner, sig.name()),
+            None => sig.name().to_string(),
+        };
+        for param in sig.parameters() {
+            let location = format!("parameter `{}` of {}", param.name(), self.item("fn", &name));
+            self.check(param.ty(), &generics, &location);
+        }
+        if let Some(ty) = sig.return_ty() {
+            let location = format!("return type of {}", self.item("fn", &name));
+            self.check(ty, &generics, &location);
+        }
+    }
+
+    fn fields(&mut self, owner: &str, fields: &[Field], generics: &[&str]) {
+        for field in fields {
+            let location = self.item("field", &format!("{}::{}", owner, field.name()));
+            self.check(field.ty(), generics, &location);
+        }
+    }
+
+    fn module(&mut self, module: &Module) {
+        for item in module.consts() {
+            let location = self.item("const", item.name());
+            self.check(item.ty(), &[], &location);
+        }
+        for item in module.statics() {
+            let location = self.item("static", item.name());
+            self.check(item.ty(), &[], &location);
+        }
+        for item in module.type_aliases() {
+            let generics = item
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            let location = self.item("type", item.name());
+            self.check(item.ty(), &generics, &location);
+        }
+        for tr8t in module.traits() {
+            let mut generics = tr8t
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            generics.extend(tr8t.associated_types().iter().map(|t| t.name()));
+            for sig in tr8t.signatures() {
+                self.signature(Some(tr8t.name()), sig, &generics);
+            }
+            for function in tr8t.functions() {
+                self.signature(Some(tr8t.name()), function.signature(), &generics);
+            }
+        }
+        for function in module.functions() {
+            self.signature(None, function.signature(), &[]);
+        }
+        for stct in module.structs() {
+            let generics = stct
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            self.fields(stct.name(), stct.fields(), &generics);
+        }
+        for ipl in module.impls() {
+            let generics = ipl
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            let location = self.item("impl", &crate::merge::impl_name(ipl));
+            self.check(ipl.obj_name(), &generics, &location);
+            if let Some(tr8t) = ipl.impl_trait() {
+                self.check(tr8t.name(), &generics, &location);
+            }
+            let owner = ipl.obj_name().to_string();
+            for ty in ipl.associated_types() {
+                let location = self.item("type", &format!("{}::{}", owner, ty.name()));
+                self.check(ty.implementer(), &generics, &location);
+            }
+            for function in ipl.functions() {
+                self.signature(Some(&owner), function.signature(), &generics);
+            }
+        }
+        for enm in module.enums() {
+            let generics = enm
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            for variant in enm.variants() {
+                if let Some(inner) = variant.inner() {
+                    let location =
+                        self.item("variant", &format!("{}::{}", enm.name(), variant.name()));
+                    self.check(inner, &generics, &location);
+                }
+            }
+        }
+        for unn in module.unions() {
+            let generics = unn
+                .generics()
+                .iter()
+                .map(Generic::name)
+                .collect::<Vec<&str>>();
+            self.fields(unn.name(), unn.fields(), &generics);
+        }
+        for submodule in module.submodules() {
+            self.path.push(submodule.name().to_string());
+            self.module(submodule);
+            self.path.pop();
+        }
+    }
+}
@@ -0,0 +1,473 @@
+//!
+//! Classify the changes between two versions of a module tree against semver rules,
+//! ie. to refuse a regeneration which would break downstream crates without a major
+//! version bump.
+//!
+//! Only the public API matters: items reachable through public modules, and macros
+//! exported with `#[macro_export]`. Removing or changing such an item is breaking, as
+//! is adding a field to a struct whose fields are all public, a variant to an enum
+//! which isn't `#[non_exhaustive]`, or a required function or an associated type to a
+//! trait. Adding public items is a minor change and anything else a patch.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//!
+//! let old = Module::new("api")
+//!     .with_struct(Struct::new("User").with_is_pub(true).with_field(Field::new("id", "u64").with_is_pub(true)))
+//!     .with_enum(Enum::new("Role").with_is_pub(true).with_attribute(Attribute::from("#[non_exhaustive]")));
+//! let new = Module::new("api")
+//!     .with_struct(Struct::new("User").with_is_pub(true).with_field(Field::new("id", "u64")))
+//!     .with_enum(Enum::new("Role").with_is_pub(true).with_attribute(Attribute::from("#[non_exhaustive]")).with_variant(Variant::new("Admin")));
+//!
+//! let report = old.semver_check(&new);
+//! assert_eq!(report.bump(), Bump::Major);
+//! assert_eq!(
+//!     report.to_string(),
+//!     "major: field `User::id` is no longer public
+//! minor: added variant `Role::Admin`
+//! "
+//! );
+//!
+//! let err = report.check(Bump::Minor).unwrap_err();
+//! assert_eq!(err.to_string(), "changes requiring a major version bump: field `User::id` is no longer public");
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::mem::discriminant;
+
+use crate::merge::{impl_name, pair_named};
+use crate::resolve::module_at;
+use crate::*;
+
+/// Part of the version to increment for a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Bump {
+    /// A change invisible to downstream crates
+    Patch,
+    /// A backwards compatible addition to the public API
+    Minor,
+    /// A breaking change to the public API
+    Major,
+}
+
+impl fmt::Display for Bump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bump = match self {
+            Bump::Patch => "patch",
+            Bump::Minor => "minor",
+            Bump::Major => "major",
+        };
+        write!(f, "{}", bump)
+    }
+}
+
+/// A change with the version bump it requires
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SemverChange {
+    change: Change,
+    bump: Bump,
+}
+
+impl SemverChange {
+    /// The change
+    pub fn change(&self) -> &Change {
+        &self.change
+    }
+
+    /// The version bump it requires
+    pub fn bump(&self) -> Bump {
+        self.bump
+    }
+}
+
+impl fmt::Display for SemverChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.bump, self.change)
+    }
+}
+
+/// The classified changes between two versions of a module tree. Displays as a report
+/// with one change per line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SemverReport {
+    changes: Vec<SemverChange>,
+}
+
+impl SemverReport {
+    /// The classified changes, in the order of `Module::diff`
+    pub fn changes(&self) -> &[SemverChange] {
+        self.changes.as_slice()
+    }
+
+    /// The version bump required by all changes, a patch if nothing changed
+    pub fn bump(&self) -> Bump {
+        self.changes
+            .iter()
+            .map(SemverChange::bump)
+            .max()
+            .unwrap_or(Bump::Patch)
+    }
+
+    /// If any change breaks the public API
+    pub fn is_breaking(&self) -> bool {
+        self.bump() == Bump::Major
+    }
+
+    /// The changes breaking the public API
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.bump == Bump::Major)
+            .map(SemverChange::change)
+    }
+
+    /// Check the changes are allowed by a version bump of `allowed`, ie. `Bump::Minor`
+    /// for a regeneration released as a minor version
+    pub fn check(&self, allowed: Bump) -> Result<(), SemverError> {
+        let bump = self.bump();
+        if bump <= allowed {
+            return Ok(());
+        }
+        Err(SemverError {
+            bump,
+            changes: self
+                .changes
+                .iter()
+                .filter(|c| c.bump > allowed)
+                .map(|c| c.change.clone())
+                .collect(),
+        })
+    }
+}
+
+impl fmt::Display for SemverReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned by `SemverReport::check` when changes need a greater version bump
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemverError {
+    bump: Bump,
+    changes: Vec<Change>,
+}
+
+impl SemverError {
+    /// The version bump required by all changes
+    pub fn bump(&self) -> Bump {
+        self.bump
+    }
+
+    /// The changes not allowed by the version bump checked against
+    pub fn changes(&self) -> &[Change] {
+        self.changes.as_slice()
+    }
+}
+
+impl fmt::Display for SemverError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let changes = self
+            .changes
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "changes requiring a {} version bump: {}",
+            self.bump,
+            changes.join(", ")
+        )
+    }
+}
+
+impl Error for SemverError {}
+
+impl Module {
+    /// Classify the changes from this module to `new` against semver rules
+    pub fn semver_check(&self, new: &Module) -> SemverReport {
+        let diff = self.diff(new);
+        let changes = diff
+            .changes()
+            .iter()
+            .enumerate()
+            .map(|(idx, change)| {
+                // Changes to items sharing a name, ie. several `impl User` blocks, are
+                // told apart by their order
+                let nth = diff.changes()[..idx]
+                    .iter()
+                    .filter(|c| {
+                        c.path() == change.path()
+                            && c.kind() == change.kind()
+                            && c.name() == change.name()
+                            && discriminant(c.detail()) == discriminant(change.detail())
+                    })
+                    .count();
+                SemverChange {
+                    change: change.clone(),
+                    bump: classify(self, new, change, nth),
+                }
+            })
+            .collect();
+        SemverReport { changes }
+    }
+}
+
+/// The submodule at `path`, unless it or any module on the way is private
+fn public_module<'a>(root: &'a Module, path: &[String]) -> Option<&'a Module> {
+    path.iter().try_fold(root, |module, name| {
+        module.get_submodule(name.as_str()).filter(|m| m.is_pub())
+    })
+}
+
+fn is_non_exhaustive(attributes: &[Attribute]) -> bool {
+    attributes
+        .iter()
+        .any(|a| a.path().as_deref() == Some("non_exhaustive"))
+}
+
+/// If a type is public, assuming types defined elsewhere are
+fn type_is_pub(module: &Module, name: &str) -> bool {
+    if let Some(s) = module.get_struct(name) {
+        s.is_pub()
+    } else if let Some(e) = module.get_enum(name) {
+        e.is_pub()
+    } else if let Some(u) = module.get_union(name) {
+        u.is_pub()
+    } else if let Some(t) = module.get_type_alias(name) {
+        t.is_pub()
+    } else {
+        true
+    }
+}
+
+/// If a trait is public, assuming traits defined elsewhere are
+fn trait_is_pub(module: &Module, name: &str) -> bool {
+    module.get_trait(name).is_none_or(Trait::is_pub)
+}
+
+/// If a struct can be built or matched without `..` downstream, so any added field
+/// breaks it
+fn is_exhaustive_struct(module: &Module, name: &str) -> bool {
+    module.get_struct(name).is_some_and(|s| {
+        s.is_pub() && !is_non_exhaustive(s.attributes()) && s.fields().iter().all(Field::is_pub)
+    })
+}
+
+/// Where a function or associated type named by `Change::name` is defined
+enum Member<'a> {
+    /// `Trait::member`
+    Trait(&'a Trait, &'a str),
+    /// `<Type as Trait>::member`
+    TraitImpl(&'a str, &'a str),
+    /// `Type::member`
+    Inherent(&'a str, &'a str),
+    /// A free item
+    Free(&'a str),
+}
+
+fn member<'a>(module: &'a Module, name: &'a str) -> Member<'a> {
+    if name.starts_with('<') {
+        let (qualified, _) = name.rsplit_once("::").unwrap_or((name, ""));
+        let (ty, tr8t) = qualified[1..qualified.len() - 1]
+            .split_once(" as ")
+            .unwrap_or(("", ""));
+        return Member::TraitImpl(ty, tr8t);
+    }
+    match name.rsplit_once("::") {
+        Some((parent, member)) => match module.get_trait(parent) {
+            Some(tr8t) => Member::Trait(tr8t, member),
+            None => Member::Inherent(parent, member),
+        },
+        None => Member::Free(name),
+    }
+}
+
+/// The impl block of `module` the `nth` change of its kind to impl blocks named like
+/// `change` is about. Impl blocks are paired with those of `other` the way the diff
+/// pairs them, so additions and removals are unpaired blocks and other changes are
+/// paired blocks differing in the changed part.
+fn changed_impl<'a>(
+    module: &'a Module,
+    other: Option<&Module>,
+    change: &Change,
+    nth: usize,
+) -> Option<&'a Impl> {
+    let other_impls = other.map_or(&[][..], Module::impls);
+    let differs = |ipl: &Impl, other: &Impl| match change.detail() {
+        ChangeKind::GenericsChanged { .. } => ipl.generics() != other.generics(),
+        ChangeKind::AttributesChanged { .. } => ipl.attributes() != other.attributes(),
+        ChangeKind::DocsChanged => ipl.docs() != other.docs(),
+        _ => true,
+    };
+    pair_named(other_impls, module.impls(), impl_name)
+        .into_iter()
+        .filter(|(_, ipl)| impl_name(ipl) == change.name())
+        .filter(|(idx, ipl)| match (change.detail(), idx) {
+            (ChangeKind::Added | ChangeKind::Removed, idx) => idx.is_none(),
+            (_, Some(idx)) => differs(ipl, &other_impls[*idx]),
+            (_, None) => false,
+        })
+        .nth(nth)
+        .map(|(_, ipl)| ipl)
+}
+
+/// If the item of a change is part of the public API of `module`, `ipl` being the
+/// impl block of a change to one
+fn is_public(module: &Module, kind: ItemKind, name: &str, ipl: Option<&Impl>) -> bool {
+    let parent = || name.split("::").next().unwrap_or(name);
+    let field = || name.rsplit("::").next().unwrap_or(name);
+    match kind {
+        ItemKind::Module => module
+            .get_submodule(name)
+            .map_or(name == module.name(), Module::is_pub),
+        ItemKind::Struct | ItemKind::Enum | ItemKind::Union | ItemKind::TypeAlias => {
+            type_is_pub(module, name)
+        }
+        ItemKind::Trait => trait_is_pub(module, name),
+        ItemKind::Macro => module.get_macro(name).is_some_and(MacroRules::is_exported),
+        ItemKind::Const => module.get_const(name).is_some_and(Const::is_pub),
+        ItemKind::Static => module.get_static(name).is_some_and(Static::is_pub),
+        ItemKind::Field => {
+            let field_is_pub = |fields: &[Field]| {
+                fields
+                    .iter()
+                    .find(|f| f.name() == field())
+                    .is_some_and(Field::is_pub)
+            };
+            if let Some(s) = module.get_struct(parent()) {
+                s.is_pub() && field_is_pub(s.fields())
+            } else if let Some(u) = module.get_union(parent()) {
+                u.is_pub() && field_is_pub(u.fields())
+            } else {
+                false
+            }
+        }
+        ItemKind::Variant => module.get_enum(parent()).is_some_and(Enum::is_pub),
+        ItemKind::Impl => ipl.is_some_and(|ipl| {
+            type_is_pub(module, ipl.obj_name())
+                && match ipl.impl_trait() {
+                    Some(tr8t) => trait_is_pub(module, tr8t.name()),
+                    None => ipl.functions().iter().any(Function::is_pub),
+                }
+        }),
+        ItemKind::Function | ItemKind::AssociatedType => match member(module, name) {
+            Member::Trait(tr8t, _) => tr8t.is_pub(),
+            Member::TraitImpl(ty, tr8t) => type_is_pub(module, ty) && trait_is_pub(module, tr8t),
+            Member::Inherent(ty, function) => {
+                type_is_pub(module, ty)
+                    && module
+                        .impls()
+                        .iter()
+                        .filter(|i| i.obj_name() == ty && i.impl_trait().is_none())
+                        .flat_map(|i| i.functions())
+                        .any(|f| f.name() == function && f.is_pub())
+            }
+            Member::Free(function) => module.get_function(function).is_some_and(Function::is_pub),
+        },
+    }
+}
+
+/// The version bump required by a change from `old` to `new`, the `nth` change of its
+/// kind to items of the same name
+fn classify(old: &Module, new: &Module, change: &Change, nth: usize) -> Bump {
+    // Look items up where downstream crates can see them: in the new tree for additions
+    // and items made public, in the old one otherwise
+    let (side, other) = match change.detail() {
+        ChangeKind::Added | ChangeKind::VisibilityChanged { is_pub: true } => (new, old),
+        _ => (old, new),
+    };
+    // Exported macros are reachable from the crate root wherever they're defined
+    let module = if change.kind() == ItemKind::Macro {
+        module_at(side, change.path())
+    } else {
+        public_module(side, change.path())
+    };
+    let module = match module {
+        Some(module) => module,
+        None => return Bump::Patch,
+    };
+    let (kind, name) = (change.kind(), change.name());
+    let ipl = if kind == ItemKind::Impl {
+        changed_impl(module, module_at(other, change.path()), change, nth)
+    } else {
+        None
+    };
+    let public = is_public(module, kind, name, ipl);
+    let breaking_if_public = if public { Bump::Major } else { Bump::Patch };
+    let minor_if_public = if public { Bump::Minor } else { Bump::Patch };
+
+    match change.detail() {
+        ChangeKind::Added => match kind {
+            ItemKind::Field => {
+                let parent = name.split("::").next().unwrap_or(name);
+                let exhaustive = module_at(other, change.path())
+                    .is_some_and(|m| is_exhaustive_struct(m, parent));
+                if exhaustive {
+                    Bump::Major
+                } else {
+                    minor_if_public
+                }
+            }
+            ItemKind::Variant => {
+                let parent = name.split("::").next().unwrap_or(name);
+                let non_exhaustive = module_at(other, change.path())
+                    .and_then(|m| m.get_enum(parent))
+                    .is_some_and(|e| is_non_exhaustive(e.attributes()));
+                if non_exhaustive {
+                    minor_if_public
+                } else {
+                    breaking_if_public
+                }
+            }
+            ItemKind::AssociatedType => match member(module, name) {
+                Member::Trait(..) => breaking_if_public,
+                _ => minor_if_public,
+            },
+            ItemKind::Function => match member(module, name) {
+                Member::Trait(tr8t, function)
+                    if tr8t.signatures().iter().any(|s| s.name() == function) =>
+                {
+                    breaking_if_public
+                }
+                _ => minor_if_public,
+            },
+            _ => minor_if_public,
+        },
+        ChangeKind::Removed
+        | ChangeKind::VisibilityChanged { is_pub: false }
+        | ChangeKind::TypeChanged { .. }
+        | ChangeKind::SignatureChanged { .. }
+        | ChangeKind::GenericsChanged { .. }
+        | ChangeKind::DefaultChanged { has_default: false } => breaking_if_public,
+        ChangeKind::VisibilityChanged { is_pub: true }
+        | ChangeKind::DefaultChanged { has_default: true } => minor_if_public,
+        ChangeKind::DerivesChanged { removed, .. } => {
+            if removed.is_empty() {
+                minor_if_public
+            } else {
+                breaking_if_public
+            }
+        }
+        ChangeKind::AttributesChanged { added, removed } => {
+            let non_exhaustive =
+                |attributes: &[String]| attributes.iter().any(|a| a.trim() == "#[non_exhaustive]");
+            if non_exhaustive(added) {
+                breaking_if_public
+            } else if non_exhaustive(removed) {
+                minor_if_public
+            } else {
+                Bump::Patch
+            }
+        }
+        ChangeKind::ValueChanged { .. } | ChangeKind::DocsChanged | ChangeKind::BodyChanged => {
+            Bump::Patch
+        }
+    }
+}
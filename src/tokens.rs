@@ -0,0 +1,167 @@
+//!
+//! `quote::ToTokens` implementations for the code generation objects, enabled
+//! with the `tokens` feature.
+//!
+//! Tokens are lexed from the source code rendered by `SrcCode::generate`, so every
+//! token has a call-site span and comments, which aren't tokens, are dropped.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//! use quote::quote;
+//!
+//! let s = Struct::new("Foo").add_field(Field::new("bar", "u32")).to_owned();
+//! let tokens = quote! {
+//!     #s
+//!     impl Foo {}
+//! };
+//! assert_eq!(
+//!     tokens.to_string(),
+//!     quote! { struct Foo { bar: u32, } impl Foo {} }.to_string()
+//! );
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::*;
+
+/// Error returned when generated source code can't be tokenized, ie. a raw string
+/// given to one of the objects contains unbalanced delimiters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenizeError {
+    message: String,
+    src_code: String,
+}
+
+impl TokenizeError {
+    /// The error reported by the lexer
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    /// The complete generated source code which failed to tokenize
+    pub fn src_code(&self) -> &str {
+        self.src_code.as_str()
+    }
+
+    /// Tokens of a `compile_error!` invocation reporting this error, so it surfaces
+    /// where the tokens are expanded rather than panicking in the procedural macro.
+    pub fn to_compile_error(&self) -> TokenStream {
+        let msg = self.to_string();
+        quote! { compile_error! { #msg } }
+    }
+}
+
+impl fmt::Display for TokenizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to tokenize generated source code: {}\n{}",
+            self.message, self.src_code
+        )
+    }
+}
+
+impl Error for TokenizeError {}
+
+/// Tokenize the generated `src_code`.
+fn tokenize(src_code: String) -> Result<TokenStream, TokenizeError> {
+    TokenStream::from_str(&src_code).map_err(|err| TokenizeError {
+        message: err.to_string(),
+        src_code,
+    })
+}
+
+/// Trait implemented for elements which can be converted into tokens, reporting
+/// generated source code which can't be tokenized instead of failing at expansion.
+///
+/// Their `ToTokens` implementation expands to a `compile_error!` in that case.
+pub trait TryToTokens {
+    /// Tokenize this element, returning an error if the generated source code
+    /// isn't valid tokens.
+    fn try_to_tokens(&self) -> Result<TokenStream, TokenizeError>;
+}
+
+macro_rules! impl_to_tokens {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl TryToTokens for $ty {
+                fn try_to_tokens(&self) -> Result<TokenStream, TokenizeError> {
+                    tokenize(self.generate())
+                }
+            }
+
+            impl ToTokens for $ty {
+                fn to_tokens(&self, tokens: &mut TokenStream) {
+                    tokens.extend(
+                        self.try_to_tokens()
+                            .unwrap_or_else(|err| err.to_compile_error()),
+                    );
+                }
+            }
+        )*
+    };
+}
+
+impl_to_tokens!(
+    AssociatedTypeDeclaration,
+    AssociatedTypeDefinition,
+    Attribute,
+    Block,
+    Cfg,
+    Comment,
+    Const,
+    Enum,
+    Expr,
+    ExternBlock,
+    Field,
+    FileHeader,
+    ForeignStatic,
+    ForeignType,
+    Function,
+    FunctionBody,
+    FunctionSignature,
+    Impl,
+    MacroInvocation,
+    MacroRule,
+    MacroRules,
+    MatchArm,
+    Meta,
+    MetaList,
+    Module,
+    Parameter,
+    Static,
+    Stmt,
+    Struct,
+    Trait,
+    TypeAlias,
+    Union,
+    Variant,
+);
+
+/// Tokens of a single generic parameter with its bounds, ie. `T: Clone + Send`,
+/// to be used within `<...>`.
+impl TryToTokens for Generic {
+    fn try_to_tokens(&self) -> Result<TokenStream, TokenizeError> {
+        if self.traits().is_empty() {
+            tokenize(self.name().to_string())
+        } else {
+            tokenize(format!("{}: {}", self.name(), self.traits().join(" + ")))
+        }
+    }
+}
+
+impl ToTokens for Generic {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(
+            self.try_to_tokens()
+                .unwrap_or_else(|err| err.to_compile_error()),
+        );
+    }
+}
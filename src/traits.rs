@@ -3,12 +3,16 @@
 //!
 
 pub mod attributes;
+pub mod comments;
+pub mod derives;
 pub mod docs;
 pub mod fields;
 pub mod generics;
 pub mod trait_bounds;
 
 pub use attributes::*;
+pub use comments::*;
+pub use derives::*;
 pub use docs::*;
 pub use fields::*;
 pub use generics::*;
@@ -34,7 +38,7 @@ impl<T: SrcCode> SrcCodeVec for Vec<T> {
     }
 }
 
-impl<'a> SrcCode for &'a str {
+impl SrcCode for &str {
     fn generate(&self) -> String {
         self.to_string()
     }
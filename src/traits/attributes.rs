@@ -2,25 +2,55 @@
 //! Traits for attributes
 //!
 
+use crate::gen::attribute::push_attribute;
 use crate::internal::Attributes;
 use crate::Attribute;
 
 /// Provides methods to add attributes to elements.
+///
+/// Elements which only take inner attributes, ie. a `FileHeader`, convert `#[...]`
+/// attributes into `#![...]` as they're added, so they can't end up attached to the
+/// item which follows.
 pub trait AttributeExt {
-    /// Add a single attribute.
+    /// Add a single attribute. A `derive` attribute is merged into an existing
+    /// `derive` attribute, if there is one.
     fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self;
 
+    /// Add a single attribute. A `derive` attribute is merged into an existing
+    /// `derive` attribute, if there is one.
+    fn with_attribute(self, attribute: impl Into<Attribute>) -> Self;
+
     /// Add multiple attributes at once.
     fn add_attributes(
         &mut self,
         attributes: impl IntoIterator<Item = impl Into<Attribute>>,
     ) -> &mut Self;
+
+    /// Add multiple attributes at once.
+    fn with_attributes(self, attributes: impl IntoIterator<Item = impl Into<Attribute>>) -> Self;
+}
+
+/// Convert `attribute` into an inner attribute if `item` only takes inner attributes
+fn restyle_attribute<T: Attributes>(item: &T, attribute: Attribute) -> Attribute {
+    match attribute {
+        Attribute::ItemAttr(a) if item.inner_attributes() => {
+            Attribute::ScopeAttr(format!("#!{}", a.trim_start().trim_start_matches('#')))
+        }
+        attribute => attribute,
+    }
 }
 
 impl<T: Attributes> AttributeExt for T {
     /// Add a single attribute.
     fn add_attribute(&mut self, attribute: impl Into<Attribute>) -> &mut Self {
-        self.attributes_mut().push(attribute.into());
+        let attribute = restyle_attribute(self, attribute.into());
+        push_attribute(self.attributes_mut(), attribute);
+        self
+    }
+
+    /// Add a single attribute.
+    fn with_attribute(mut self, attribute: impl Into<Attribute>) -> Self {
+        self.add_attribute(attribute);
         self
     }
 
@@ -29,8 +59,19 @@ impl<T: Attributes> AttributeExt for T {
         &mut self,
         attributes: impl IntoIterator<Item = impl Into<Attribute>>,
     ) -> &mut Self {
-        self.attributes_mut()
-            .extend(attributes.into_iter().map(|a| a.into()));
+        for attribute in attributes {
+            let attribute = restyle_attribute(self, attribute.into());
+            push_attribute(self.attributes_mut(), attribute);
+        }
+        self
+    }
+
+    /// Add multiple attributes at once.
+    fn with_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = impl Into<Attribute>>,
+    ) -> Self {
+        self.add_attributes(attributes);
         self
     }
 }
@@ -0,0 +1,109 @@
+//!
+//! Traits for comments
+//!
+
+use crate::internal::{Anchored, AnchoredEntry, Anchors, Comments};
+use crate::Comment;
+
+/// Provides methods to add comments and blank lines before elements.
+pub trait CommentExt {
+    /// Add a `//` comment, given as plain text which may span multiple lines.
+    fn add_comment(&mut self, comment: impl ToString) -> &mut Self;
+
+    /// Add a `//` comment, given as plain text which may span multiple lines.
+    fn with_comment(self, comment: impl ToString) -> Self;
+
+    /// Add a blank line, separating this element from the one before it.
+    fn add_blank_line(&mut self) -> &mut Self;
+
+    /// Add a blank line, separating this element from the one before it.
+    fn with_blank_line(self) -> Self;
+}
+
+impl<T: Comments> CommentExt for T {
+    /// Add a `//` comment, given as plain text which may span multiple lines.
+    fn add_comment(&mut self, comment: impl ToString) -> &mut Self {
+        self.comments_mut().push(Comment::line(comment));
+        self
+    }
+
+    /// Add a `//` comment, given as plain text which may span multiple lines.
+    fn with_comment(mut self, comment: impl ToString) -> Self {
+        self.add_comment(comment);
+        self
+    }
+
+    /// Add a blank line, separating this element from the one before it.
+    fn add_blank_line(&mut self) -> &mut Self {
+        self.comments_mut().push(Comment::Blank);
+        self
+    }
+
+    /// Add a blank line, separating this element from the one before it.
+    fn with_blank_line(mut self) -> Self {
+        self.add_blank_line();
+        self
+    }
+}
+
+/// Provides methods to add standalone comments and blank lines among the items of a
+/// `Module` or `Impl`, ie. a `// region: models` separator.
+///
+/// Items are rendered grouped by their kind rather than in the order they were
+/// added, so a separator is placed before the item added next and moves along with it.
+/// It is rendered last if no item is added after it, and before the item following
+/// the one it preceded if that item is removed.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let ipl = Impl::new("User")
+///     .with_separator(Comment::line("region: getters"))
+///     .with_function(Function::new("id").with_return_ty("u64").with_body("self.id"))
+///     .with_separator(Comment::Blank)
+///     .with_separator(Comment::line("region: setters"))
+///     .with_function(Function::new("set_id").with_parameter(Parameter::new("id", "u64")));
+///
+/// let expected = r#"
+///     impl User
+///     {
+///         // region: getters
+///         fn id() -> u64
+///         {
+///             self.id
+///         }
+///
+///         // region: setters
+///         fn set_id(id: u64) -> ()
+///         {
+///         }
+///     }
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&ipl.generate()));
+/// ```
+pub trait SeparatorExt {
+    /// Add a comment or blank line before the item added next.
+    fn add_separator(&mut self, separator: Comment) -> &mut Self;
+
+    /// Add a comment or blank line before the item added next.
+    fn with_separator(self, separator: Comment) -> Self;
+}
+
+impl<T: Anchors> SeparatorExt for T {
+    /// Add a comment or blank line before the item added next.
+    fn add_separator(&mut self, separator: Comment) -> &mut Self {
+        self.anchored_mut().push(Anchored {
+            before: None,
+            entry: AnchoredEntry::Separator(separator),
+        });
+        self
+    }
+
+    /// Add a comment or blank line before the item added next.
+    fn with_separator(mut self, separator: Comment) -> Self {
+        self.add_separator(separator);
+        self
+    }
+}
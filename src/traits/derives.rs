@@ -0,0 +1,105 @@
+//!
+//! Traits for derived traits
+//!
+
+use crate::gen::attribute::push_attribute;
+use crate::internal::Derives;
+use crate::Attribute;
+
+/// Provides methods to manage the traits derived by items, ie. structs and enums.
+///
+/// All derived traits are merged into a single `#[derive(...)]` attribute, in the order
+/// they were first added and without duplicates.
+pub trait DeriveExt {
+    /// Derive a single trait.
+    fn add_derive(&mut self, derive: impl ToString) -> &mut Self;
+
+    /// Derive a single trait.
+    fn with_derive(self, derive: impl ToString) -> Self;
+
+    /// Derive multiple traits at once.
+    fn add_derives(&mut self, derives: impl IntoIterator<Item = impl ToString>) -> &mut Self;
+
+    /// Derive multiple traits at once.
+    fn with_derives(self, derives: impl IntoIterator<Item = impl ToString>) -> Self;
+
+    /// Stop deriving a trait, if it's derived.
+    fn remove_derive(&mut self, derive: &str) -> &mut Self;
+
+    /// Stop deriving a trait, if it's derived.
+    fn without_derive(self, derive: &str) -> Self;
+
+    /// Check if a trait is derived.
+    fn has_derive(&self, derive: &str) -> bool;
+
+    /// The derived traits, in order.
+    fn derives(&self) -> Vec<String>;
+}
+
+impl<T: Derives> DeriveExt for T {
+    /// Derive a single trait.
+    fn add_derive(&mut self, derive: impl ToString) -> &mut Self {
+        push_attribute(self.attributes_mut(), Attribute::derive([derive]));
+        self
+    }
+
+    /// Derive a single trait.
+    fn with_derive(mut self, derive: impl ToString) -> Self {
+        self.add_derive(derive);
+        self
+    }
+
+    /// Derive multiple traits at once.
+    fn add_derives(&mut self, derives: impl IntoIterator<Item = impl ToString>) -> &mut Self {
+        let derives = derives
+            .into_iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<String>>();
+        if !derives.is_empty() {
+            push_attribute(self.attributes_mut(), Attribute::derive(derives));
+        }
+        self
+    }
+
+    /// Derive multiple traits at once.
+    fn with_derives(mut self, derives: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.add_derives(derives);
+        self
+    }
+
+    /// Stop deriving a trait, if it's derived.
+    fn remove_derive(&mut self, derive: &str) -> &mut Self {
+        let attributes = self.attributes_mut();
+        for attribute in attributes.iter_mut() {
+            let derives = attribute.derives();
+            if derives.iter().any(|d| d == derive) {
+                *attribute = Attribute::derive(derives.into_iter().filter(|d| d != derive));
+            }
+        }
+        // Drop `#[derive()]` left behind after removing the last trait.
+        attributes.retain(|a| a.path().as_deref() != Some("derive") || !a.derives().is_empty());
+        self
+    }
+
+    /// Stop deriving a trait, if it's derived.
+    fn without_derive(mut self, derive: &str) -> Self {
+        self.remove_derive(derive);
+        self
+    }
+
+    /// Check if a trait is derived.
+    fn has_derive(&self, derive: &str) -> bool {
+        self.derives().iter().any(|d| d == derive)
+    }
+
+    /// The derived traits, in order.
+    fn derives(&self) -> Vec<String> {
+        let mut derives: Vec<String> = vec![];
+        for derive in self.attributes().iter().flat_map(|a| a.derives()) {
+            if !derives.contains(&derive) {
+                derives.push(derive);
+            }
+        }
+        derives
+    }
+}
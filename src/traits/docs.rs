@@ -4,26 +4,221 @@
 
 use crate::internal::Docs;
 
-/// Provides methods to add documentation line. to elements.
+/// Provides methods to add documentation lines to elements.
+///
+/// Documentation is given as plain text, each line is prefixed with `///`, or `//!`
+/// for the inner documentation of a `Module`. Lines which are already documentation,
+/// ie. starting with `///` or `//!`, or `doc` attributes are kept, converted to the
+/// outer or inner style of the element. Plain `//` comments are kept as they are. Lines
+/// which can't be written as a doc comment, ie. those containing a carriage return, are
+/// written as `#[doc = "..."]`.
+///
+/// Example
+/// -------
+/// ```
+/// use proffer::*;
+///
+/// let f = Function::new("parse")
+///     .add_doc("Parse the input.\n\nReturns `None` if it's empty.")
+///     .add_doc_section("Panics", "If the input isn't UTF-8.")
+///     .add_doc_example("assert!(parse(\"\").is_none());")
+///     .to_owned();
+/// let expected = r#"
+///     /// Parse the input.
+///     ///
+///     /// Returns `None` if it's empty.
+///     ///
+///     /// # Panics
+///     ///
+///     /// If the input isn't UTF-8.
+///     ///
+///     /// # Examples
+///     ///
+///     /// ```
+///     /// assert!(parse("").is_none());
+///     /// ```
+///     fn parse() -> ()
+///     {
+///     }
+/// "#;
+/// assert_eq!(norm_whitespace(expected), norm_whitespace(&f.generate()));
+/// ```
 pub trait DocExt {
-    /// Add a single documentation line.
+    /// Add documentation, which may span multiple lines.
     fn add_doc(&mut self, doc: impl ToString) -> &mut Self;
 
-    /// Add multiple documentation lines at once.
+    /// Add documentation, which may span multiple lines.
+    fn with_doc(self, doc: impl ToString) -> Self;
+
+    /// Add multiple pieces of documentation at once.
     fn add_docs(&mut self, docs: impl IntoIterator<Item = impl ToString>) -> &mut Self;
+
+    /// Add multiple pieces of documentation at once.
+    fn with_docs(self, docs: impl IntoIterator<Item = impl ToString>) -> Self;
+
+    /// Add a section, ie. `# Errors`, followed by `text`.
+    fn add_doc_section(&mut self, heading: impl ToString, text: impl ToString) -> &mut Self;
+
+    /// Add a section, ie. `# Errors`, followed by `text`.
+    fn with_doc_section(self, heading: impl ToString, text: impl ToString) -> Self;
+
+    /// Add a fenced code block, `lang` being the info string of the fence, ie. `ignore`.
+    fn add_doc_code(&mut self, code: impl ToString, lang: Option<&str>) -> &mut Self;
+
+    /// Add a fenced code block, `lang` being the info string of the fence, ie. `ignore`.
+    fn with_doc_code(self, code: impl ToString, lang: Option<&str>) -> Self;
+
+    /// Add an `# Examples` section with `code` as a fenced code block.
+    fn add_doc_example(&mut self, code: impl ToString) -> &mut Self;
+
+    /// Add an `# Examples` section with `code` as a fenced code block.
+    fn with_doc_example(self, code: impl ToString) -> Self;
+
+    /// Remove all documentation.
+    fn clear_docs(&mut self) -> &mut Self;
+
+    /// Remove all documentation.
+    fn without_docs(self) -> Self;
+}
+
+/// Check if `line` is already a doc comment or `doc` attribute. `////` starts a
+/// plain comment rather than a doc comment.
+fn is_doc_line(line: &str) -> bool {
+    let line = line.trim_start();
+    (line.starts_with("///") && !line.starts_with("////"))
+        || ["//!", "#[doc", "#![doc"]
+            .iter()
+            .any(|p| line.starts_with(p))
+}
+
+/// Convert a doc comment or `doc` attribute into the outer or inner style, ie. `//!`
+/// into `///` for an item documented from the outside.
+fn restyle_doc_line(line: &str, inner: bool) -> String {
+    let line = line.trim_start();
+    let (from, to) = if inner {
+        [("///", "//!"), ("#[doc", "#![doc")]
+    } else {
+        [("//!", "///"), ("#![doc", "#[doc")]
+    }
+    .iter()
+    .cloned()
+    .find(|(from, _)| line.starts_with(from))
+    .unwrap_or(("", ""));
+    format!("{}{}", to, &line[from.len()..])
+}
+
+/// Add the lines of `doc` to `item`, keeping lines which are already documentation,
+/// in the style of `item`, and plain comments if `keep_doc_lines` is set.
+fn push_doc_lines<T: Docs>(item: &mut T, doc: &str, keep_doc_lines: bool) {
+    let (prefix, attr) = if item.inner_docs() {
+        ("//!", "#!")
+    } else {
+        ("///", "#")
+    };
+    // `str::lines` drops a trailing empty line and yields nothing for "".
+    let lines = if doc.is_empty() {
+        vec![""]
+    } else {
+        doc.lines().collect()
+    };
+    for line in lines {
+        let line = if keep_doc_lines && is_doc_line(line) {
+            restyle_doc_line(line, item.inner_docs())
+        } else if keep_doc_lines && line.trim_start().starts_with("//") {
+            line.to_string()
+        } else if line.chars().any(|c| c.is_control() && c != '\t') {
+            format!("{}[doc = {:?}]", attr, format!(" {}", line))
+        } else if line.is_empty() {
+            prefix.to_string()
+        } else {
+            format!("{} {}", prefix, line)
+        };
+        item.docs_mut().push(line);
+    }
 }
 
 impl<T: Docs> DocExt for T {
-    /// Add a single documentation line.
+    /// Add documentation, which may span multiple lines.
     fn add_doc(&mut self, doc: impl ToString) -> &mut Self {
-        self.docs_mut().push(doc.to_string());
+        push_doc_lines(self, &doc.to_string(), true);
+        self
+    }
+
+    /// Add documentation, which may span multiple lines.
+    fn with_doc(mut self, doc: impl ToString) -> Self {
+        self.add_doc(doc);
+        self
+    }
+
+    /// Add multiple pieces of documentation at once.
+    fn add_docs(&mut self, docs: impl IntoIterator<Item = impl ToString>) -> &mut Self {
+        for doc in docs {
+            self.add_doc(doc);
+        }
+        self
+    }
+
+    /// Add multiple pieces of documentation at once.
+    fn with_docs(mut self, docs: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.add_docs(docs);
+        self
+    }
+
+    /// Add a section, ie. `# Errors`, followed by `text`.
+    fn add_doc_section(&mut self, heading: impl ToString, text: impl ToString) -> &mut Self {
+        if !self.docs_mut().is_empty() {
+            self.add_doc("");
+        }
+        self.add_doc(format!("# {}", heading.to_string()))
+            .add_doc("")
+            .add_doc(text)
+    }
+
+    /// Add a section, ie. `# Errors`, followed by `text`.
+    fn with_doc_section(mut self, heading: impl ToString, text: impl ToString) -> Self {
+        self.add_doc_section(heading, text);
+        self
+    }
+
+    /// Add a fenced code block, `lang` being the info string of the fence, ie. `ignore`.
+    fn add_doc_code(&mut self, code: impl ToString, lang: Option<&str>) -> &mut Self {
+        self.add_doc(format!("```{}", lang.unwrap_or("")));
+        // Code is documentation as a whole, its comments and attributes included.
+        push_doc_lines(self, &code.to_string(), false);
+        self.add_doc("```")
+    }
+
+    /// Add a fenced code block, `lang` being the info string of the fence, ie. `ignore`.
+    fn with_doc_code(mut self, code: impl ToString, lang: Option<&str>) -> Self {
+        self.add_doc_code(code, lang);
+        self
+    }
+
+    /// Add an `# Examples` section with `code` as a fenced code block.
+    fn add_doc_example(&mut self, code: impl ToString) -> &mut Self {
+        if !self.docs_mut().is_empty() {
+            self.add_doc("");
+        }
+        self.add_doc("# Examples")
+            .add_doc("")
+            .add_doc_code(code, None)
+    }
+
+    /// Add an `# Examples` section with `code` as a fenced code block.
+    fn with_doc_example(mut self, code: impl ToString) -> Self {
+        self.add_doc_example(code);
+        self
+    }
+
+    /// Remove all documentation.
+    fn clear_docs(&mut self) -> &mut Self {
+        self.docs_mut().clear();
         self
     }
 
-    /// Add multiple documentation lines at once.
-    fn add_docs(&mut self, doc: impl IntoIterator<Item = impl ToString>) -> &mut Self {
-        self.docs_mut()
-            .extend(doc.into_iter().map(|d| d.to_string()));
+    /// Remove all documentation.
+    fn without_docs(mut self) -> Self {
+        self.clear_docs();
         self
     }
 }
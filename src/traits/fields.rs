@@ -10,8 +10,14 @@ pub trait FieldExt {
     /// Add a single field.
     fn add_field(&mut self, field: Field) -> &mut Self;
 
+    /// Add a single field.
+    fn with_field(self, field: Field) -> Self;
+
     /// Add multiple fields at once.
     fn add_fields<'a>(&mut self, fields: impl IntoIterator<Item = &'a Field>) -> &mut Self;
+
+    /// Add multiple fields at once.
+    fn with_fields<'a>(self, fields: impl IntoIterator<Item = &'a Field>) -> Self;
 }
 
 impl<T: Fields> FieldExt for T {
@@ -21,10 +27,22 @@ impl<T: Fields> FieldExt for T {
         self
     }
 
+    /// Add a single field.
+    fn with_field(mut self, field: Field) -> Self {
+        self.add_field(field);
+        self
+    }
+
     /// Add multiple fields at once.
     fn add_fields<'a>(&mut self, fields: impl IntoIterator<Item = &'a Field>) -> &mut Self {
         self.fields_mut()
             .extend(fields.into_iter().map(ToOwned::to_owned));
         self
     }
+
+    /// Add multiple fields at once.
+    fn with_fields<'a>(mut self, fields: impl IntoIterator<Item = &'a Field>) -> Self {
+        self.add_fields(fields);
+        self
+    }
 }
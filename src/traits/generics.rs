@@ -10,8 +10,14 @@ pub trait GenericExt {
     /// Add a single generic.
     fn add_generic(&mut self, generic: Generic) -> &mut Self;
 
+    /// Add a single generic.
+    fn with_generic(self, generic: Generic) -> Self;
+
     /// Add multiple generics at once.
     fn add_generics<'a>(&mut self, generics: impl IntoIterator<Item = &'a Generic>) -> &mut Self;
+
+    /// Add multiple generics at once.
+    fn with_generics<'a>(self, generics: impl IntoIterator<Item = &'a Generic>) -> Self;
 }
 
 impl<T: Generics> GenericExt for T {
@@ -21,10 +27,22 @@ impl<T: Generics> GenericExt for T {
         self
     }
 
+    /// Add a single generic.
+    fn with_generic(mut self, generic: Generic) -> Self {
+        self.add_generic(generic);
+        self
+    }
+
     /// Add multiple generics at once.
     fn add_generics<'a>(&mut self, generics: impl IntoIterator<Item = &'a Generic>) -> &mut Self {
         self.generics_mut()
             .extend(generics.into_iter().map(ToOwned::to_owned));
         self
     }
+
+    /// Add multiple generics at once.
+    fn with_generics<'a>(mut self, generics: impl IntoIterator<Item = &'a Generic>) -> Self {
+        self.add_generics(generics);
+        self
+    }
 }
@@ -9,11 +9,17 @@ pub trait TraitBoundExt {
     /// Add a single trait bound.
     fn add_trait_bound(&mut self, trait_bound: impl ToString) -> &mut Self;
 
+    /// Add a single trait bound.
+    fn with_trait_bound(self, trait_bound: impl ToString) -> Self;
+
     /// Add multiple trait bounds at once.
     fn add_trait_bounds(
         &mut self,
         trait_bounds: impl IntoIterator<Item = impl ToString>,
     ) -> &mut Self;
+
+    /// Add multiple trait bounds at once.
+    fn with_trait_bounds(self, trait_bounds: impl IntoIterator<Item = impl ToString>) -> Self;
 }
 
 impl<T: TraitBounds> TraitBoundExt for T {
@@ -23,6 +29,12 @@ impl<T: TraitBounds> TraitBoundExt for T {
         self
     }
 
+    /// Add a single trait bound.
+    fn with_trait_bound(mut self, trait_bound: impl ToString) -> Self {
+        self.add_trait_bound(trait_bound);
+        self
+    }
+
     /// Add multiple trait bounds at once.
     fn add_trait_bounds(
         &mut self,
@@ -32,4 +44,10 @@ impl<T: TraitBounds> TraitBoundExt for T {
             .extend(trait_bounds.into_iter().map(|t| t.to_string()));
         self
     }
+
+    /// Add multiple trait bounds at once.
+    fn with_trait_bounds(mut self, trait_bounds: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.add_trait_bounds(trait_bounds);
+        self
+    }
 }
@@ -0,0 +1,671 @@
+//!
+//! Traverse a code model, ie. a `Module` and everything within it, to inspect or
+//! transform it.
+//!
+//! Each `visit_*` method of `Visit` and `VisitMut` defaults to walking the children
+//! of the node by calling the free function of the same name. Overriding a method
+//! replaces the default walk, call the free function from the override to keep
+//! walking the children.
+//!
+//! Children are visited in the order they are rendered, submodules last and sorted
+//! by name.
+//!
+//! Example
+//! -------
+//! ```
+//! use proffer::*;
+//! use proffer::visit::{self, Visit, VisitMut};
+//!
+//! /// Derive `Debug` for every struct and prefix its name
+//! struct Prefix;
+//!
+//! impl VisitMut for Prefix {
+//!     fn visit_struct_mut(&mut self, node: &mut Struct) {
+//!         let name = format!("Api{}", node.name());
+//!         node.set_name(name).add_derive("Debug");
+//!         visit::visit_struct_mut(self, node);
+//!     }
+//! }
+//!
+//! /// Count the fields within a module and its submodules
+//! struct CountFields(usize);
+//!
+//! impl Visit for CountFields {
+//!     fn visit_field(&mut self, _node: &Field) {
+//!         self.0 += 1;
+//!     }
+//! }
+//!
+//! let mut m = Module::new("api")
+//!     .add_struct(Struct::new("User").add_field(Field::new("id", "u64")).to_owned())
+//!     .add_submodule(
+//!         Module::new("nested")
+//!             .add_struct(Struct::new("Group").add_field(Field::new("id", "u64")).to_owned())
+//!             .to_owned(),
+//!     )
+//!     .to_owned();
+//!
+//! Prefix.visit_module_mut(&mut m);
+//! assert!(m.get_struct("ApiUser").unwrap().has_derive("Debug"));
+//! assert!(m.get_submodule("nested").unwrap().get_struct("ApiGroup").is_some());
+//!
+//! let mut count = CountFields(0);
+//! count.visit_module(&m);
+//! assert_eq!(count.0, 2);
+//! ```
+
+use crate::internal::{Attributes, Fields, Generics};
+use crate::*;
+
+/// Traverse a code model by reference
+pub trait Visit {
+    /// Visit a module, see `visit_module`
+    fn visit_module(&mut self, node: &Module) {
+        visit_module(self, node)
+    }
+    /// Visit a struct, see `visit_struct`
+    fn visit_struct(&mut self, node: &Struct) {
+        visit_struct(self, node)
+    }
+    /// Visit a union, see `visit_union`
+    fn visit_union(&mut self, node: &Union) {
+        visit_union(self, node)
+    }
+    /// Visit an enum, see `visit_enum`
+    fn visit_enum(&mut self, node: &Enum) {
+        visit_enum(self, node)
+    }
+    /// Visit an enum variant, see `visit_variant`
+    fn visit_variant(&mut self, node: &Variant) {
+        visit_variant(self, node)
+    }
+    /// Visit a field of a struct or union, see `visit_field`
+    fn visit_field(&mut self, node: &Field) {
+        visit_field(self, node)
+    }
+    /// Visit a trait, see `visit_trait`
+    fn visit_trait(&mut self, node: &Trait) {
+        visit_trait(self, node)
+    }
+    /// Visit an impl block, see `visit_impl`
+    fn visit_impl(&mut self, node: &Impl) {
+        visit_impl(self, node)
+    }
+    /// Visit an associated type declared by a trait, see
+    /// `visit_associated_type_declaration`
+    fn visit_associated_type_declaration(&mut self, node: &AssociatedTypeDeclaration) {
+        visit_associated_type_declaration(self, node)
+    }
+    /// Visit an associated type defined by an impl block, see
+    /// `visit_associated_type_definition`
+    fn visit_associated_type_definition(&mut self, node: &AssociatedTypeDefinition) {
+        visit_associated_type_definition(self, node)
+    }
+    /// Visit a function, see `visit_function`
+    fn visit_function(&mut self, node: &Function) {
+        visit_function(self, node)
+    }
+    /// Visit a function signature, see `visit_function_signature`
+    fn visit_function_signature(&mut self, node: &FunctionSignature) {
+        visit_function_signature(self, node)
+    }
+    /// Visit a function parameter, see `visit_parameter`
+    fn visit_parameter(&mut self, node: &Parameter) {
+        visit_parameter(self, node)
+    }
+    /// Visit a const, see `visit_const`
+    fn visit_const(&mut self, node: &Const) {
+        visit_const(self, node)
+    }
+    /// Visit a static, see `visit_static`
+    fn visit_static(&mut self, node: &Static) {
+        visit_static(self, node)
+    }
+    /// Visit a type alias, see `visit_type_alias`
+    fn visit_type_alias(&mut self, node: &TypeAlias) {
+        visit_type_alias(self, node)
+    }
+    /// Visit a `macro_rules!` definition, see `visit_macro_rules`
+    fn visit_macro_rules(&mut self, node: &MacroRules) {
+        visit_macro_rules(self, node)
+    }
+    /// Visit a macro invocation, see `visit_macro_invocation`
+    fn visit_macro_invocation(&mut self, node: &MacroInvocation) {
+        visit_macro_invocation(self, node)
+    }
+    /// Visit an extern block, see `visit_extern_block`
+    fn visit_extern_block(&mut self, node: &ExternBlock) {
+        visit_extern_block(self, node)
+    }
+    /// Visit a static within an extern block, see `visit_foreign_static`
+    fn visit_foreign_static(&mut self, node: &ForeignStatic) {
+        visit_foreign_static(self, node)
+    }
+    /// Visit a type within an extern block, see `visit_foreign_type`
+    fn visit_foreign_type(&mut self, node: &ForeignType) {
+        visit_foreign_type(self, node)
+    }
+    /// Visit a generic parameter
+    fn visit_generic(&mut self, _node: &Generic) {}
+    /// Visit an attribute
+    fn visit_attribute(&mut self, _node: &Attribute) {}
+}
+
+/// Walk the attributes, items and submodules of a module
+pub fn visit_module<V: Visit + ?Sized>(v: &mut V, node: &Module) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.macros().iter().for_each(|n| v.visit_macro_rules(n));
+    node.macro_invocations()
+        .iter()
+        .for_each(|n| v.visit_macro_invocation(n));
+    node.consts().iter().for_each(|n| v.visit_const(n));
+    node.statics().iter().for_each(|n| v.visit_static(n));
+    node.type_aliases()
+        .iter()
+        .for_each(|n| v.visit_type_alias(n));
+    node.extern_blocks()
+        .iter()
+        .for_each(|n| v.visit_extern_block(n));
+    node.traits().iter().for_each(|n| v.visit_trait(n));
+    node.functions().iter().for_each(|n| v.visit_function(n));
+    node.structs().iter().for_each(|n| v.visit_struct(n));
+    node.impls().iter().for_each(|n| v.visit_impl(n));
+    node.enums().iter().for_each(|n| v.visit_enum(n));
+    node.unions().iter().for_each(|n| v.visit_union(n));
+    node.submodules()
+        .into_iter()
+        .for_each(|n| v.visit_module(n));
+}
+
+/// Walk the attributes, generics and fields of a struct
+pub fn visit_struct<V: Visit + ?Sized>(v: &mut V, node: &Struct) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.fields().iter().for_each(|n| v.visit_field(n));
+}
+
+/// Walk the attributes, generics and fields of a union
+pub fn visit_union<V: Visit + ?Sized>(v: &mut V, node: &Union) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.fields().iter().for_each(|n| v.visit_field(n));
+}
+
+/// Walk the attributes, generics and variants of an enum
+pub fn visit_enum<V: Visit + ?Sized>(v: &mut V, node: &Enum) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.variants().iter().for_each(|n| v.visit_variant(n));
+}
+
+/// Walk the attributes of an enum variant
+pub fn visit_variant<V: Visit + ?Sized>(v: &mut V, node: &Variant) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of a field
+pub fn visit_field<V: Visit + ?Sized>(v: &mut V, node: &Field) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes, generics, associated types, required and provided functions of
+/// a trait
+pub fn visit_trait<V: Visit + ?Sized>(v: &mut V, node: &Trait) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.associated_types()
+        .iter()
+        .for_each(|n| v.visit_associated_type_declaration(n));
+    node.signatures()
+        .iter()
+        .for_each(|n| v.visit_function_signature(n));
+    node.functions().iter().for_each(|n| v.visit_function(n));
+}
+
+/// Walk the attributes, generics, associated types and functions of an impl block
+pub fn visit_impl<V: Visit + ?Sized>(v: &mut V, node: &Impl) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.associated_types()
+        .iter()
+        .for_each(|n| v.visit_associated_type_definition(n));
+    node.functions().iter().for_each(|n| v.visit_function(n));
+}
+
+/// Walk the attributes of an associated type declared by a trait
+pub fn visit_associated_type_declaration<V: Visit + ?Sized>(
+    v: &mut V,
+    node: &AssociatedTypeDeclaration,
+) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of an associated type defined by an impl block
+pub fn visit_associated_type_definition<V: Visit + ?Sized>(
+    v: &mut V,
+    node: &AssociatedTypeDefinition,
+) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the signature of a function
+pub fn visit_function<V: Visit + ?Sized>(v: &mut V, node: &Function) {
+    v.visit_function_signature(node.signature());
+}
+
+/// Walk the attributes, generics and parameters of a function signature
+pub fn visit_function_signature<V: Visit + ?Sized>(v: &mut V, node: &FunctionSignature) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+    node.parameters().iter().for_each(|n| v.visit_parameter(n));
+}
+
+/// Walk the attributes of a function parameter
+pub fn visit_parameter<V: Visit + ?Sized>(v: &mut V, node: &Parameter) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of a const
+pub fn visit_const<V: Visit + ?Sized>(v: &mut V, node: &Const) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of a static
+pub fn visit_static<V: Visit + ?Sized>(v: &mut V, node: &Static) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes and generics of a type alias
+pub fn visit_type_alias<V: Visit + ?Sized>(v: &mut V, node: &TypeAlias) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.generics().iter().for_each(|n| v.visit_generic(n));
+}
+
+/// Walk the attributes of a `macro_rules!` definition
+pub fn visit_macro_rules<V: Visit + ?Sized>(v: &mut V, node: &MacroRules) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of a macro invocation
+pub fn visit_macro_invocation<V: Visit + ?Sized>(v: &mut V, node: &MacroInvocation) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes, types, statics and functions of an extern block
+pub fn visit_extern_block<V: Visit + ?Sized>(v: &mut V, node: &ExternBlock) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+    node.types().iter().for_each(|n| v.visit_foreign_type(n));
+    node.statics()
+        .iter()
+        .for_each(|n| v.visit_foreign_static(n));
+    node.functions()
+        .iter()
+        .for_each(|n| v.visit_function_signature(n));
+}
+
+/// Walk the attributes of a static within an extern block
+pub fn visit_foreign_static<V: Visit + ?Sized>(v: &mut V, node: &ForeignStatic) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Walk the attributes of a type within an extern block
+pub fn visit_foreign_type<V: Visit + ?Sized>(v: &mut V, node: &ForeignType) {
+    node.attributes().iter().for_each(|n| v.visit_attribute(n));
+}
+
+/// Traverse a code model by mutable reference, to transform it in place
+pub trait VisitMut {
+    /// Visit a module, see `visit_module_mut`
+    fn visit_module_mut(&mut self, node: &mut Module) {
+        visit_module_mut(self, node)
+    }
+    /// Visit a struct, see `visit_struct_mut`
+    fn visit_struct_mut(&mut self, node: &mut Struct) {
+        visit_struct_mut(self, node)
+    }
+    /// Visit a union, see `visit_union_mut`
+    fn visit_union_mut(&mut self, node: &mut Union) {
+        visit_union_mut(self, node)
+    }
+    /// Visit an enum, see `visit_enum_mut`
+    fn visit_enum_mut(&mut self, node: &mut Enum) {
+        visit_enum_mut(self, node)
+    }
+    /// Visit an enum variant, see `visit_variant_mut`
+    fn visit_variant_mut(&mut self, node: &mut Variant) {
+        visit_variant_mut(self, node)
+    }
+    /// Visit a field of a struct or union, see `visit_field_mut`
+    fn visit_field_mut(&mut self, node: &mut Field) {
+        visit_field_mut(self, node)
+    }
+    /// Visit a trait, see `visit_trait_mut`
+    fn visit_trait_mut(&mut self, node: &mut Trait) {
+        visit_trait_mut(self, node)
+    }
+    /// Visit an impl block, see `visit_impl_mut`
+    fn visit_impl_mut(&mut self, node: &mut Impl) {
+        visit_impl_mut(self, node)
+    }
+    /// Visit an associated type declared by a trait, see
+    /// `visit_associated_type_declaration_mut`
+    fn visit_associated_type_declaration_mut(&mut self, node: &mut AssociatedTypeDeclaration) {
+        visit_associated_type_declaration_mut(self, node)
+    }
+    /// Visit an associated type defined by an impl block, see
+    /// `visit_associated_type_definition_mut`
+    fn visit_associated_type_definition_mut(&mut self, node: &mut AssociatedTypeDefinition) {
+        visit_associated_type_definition_mut(self, node)
+    }
+    /// Visit a function, see `visit_function_mut`
+    fn visit_function_mut(&mut self, node: &mut Function) {
+        visit_function_mut(self, node)
+    }
+    /// Visit a function signature, see `visit_function_signature_mut`
+    fn visit_function_signature_mut(&mut self, node: &mut FunctionSignature) {
+        visit_function_signature_mut(self, node)
+    }
+    /// Visit a function parameter, see `visit_parameter_mut`
+    fn visit_parameter_mut(&mut self, node: &mut Parameter) {
+        visit_parameter_mut(self, node)
+    }
+    /// Visit a const, see `visit_const_mut`
+    fn visit_const_mut(&mut self, node: &mut Const) {
+        visit_const_mut(self, node)
+    }
+    /// Visit a static, see `visit_static_mut`
+    fn visit_static_mut(&mut self, node: &mut Static) {
+        visit_static_mut(self, node)
+    }
+    /// Visit a type alias, see `visit_type_alias_mut`
+    fn visit_type_alias_mut(&mut self, node: &mut TypeAlias) {
+        visit_type_alias_mut(self, node)
+    }
+    /// Visit a `macro_rules!` definition, see `visit_macro_rules_mut`
+    fn visit_macro_rules_mut(&mut self, node: &mut MacroRules) {
+        visit_macro_rules_mut(self, node)
+    }
+    /// Visit a macro invocation, see `visit_macro_invocation_mut`
+    fn visit_macro_invocation_mut(&mut self, node: &mut MacroInvocation) {
+        visit_macro_invocation_mut(self, node)
+    }
+    /// Visit an extern block, see `visit_extern_block_mut`
+    fn visit_extern_block_mut(&mut self, node: &mut ExternBlock) {
+        visit_extern_block_mut(self, node)
+    }
+    /// Visit a static within an extern block, see `visit_foreign_static_mut`
+    fn visit_foreign_static_mut(&mut self, node: &mut ForeignStatic) {
+        visit_foreign_static_mut(self, node)
+    }
+    /// Visit a type within an extern block, see `visit_foreign_type_mut`
+    fn visit_foreign_type_mut(&mut self, node: &mut ForeignType) {
+        visit_foreign_type_mut(self, node)
+    }
+    /// Visit a generic parameter
+    fn visit_generic_mut(&mut self, _node: &mut Generic) {}
+    /// Visit an attribute
+    fn visit_attribute_mut(&mut self, _node: &mut Attribute) {}
+    /// Called when two submodules were renamed to the same name while visiting
+    /// them, with the submodule visited first and the one colliding with it, see
+    /// `merge_submodules_mut`
+    fn merge_submodules_mut(&mut self, first: &mut Module, other: Module) {
+        merge_submodules_mut(self, first, other)
+    }
+}
+
+/// Walk the attributes, items and submodules of a module. Submodules renamed by the
+/// visitor can be looked up by their new name afterwards, submodules renamed to the
+/// same name are combined with `VisitMut::merge_submodules_mut`.
+pub fn visit_module_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Module) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.macros
+        .iter_mut()
+        .for_each(|n| v.visit_macro_rules_mut(n));
+    node.macro_invocations
+        .iter_mut()
+        .for_each(|n| v.visit_macro_invocation_mut(n));
+    node.consts.iter_mut().for_each(|n| v.visit_const_mut(n));
+    node.statics.iter_mut().for_each(|n| v.visit_static_mut(n));
+    node.type_aliases
+        .iter_mut()
+        .for_each(|n| v.visit_type_alias_mut(n));
+    node.extern_blocks
+        .iter_mut()
+        .for_each(|n| v.visit_extern_block_mut(n));
+    node.traits.iter_mut().for_each(|n| v.visit_trait_mut(n));
+    node.functions
+        .iter_mut()
+        .for_each(|n| v.visit_function_mut(n));
+    node.structs.iter_mut().for_each(|n| v.visit_struct_mut(n));
+    node.impls.iter_mut().for_each(|n| v.visit_impl_mut(n));
+    node.enums.iter_mut().for_each(|n| v.visit_enum_mut(n));
+    node.unions.iter_mut().for_each(|n| v.visit_union_mut(n));
+
+    let mut sub_modules = node
+        .sub_modules
+        .drain()
+        .map(|(_, m)| m)
+        .collect::<Vec<Module>>();
+    sub_modules.sort_by(|a, b| a.name().cmp(b.name()));
+    for mut sub_module in sub_modules {
+        v.visit_module_mut(&mut sub_module);
+        match node.sub_modules.get_mut(sub_module.name()) {
+            Some(first) => v.merge_submodules_mut(first, sub_module),
+            None => {
+                node.add_submodule(sub_module);
+            }
+        }
+    }
+}
+
+/// Merge a submodule renamed to the name of another submodule into it, keeping the
+/// items of `first` on conflicts, see `MergePolicy::KeepFirst`
+pub fn merge_submodules_mut<V: VisitMut + ?Sized>(_v: &mut V, first: &mut Module, other: Module) {
+    first
+        .merge(other, MergePolicy::KeepFirst)
+        .expect("`MergePolicy::KeepFirst` resolves every conflict");
+}
+
+/// Walk the attributes, generics and fields of a struct
+pub fn visit_struct_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Struct) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.fields_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_field_mut(n));
+}
+
+/// Walk the attributes, generics and fields of a union
+pub fn visit_union_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Union) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.fields_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_field_mut(n));
+}
+
+/// Walk the attributes, generics and variants of an enum
+pub fn visit_enum_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Enum) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.variants
+        .iter_mut()
+        .for_each(|n| v.visit_variant_mut(n));
+}
+
+/// Walk the attributes of an enum variant
+pub fn visit_variant_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Variant) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of a field
+pub fn visit_field_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Field) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes, generics, associated types, required and provided functions of
+/// a trait
+pub fn visit_trait_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Trait) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.associated_types
+        .iter_mut()
+        .for_each(|n| v.visit_associated_type_declaration_mut(n));
+    node.signatures
+        .iter_mut()
+        .for_each(|n| v.visit_function_signature_mut(n));
+    node.functions
+        .iter_mut()
+        .for_each(|n| v.visit_function_mut(n));
+}
+
+/// Walk the attributes, generics, associated types and functions of an impl block
+pub fn visit_impl_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Impl) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.associated_types
+        .iter_mut()
+        .for_each(|n| v.visit_associated_type_definition_mut(n));
+    node.functions
+        .iter_mut()
+        .for_each(|n| v.visit_function_mut(n));
+}
+
+/// Walk the attributes of an associated type declared by a trait
+pub fn visit_associated_type_declaration_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut AssociatedTypeDeclaration,
+) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of an associated type defined by an impl block
+pub fn visit_associated_type_definition_mut<V: VisitMut + ?Sized>(
+    v: &mut V,
+    node: &mut AssociatedTypeDefinition,
+) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the signature of a function
+pub fn visit_function_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Function) {
+    v.visit_function_signature_mut(&mut node.signature);
+}
+
+/// Walk the attributes, generics and parameters of a function signature
+pub fn visit_function_signature_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut FunctionSignature) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+    node.parameters
+        .iter_mut()
+        .for_each(|n| v.visit_parameter_mut(n));
+}
+
+/// Walk the attributes of a function parameter
+pub fn visit_parameter_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Parameter) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of a const
+pub fn visit_const_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Const) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of a static
+pub fn visit_static_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut Static) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes and generics of a type alias
+pub fn visit_type_alias_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut TypeAlias) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.generics_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_generic_mut(n));
+}
+
+/// Walk the attributes of a `macro_rules!` definition
+pub fn visit_macro_rules_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut MacroRules) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of a macro invocation
+pub fn visit_macro_invocation_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut MacroInvocation) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes, types, statics and functions of an extern block
+pub fn visit_extern_block_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ExternBlock) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+    node.types
+        .iter_mut()
+        .for_each(|n| v.visit_foreign_type_mut(n));
+    node.statics
+        .iter_mut()
+        .for_each(|n| v.visit_foreign_static_mut(n));
+    node.functions
+        .iter_mut()
+        .for_each(|n| v.visit_function_signature_mut(n));
+}
+
+/// Walk the attributes of a static within an extern block
+pub fn visit_foreign_static_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ForeignStatic) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
+
+/// Walk the attributes of a type within an extern block
+pub fn visit_foreign_type_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut ForeignType) {
+    node.attributes_mut()
+        .iter_mut()
+        .for_each(|n| v.visit_attribute_mut(n));
+}
@@ -0,0 +1,132 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+fn module() -> Module {
+    Module::new("models")
+        .set_is_pub(true)
+        .add_struct(
+            Struct::new("User")
+                .set_is_pub(true)
+                .add_field(Field::new("id", "u64"))
+                .add_field(Field::new("name", "String").set_is_pub(true).to_owned())
+                .add_doc("A user")
+                .to_owned(),
+        )
+        .add_enum(
+            Enum::new("Role")
+                .add_variant(Variant::new("Admin"))
+                .add_variant(Variant::new("Guest").set_inner(Some("(u8)")).to_owned())
+                .to_owned(),
+        )
+        .add_impl(
+            Impl::new("User")
+                .add_function(
+                    Function::new("id")
+                        .add_parameter(Parameter::new("self", "&Self"))
+                        .set_return_ty("u64")
+                        .set_body("self.id")
+                        .to_owned(),
+                )
+                .to_owned(),
+        )
+        .add_const(Const::new("MAX", "usize", "10"))
+        .add_submodule(Module::new("b"))
+        .add_submodule(Module::new("a"))
+        .to_owned()
+}
+
+#[test]
+fn read_accessors() {
+    let m = module();
+    assert_eq!(m.name(), "models");
+    assert!(m.is_pub());
+    assert_eq!(m.structs().len(), 1);
+    assert_eq!(
+        m.submodules()
+            .iter()
+            .map(|s| s.name())
+            .collect::<Vec<&str>>(),
+        vec!["a", "b"]
+    );
+
+    let user = m.get_struct("User").unwrap();
+    assert!(user.is_pub());
+    assert_eq!(user.docs(), ["/// A user"]);
+    let name = user.get_field("name").unwrap();
+    assert_eq!(
+        (name.name(), name.ty(), name.is_pub()),
+        ("name", "String", true)
+    );
+    assert!(user.get_field("email").is_none());
+
+    let role = m.get_enum("Role").unwrap();
+    assert_eq!(role.get_variant("Guest").unwrap().inner(), Some("(u8)"));
+    assert_eq!(role.get_variant("Admin").unwrap().inner(), None);
+
+    let ipl = &m.impls()[0];
+    assert_eq!(ipl.obj_name(), "User");
+    assert!(ipl.impl_trait().is_none());
+    let id = ipl.get_function("id").unwrap();
+    assert_eq!(id.return_ty(), Some("u64"));
+    assert_eq!(id.parameters()[0].ty(), "&Self");
+    assert_eq!(id.body(), ["self.id"]);
+
+    let max = m.get_const("MAX").unwrap();
+    assert_eq!((max.ty(), max.value()), ("usize", "10"));
+}
+
+#[test]
+fn lookup_mutation_and_removal() {
+    let mut m = module();
+    m.get_struct_mut("User")
+        .unwrap()
+        .add_field(Field::new("email", "String"))
+        .get_field_mut("id")
+        .unwrap()
+        .set_is_pub(true);
+    let removed = m
+        .get_struct_mut("User")
+        .unwrap()
+        .remove_field("name")
+        .unwrap();
+    assert_eq!(removed.name(), "name");
+
+    m.get_enum_mut("Role").unwrap().remove_variant("Guest");
+    m.get_struct_mut("User").unwrap().add_doc("More docs");
+    assert!(m.remove_const("MAX").is_some());
+    assert!(m.remove_const("MAX").is_none());
+    assert!(m.remove_submodule("a").is_some());
+    assert!(m.remove_submodule("b").is_some());
+    let mut ipl = m.impls()[0].clone();
+    ipl.get_function_mut("id").unwrap().set_is_pub(true);
+    assert!(ipl.get_function("id").unwrap().is_pub());
+
+    let expected = r#"
+        pub mod models
+        {
+            /// A user
+            /// More docs
+            pub struct User
+            {
+                pub id: u64,
+                email: String,
+            }
+            impl User
+            {
+                fn id(self: &Self) -> u64
+                {
+                    self.id
+                }
+            }
+            enum Role
+            {
+                Admin,
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
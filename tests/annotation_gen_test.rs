@@ -1,12 +1,12 @@
 pub mod utilities;
-use proffer::{Attribute, SrcCode};
+use proffer::*;
 
 #[test]
 fn test_attribute_attr() {
     let ann = "#[attr]";
     let attribute = Attribute::from(ann);
     match &attribute {
-        &Attribute::ItemAttr(ref s) => assert_eq!(&s, &ann),
+        Attribute::ItemAttr(s) => assert_eq!(&s, &ann),
         _ => panic!("Expected to match to Attribute::ItemAttr, got {:?}", ann),
     };
     assert_eq!(&attribute.generate(), ann);
@@ -17,8 +17,89 @@ fn test_attribute_mod_attr() {
     let ann = "#![foo_attr]";
     let attribute = Attribute::from(ann);
     match &attribute {
-        &Attribute::ScopeAttr(ref s) => assert_eq!(&s, &ann),
+        Attribute::ScopeAttr(s) => assert_eq!(&s, &ann),
         _ => panic!("Expected to match to Attribute::ScopeAttr, got {:?}", ann),
     };
     assert_eq!(&attribute.generate(), ann);
 }
+
+#[test]
+fn test_structured_attributes() {
+    assert_eq!(
+        Attribute::derive(["Debug", "serde::Serialize"]).generate(),
+        "#[derive(Debug, serde::Serialize)]"
+    );
+    assert_eq!(Attribute::repr(["C", "u8"]).generate(), "#[repr(C, u8)]");
+    let cfg = Cfg::any([
+        Cfg::all([Cfg::option("unix"), Cfg::feature("mio")]),
+        Cfg::not(Cfg::key_value("target_os", "windows")),
+    ]);
+    assert_eq!(
+        Attribute::cfg(cfg.clone()).generate(),
+        r#"#[cfg(any(all(unix, feature = "mio"), not(target_os = "windows")))]"#
+    );
+    assert_eq!(
+        Attribute::cfg_attr(
+            Cfg::feature("serde"),
+            MetaList::new("derive").add_paths(["Serialize"]).to_owned()
+        )
+        .generate(),
+        r#"#[cfg_attr(feature = "serde", derive(Serialize))]"#
+    );
+    let meta = MetaList::new("serde")
+        .add_item(Meta::name_str("rename", "say \"hi\""))
+        .add_item("default")
+        .to_owned();
+    assert_eq!(
+        Attribute::meta(meta).generate(),
+        r#"#[serde(rename = "say \"hi\"", default)]"#
+    );
+    assert_eq!(
+        Attribute::scope_meta(MetaList::new("allow").add_item("dead_code").to_owned()).generate(),
+        "#![allow(dead_code)]"
+    );
+}
+
+#[test]
+fn test_attribute_inspection() {
+    let attribute = Attribute::from(r#"#[serde(rename = "a,b", skip(x))]"#);
+    assert_eq!(attribute.path(), Some("serde".to_string()));
+    match attribute.parse_meta() {
+        Some(Meta::List(list)) => {
+            assert_eq!(
+                list.items(),
+                &[
+                    Meta::name_value("rename", "\"a,b\""),
+                    Meta::List(MetaList::new("skip").add_item("x").to_owned())
+                ]
+            );
+        }
+        other => panic!("Expected a meta list, got {:?}", other),
+    }
+    assert_eq!(
+        Attribute::from("#[derive(Clone, PartialEq)]").derives(),
+        vec!["Clone", "PartialEq"]
+    );
+    assert!(Attribute::from("#[doc = \"x\"]").derives().is_empty());
+    assert!(Attribute::from("#[foo(]").parse_meta().is_none());
+}
+
+#[test]
+fn test_derive_merge() {
+    let s = Struct::new("Foo")
+        .add_attribute(Attribute::derive(["Debug"]))
+        .add_attribute("#[serde(default)]")
+        .add_attributes(vec![
+            Attribute::from("#[derive(Clone, Debug)]"),
+            Attribute::derive(["Hash"]),
+        ])
+        .to_owned();
+    let expected = r#"
+        #[derive(Debug, Clone, Hash)]
+        #[serde(default)]
+        struct Foo
+        {
+        }
+    "#;
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&s.generate()));
+}
@@ -0,0 +1,251 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn block_gen_statements() {
+    let block = Block::new()
+        .add_stmt(Stmt::let_typed("mut total", "u64", "0"))
+        .add_stmt(Stmt::expr(Expr::for_(
+            "item",
+            Expr::path("items").method("iter", vec![]),
+            Block::new()
+                .add_stmt(Stmt::semi(Expr::binary(
+                    "total",
+                    "+=",
+                    Expr::path("item").field("size"),
+                )))
+                .to_owned(),
+        )))
+        .add_stmt(Stmt::expr(
+            Expr::if_(
+                Expr::binary("total", ">", "LIMIT"),
+                Block::new()
+                    .add_stmt(Stmt::semi(Expr::return_(Expr::call(
+                        "Err",
+                        vec![Expr::lit_str("too large")],
+                    ))))
+                    .to_owned(),
+            )
+            .set_else(Some(Block::new()))
+            .to_owned(),
+        ))
+        .add_stmt(Stmt::expr(Expr::call("Ok", vec!["total".into()])))
+        .to_owned();
+    let expected = r#"
+        {
+            let mut total: u64 = 0;
+            for item in items.iter()
+            {
+                total += item.size;
+            }
+            if total > LIMIT
+            {
+                return Err("too large");
+            }
+            else {
+            }
+            Ok(total)
+        }
+    "#;
+    let src_code = block.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn expr_gen() {
+    let expr = Expr::struct_("User")
+        .add_field_value(
+            "id",
+            Expr::path("row").method("get", vec!["0".into()]).try_op(),
+        )
+        .add_field_value(
+            "tags",
+            Expr::path("tags")
+                .method("into_iter", vec![])
+                .method(
+                    "map",
+                    vec![Expr::closure(
+                        vec!["t"],
+                        Expr::path("t").method("to_string", vec![]),
+                    )],
+                )
+                .method("collect", vec![]),
+        )
+        .set_struct_base(Some("Default::default()"))
+        .to_owned();
+    let expected = r#"User { id: row.get(0)?, tags: tags.into_iter().map(|t| t.to_string()).collect(), ..Default::default() }"#;
+    let src_code = expr.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(expected, &src_code);
+
+    let expr = Expr::binary("a", "+", "b")
+        .reference(false)
+        .method("clone", vec![]);
+    assert_eq!("(&(a + b)).clone()", expr.generate_and_verify());
+}
+
+#[test]
+fn function_with_block_body() {
+    let body = Block::new()
+        .add_stmt(Stmt::expr(
+            Expr::match_(Expr::path("self"))
+                .add_arm(MatchArm::new("Color::Red", Expr::lit_str("red")))
+                .add_arm(
+                    MatchArm::new(
+                        "Color::Other(name)",
+                        Expr::path("name").method("as_str", vec![]),
+                    )
+                    .set_guard(Some("!name.is_empty()"))
+                    .to_owned(),
+                )
+                .add_arm(MatchArm::new(
+                    "_",
+                    Block::new()
+                        .add_stmt(Stmt::expr(Expr::lit_str("unknown")))
+                        .to_owned(),
+                ))
+                .to_owned(),
+        ))
+        .to_owned();
+    let function = Function::new("name")
+        .add_parameter(Parameter::new("self", "&Self"))
+        .set_return_ty("&str")
+        .set_block(body)
+        .to_owned();
+    let expected = r#"
+        fn name(self: &Self) -> &str
+        {
+            match self
+            {
+                Color::Red => "red",
+                Color::Other(name) if !name.is_empty() => name.as_str(),
+                _ => {
+                    "unknown"
+                },
+            }
+        }
+    "#;
+    let src_code = function.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn stmt_push_into_body() {
+    let function = Function::new("foo")
+        .push_into_body(Stmt::let_("x", Expr::call("bar", vec![])))
+        .push_into_body(Stmt::expr(Expr::path("x").try_op()))
+        .set_return_ty("Result<(), Error>")
+        .to_owned();
+    let expected = r#"
+        fn foo() -> Result<(), Error>
+        {
+            let x = bar();
+            x?
+        }
+    "#;
+    let src_code = function.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn expr_gen_binary_precedence() {
+    let expr = Expr::binary(Expr::binary("a", "+", "b"), "*", "c");
+    assert_eq!("(a + b) * c", expr.generate_and_verify());
+
+    let expr = Expr::binary("a", "+", Expr::binary("b", "*", "c"));
+    assert_eq!("a + b * c", expr.generate_and_verify());
+
+    let expr = Expr::binary("a", "-", Expr::binary("b", "-", "c"));
+    assert_eq!("a - (b - c)", expr.generate_and_verify());
+
+    let expr = Expr::binary(Expr::binary("a", "-", "b"), "-", "c");
+    assert_eq!("a - b - c", expr.generate_and_verify());
+
+    let expr = Expr::binary(
+        Expr::binary("a", "==", "b"),
+        "==",
+        Expr::binary("c", "<", "d"),
+    );
+    assert_eq!("(a == b) == (c < d)", expr.generate_and_verify());
+
+    let expr = Expr::binary(
+        Expr::binary("0", "..", "n"),
+        "==",
+        Expr::Cast {
+            expr: Box::new(Expr::binary("a", "|", "b")),
+            ty: "u8".to_string(),
+        },
+    );
+    assert_eq!("(0 .. n) == (a | b) as u8", expr.generate_and_verify());
+
+    let expr = Expr::binary(
+        "x",
+        "=",
+        Expr::binary("y", "=", Expr::closure(vec!["z"], "z")),
+    );
+    assert_eq!("x = y = (|z| z)", expr.generate());
+}
+
+#[test]
+fn expr_gen_raw_and_cast_operands() {
+    let expr = Expr::binary("a + b", "*", "c");
+    assert_eq!("(a + b) * c", expr.generate_and_verify());
+
+    let expr = Expr::binary("-a", "*", "self.b.len()");
+    assert_eq!("-a * self.b.len()", expr.generate_and_verify());
+
+    let expr = Expr::binary("f(a < b, c)", "+", "Vec::<u8>::new().len()");
+    assert_eq!(
+        "f(a < b, c) + Vec::<u8>::new().len()",
+        expr.generate_and_verify()
+    );
+
+    let expr = Expr::raw("a as u8").method("into", vec![]);
+    assert_eq!("(a as u8).into()", expr.generate_and_verify());
+
+    let cast = Expr::Cast {
+        expr: Box::new(Expr::path("a")),
+        ty: "u32".to_string(),
+    };
+    let expr = Expr::binary(cast.clone(), "<", "b");
+    assert_eq!("(a as u32) < b", expr.generate_and_verify());
+
+    let expr = Expr::binary(Expr::binary("x", "+", cast.clone()), "<<", "b");
+    assert_eq!("(x + a as u32) << b", expr.generate_and_verify());
+
+    let expr = Expr::binary("b", "<", cast);
+    assert_eq!("b < a as u32", expr.generate_and_verify());
+}
+
+#[test]
+fn expr_gen_struct_in_conditions() {
+    let foo = Expr::struct_("Foo").with_field_value("a", "1");
+
+    let expr = Expr::if_(Expr::binary(foo.clone(), "==", "x"), Block::new());
+    let src_code = expr.generate_and_verify();
+    assert_eq!(Some("if (Foo { a: 1 } == x)"), src_code.lines().next());
+
+    let expr = Expr::while_(foo.clone().method("is_empty", vec![]), Block::new());
+    let src_code = expr.generate_and_verify();
+    assert_eq!(
+        Some("while (Foo { a: 1 }.is_empty())"),
+        src_code.lines().next()
+    );
+
+    let expr = Expr::match_(foo.clone()).with_arm(MatchArm::new("_", "()"));
+    let src_code = expr.generate_and_verify();
+    assert_eq!(Some("match (Foo { a: 1 })"), src_code.lines().next());
+
+    let expr = Expr::if_("let Foo { a } = x", Block::new());
+    let src_code = expr.generate_and_verify();
+    assert_eq!(Some("if let Foo { a } = x"), src_code.lines().next());
+
+    let expr = Expr::if_(Expr::call("f", vec![foo]), Block::new());
+    let src_code = expr.generate_and_verify();
+    assert_eq!(Some("if f(Foo { a: 1 })"), src_code.lines().next());
+}
@@ -0,0 +1,93 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn case_conversion() {
+    assert_eq!(to_snake_case("userId"), "user_id");
+    assert_eq!(to_snake_case("HTTPServerURL"), "http_server_url");
+    assert_eq!(to_snake_case("content-type"), "content_type");
+    assert_eq!(to_snake_case("sha256Sum"), "sha256_sum");
+    assert_eq!(to_snake_case("__Private__Name"), "private_name");
+    assert_eq!(to_snake_case("ÄrgerÜber"), "ärger_über");
+    assert_eq!(to_snake_case("名前Value"), "名前_value");
+
+    assert_eq!(to_upper_camel_case("user_id"), "UserId");
+    assert_eq!(to_upper_camel_case("XMLHttpRequest"), "XmlHttpRequest");
+    assert_eq!(to_upper_camel_case("version 2"), "Version2");
+    assert_eq!(to_upper_camel_case("v1_2"), "V1_2");
+    assert_eq!(to_upper_camel_case("straße"), "Straße");
+
+    assert_eq!(to_screaming_snake_case("maxRetries"), "MAX_RETRIES");
+    assert_eq!(to_screaming_snake_case("api.version"), "API_VERSION");
+    assert_eq!(to_screaming_snake_case("straße"), "STRASSE");
+}
+
+#[test]
+fn convert_with_policy() {
+    let policy = IdentPolicy::raw();
+    let ident = policy.convert("user_id", Case::Snake);
+    assert_eq!(ident.ident(), "user_id");
+    assert_eq!(ident.original(), None);
+
+    let ident = policy.convert("Type", Case::Snake);
+    assert_eq!(ident.ident(), "r#type");
+    assert_eq!(ident.original(), Some("Type"));
+
+    let ident = policy.convert("2fa-code", Case::UpperCamel);
+    assert_eq!(ident.ident(), "_2faCode");
+    assert_eq!(ident.original(), Some("2fa-code"));
+}
+
+#[test]
+fn external_name_gen() {
+    let policy = IdentPolicy::sanitize()
+        .with_rename_attribute(Some("#[serde(rename = \"{{ name }}\")]"))
+        .unwrap();
+    let s = Struct::new_from_external_name("user-profile", &policy)
+        .add_field(Field::new_from_external_name("userId", "u64", &policy))
+        .add_field(Field::new_from_external_name("name", "String", &policy))
+        .to_owned();
+    let e = Enum::new_from_external_name("account_state", &policy)
+        .add_variant(Variant::new_from_external_name("ACTIVE", &policy))
+        .add_variant(Variant::new_from_external_name("Closed", &policy))
+        .to_owned();
+    let c = Const::new_from_external_name("maxUsers", "usize", "10", &policy);
+    let f = Function::new_from_external_name("GetUser", &policy)
+        .add_parameter(Parameter::new_from_external_name("UserId", "u64", &policy))
+        .to_owned();
+    let t = TypeAlias::new_from_external_name("user_ids", "Vec<u64>", &policy);
+    let m = Module::new_from_external_name("UserApi", &policy)
+        .add_type_alias(t)
+        .add_const(c)
+        .add_struct(s)
+        .add_enum(e)
+        .add_function(f)
+        .to_owned();
+    let expected = r#"
+        mod user_api
+        {
+            const MAX_USERS: usize = 10;
+            type UserIds = Vec<u64>;
+            fn get_user(user_id: u64) -> ()
+            {
+            }
+            struct UserProfile
+            {
+                #[serde(rename = "userId")]
+                user_id: u64,
+                name: String,
+            }
+            enum AccountState
+            {
+                #[serde(rename = "ACTIVE")]
+                Active,
+                Closed,
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
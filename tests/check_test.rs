@@ -0,0 +1,145 @@
+#![cfg(feature = "check")]
+
+use proffer::*;
+
+#[test]
+fn valid_source_is_returned() {
+    let s = Struct::new("Foo")
+        .add_field(Field::new("bar", "Vec<u8>"))
+        .to_owned();
+    assert_eq!(s.generate_checked().unwrap(), s.generate());
+
+    let m = Module::new("foo").add_struct(s).to_owned();
+    assert_eq!(m.generate_file_checked().unwrap(), m.generate_file());
+}
+
+#[test]
+fn invalid_field_type() {
+    let s = Struct::new("Foo")
+        .add_field(Field::new("ok", "u8"))
+        .add_field(Field::new("bar", "Vec<u8"))
+        .to_owned();
+    let err = s.generate_checked().unwrap_err();
+    println!("{}", err);
+    // The parser only notices the missing `>` at the closing brace, the context
+    // lines of the snippet still show the offending field.
+    let line = err.src_code().lines().nth(err.line() - 1).unwrap();
+    assert_eq!(line.trim(), "}");
+    assert!(err.snippet().contains("bar: Vec<u8,"));
+    assert!(err.to_string().contains(&format!("line {}", err.line())));
+}
+
+#[test]
+fn invalid_variant_inner() {
+    let e = Enum::new("Foo")
+        .add_variant(Variant::new("A").set_inner(Some("(u8")).to_owned())
+        .to_owned();
+    let err = e.generate_checked().unwrap_err();
+    println!("{}", err);
+    assert!(err.snippet().contains("A(u8"));
+}
+
+#[test]
+fn invalid_function_body() {
+    let f = Function::new("foo")
+        .set_body("if true { 1 } else {")
+        .to_owned();
+    let err = f.generate_checked().unwrap_err();
+    println!("{}", err);
+    assert!(!err.message().is_empty());
+    assert!(err.line() > 0);
+}
+
+#[test]
+fn variadic_function_with_body() {
+    let f = Function::from(
+        FunctionSignature::new("foo")
+            .with_parameter(Parameter::new("format", "*const u8"))
+            .with_is_variadic(true),
+    );
+    let err = f.generate_checked().unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.message(), "only foreign functions may be variadic");
+    assert!(err.snippet().contains("format: *const u8, ..."));
+
+    let block = ExternBlock::new("C").with_function(f.signature().clone());
+    assert!(block.generate_checked().is_ok());
+}
+
+#[test]
+fn bracketed_operands_are_valid() {
+    let foo = Expr::struct_("Foo").with_field_value("a", "1");
+    let expr = Expr::if_(Expr::binary(foo, "==", "x"), Block::new());
+    assert!(expr.generate_checked().is_ok());
+
+    let cast = Expr::Cast {
+        expr: Box::new(Expr::path("a")),
+        ty: "u32".to_string(),
+    };
+    assert!(Expr::binary(cast, "<", "b").generate_checked().is_ok());
+}
+
+#[test]
+fn valid_parts_are_returned() {
+    let field = Field::new("bar", "u8").set_is_pub(true).to_owned();
+    assert!(field.generate_checked().is_ok());
+    let param = Parameter::new("bar", "&mut Vec<u8>");
+    assert!(param.generate_checked().is_ok());
+    let sig = FunctionSignature::new("foo")
+        .with_parameter(param)
+        .with_return_ty(Some("u8"));
+    assert!(sig.generate_checked().is_ok());
+    let generics = vec![
+        Generic::new("T").with_trait_bound("Clone"),
+        Generic::new("U"),
+    ];
+    assert!(generics.generate_checked().is_ok());
+    assert!(Attribute::from("#[derive(Clone)]")
+        .generate_checked()
+        .is_ok());
+    assert!(Attribute::from("#![allow(dead_code)]")
+        .generate_checked()
+        .is_ok());
+    assert!(MacroRule::new("$a:expr", "$a").generate_checked().is_ok());
+    assert!(Comment::line("region: models").generate_checked().is_ok());
+    assert!(Cfg::feature("serde").generate_checked().is_ok());
+    assert!(AssociatedTypeDefinition::new("Item", "u8")
+        .generate_checked()
+        .is_ok());
+}
+
+#[test]
+fn documented_pub_signature() {
+    let sig = FunctionSignature::new("name")
+        .with_is_pub(true)
+        .with_doc("/// The name")
+        .with_attribute("#[inline]")
+        .with_parameter(Parameter::new("self", "&Self"))
+        .with_return_ty(Some("String"));
+    assert_eq!(sig.generate_checked().unwrap(), sig.generate());
+
+    let sig = sig.with_return_ty(Some("Vec<String"));
+    assert!(sig.generate_checked().is_err());
+}
+
+#[test]
+fn invalid_parts() {
+    assert!(Field::new("bar", "Vec<u8").generate_checked().is_err());
+    assert!(Parameter::new("bar", "u8)").generate_checked().is_err());
+    assert!(Attribute::from("#[derive(Clone]")
+        .generate_checked()
+        .is_err());
+    assert!(vec![Generic::new("T").with_trait_bound("Clone<")]
+        .generate_checked()
+        .is_err());
+}
+
+#[test]
+fn error_at_end_of_input() {
+    let err = Parameter::new("bar", "Vec<u8")
+        .generate_checked()
+        .unwrap_err();
+    println!("{}", err);
+    assert_eq!(err.line(), 1);
+    assert!(err.snippet().contains("bar: Vec<u8"));
+}
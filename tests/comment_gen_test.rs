@@ -0,0 +1,228 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn comment_gen() {
+    let e = Enum::new("Kind")
+        .add_comment("Generated from schema.json: enum kind")
+        .add_variant(Variant::new("A"))
+        .add_variant(
+            Variant::new("B")
+                .add_comment("Deprecated\nuse A instead")
+                .add_doc("The B kind")
+                .to_owned(),
+        )
+        .to_owned();
+    let f = Function::new("kind")
+        .add_blank_line()
+        .add_comment("region: accessors")
+        .set_block(
+            Block::new()
+                .add_stmt(Stmt::comment("Always A for now"))
+                .add_stmt(Stmt::blank_line())
+                .add_stmt(Stmt::expr(Expr::path("Kind::A")))
+                .to_owned(),
+        )
+        .set_return_ty("Kind")
+        .to_owned();
+    let m = Module::new("kinds")
+        .add_comment("")
+        .add_enum(e)
+        .add_function(f)
+        .to_owned();
+    let expected = r#"
+        //
+        mod kinds
+        {
+            // region: accessors
+            fn kind() -> Kind
+            {
+                // Always A for now
+                Kind::A
+            }
+            // Generated from schema.json: enum kind
+            enum Kind
+            {
+                A,
+                // Deprecated
+                // use A instead
+                /// The B kind
+                B,
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn blank_line_gen() {
+    let src_code = Stmt::blank_line().generate();
+    assert_eq!(src_code, "");
+    let c = Const::new("A", "u8", "1")
+        .add_blank_line()
+        .add_comment("Limits")
+        .to_owned();
+    let src_code = c.generate();
+    let lines = src_code.lines().map(str::trim).collect::<Vec<&str>>();
+    let idx = lines.iter().position(|l| *l == "// Limits").unwrap();
+    assert_eq!(lines[idx - 1], "");
+}
+
+#[test]
+fn separator_gen() {
+    let mut m = Module::new("models")
+        .with_function(Function::new("helper"))
+        .with_separator(Comment::line("region: users"))
+        .with_struct(Struct::new("User"))
+        .with_impl(Impl::new("User"))
+        .with_separator(Comment::line("endregion: users"))
+        .with_separator(Comment::line("region: groups"))
+        .with_enum(Enum::new("Group"))
+        .with_separator(Comment::line("endregion: groups"));
+    let expected = r#"
+        mod models
+        {
+            fn helper() -> ()
+            {
+            }
+            // region: users
+            struct User
+            {
+            }
+            impl User
+            {
+            }
+            // endregion: users
+            // region: groups
+            enum Group
+            {
+            }
+            // endregion: groups
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    // Separators before a removed item move to the item rendered after it.
+    m.remove_struct("User");
+    let expected = r#"
+        mod models
+        {
+            fn helper() -> ()
+            {
+            }
+            // region: users
+            impl User
+            {
+            }
+            // endregion: users
+            // region: groups
+            enum Group
+            {
+            }
+            // endregion: groups
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn separator_out_of_render_order_gen() {
+    let mut m = Module::new("m")
+        .with_separator(Comment::line("region: models"))
+        .with_struct(Struct::new("A"))
+        .with_separator(Comment::line("region: functions"))
+        .with_function(Function::new("f"))
+        .with_separator(Comment::line("region: constants"))
+        .with_const(Const::new("MAX", "u8", "1"));
+    let expected = r#"
+        mod m
+        {
+            // region: constants
+            const MAX: u8 = 1;
+            // region: functions
+            fn f() -> ()
+            {
+            }
+            // region: models
+            struct A
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    // Removing the last item of a kind moves its separators to the next kind rendered.
+    m.remove_function("f");
+    let expected = r#"
+        mod m
+        {
+            // region: constants
+            const MAX: u8 = 1;
+            // region: functions
+            // region: models
+            struct A
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn separator_before_repeated_keys_gen() {
+    let m = Module::new("api")
+        .with_extern_block(ExternBlock::new("C").with_function(FunctionSignature::new("a")))
+        .with_separator(Comment::line("before second extern"))
+        .with_extern_block(ExternBlock::new("C").with_function(FunctionSignature::new("b")))
+        .with_struct(Struct::new("Foo"))
+        .with_impl(Impl::new("Foo").with_function(Function::new("a")))
+        .with_separator(Comment::line("before second impl"))
+        .with_impl(Impl::new("Foo").with_function(Function::new("b")))
+        .with_raw_item("const RAW: u8 = 0;");
+    let expected = r#"
+        mod api
+        {
+            extern "C"
+            {
+                fn a() -> ();
+            }
+            // before second extern
+            extern "C"
+            {
+                fn b() -> ();
+            }
+            struct Foo
+            {
+            }
+            impl Foo
+            {
+                fn a() -> ()
+                {
+                }
+            }
+            // before second impl
+            impl Foo
+            {
+                fn b() -> ()
+                {
+                }
+            }
+            const RAW: u8 = 0;
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
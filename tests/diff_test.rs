@@ -0,0 +1,168 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn test_diff_identical_modules() {
+    let m = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_derive("Debug")
+                .with_field(Field::new("id", "u32").with_is_pub(true)),
+        )
+        .with_enum(Enum::new("Role").with_variant(Variant::new("Admin")))
+        .with_impl(
+            Impl::new("User").with_function(
+                Function::new("id")
+                    .with_return_ty("u32")
+                    .with_body("self.id"),
+            ),
+        )
+        .with_submodule(Module::new("auth").with_const(Const::new("TTL", "u64", "60")));
+
+    let diff = m.diff(&m.clone());
+    assert!(diff.is_empty());
+    assert_eq!(diff.to_string(), "");
+}
+
+#[test]
+fn test_diff_struct_changes() {
+    let old = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_derive("Debug")
+                .with_field(Field::new("id", "u32").with_is_pub(true))
+                .with_field(Field::new("email", "String")),
+        )
+        .with_struct(Struct::new("Session"));
+    let new = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_derive("Debug")
+                .with_derive("Clone")
+                .with_field(Field::new("id", "u64").with_is_pub(true))
+                .with_field(Field::new("email", "String"))
+                .with_field(Field::new("name", "String")),
+        )
+        .with_struct(Struct::new("Token"));
+    new.generate_and_verify();
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.to_string(),
+        "\
+struct `User` derives changed: added `Clone`
+field `User::id` type changed from `u32` to `u64`
+added field `User::name`
+removed struct `Session`
+added struct `Token`
+"
+    );
+    let change = &diff.changes()[1];
+    assert_eq!(change.kind(), ItemKind::Field);
+    assert_eq!(change.name(), "User::id");
+    assert_eq!(
+        change.detail(),
+        &ChangeKind::TypeChanged {
+            from: "u32".to_string(),
+            to: "u64".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_diff_members_and_submodules() {
+    let old = Module::new("api")
+        .with_enum(
+            Enum::new("Role")
+                .with_variant(Variant::new("Admin"))
+                .with_variant(Variant::new("Guest")),
+        )
+        .with_trait(
+            Trait::new("Named")
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String")))
+                .with_function(
+                    Function::new("greet")
+                        .with_return_ty("String")
+                        .with_body("self.name()"),
+                ),
+        )
+        .with_impl(
+            Impl::new("User").with_function(
+                Function::new("id")
+                    .with_return_ty("u32")
+                    .with_body("self.id"),
+            ),
+        )
+        .with_submodule(Module::new("auth").with_const(Const::new("TTL", "u64", "60")));
+    let new = Module::new("api")
+        .with_enum(Enum::new("Role").with_variant(Variant::new("Admin")))
+        .with_trait(
+            Trait::new("Named")
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String")))
+                .with_signature(FunctionSignature::new("greet").with_return_ty(Some("String"))),
+        )
+        .with_impl(
+            Impl::new("User").with_function(
+                Function::new("id")
+                    .with_return_ty("u64")
+                    .with_body("self.id"),
+            ),
+        )
+        .with_submodule(Module::new("auth").with_const(Const::new("TTL", "u64", "120")));
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.to_string(),
+        "\
+fn `Named::greet` no longer has a default implementation
+fn `User::id` signature changed from `fn id() -> u32` to `fn id() -> u64`
+removed variant `Role::Guest`
+const `auth::TTL` value changed from `60` to `120`
+"
+    );
+    assert_eq!(diff.changes()[3].path(), ["auth"]);
+}
+
+#[test]
+fn test_diff_trait_impls_and_added_modules() {
+    let old = Module::new("api").with_impl(
+        Impl::new("User")
+            .with_impl_trait(Some(Trait::new("Display")))
+            .with_function(Function::new("fmt").with_body("Ok(())")),
+    );
+    let new = Module::new("api")
+        .with_impl(
+            Impl::new("User")
+                .with_impl_trait(Some(Trait::new("Display")))
+                .with_function(Function::new("fmt").with_body("write!(f, \"user\")")),
+        )
+        .with_submodule(Module::new("models").with_struct(Struct::new("Group")));
+
+    let diff = old.diff(&new);
+    assert_eq!(
+        diff.to_string(),
+        "\
+fn `<User as Display>::fmt` body changed
+added mod `models`
+"
+    );
+    assert_eq!(new.diff(&old).changes()[1].detail(), &ChangeKind::Removed);
+}
+
+#[test]
+fn test_diff_duplicate_impls() {
+    let m = Module::new("api")
+        .with_struct(Struct::new("User"))
+        .with_impl(Impl::new("User").with_function(Function::new("a").with_body("1")))
+        .with_impl(Impl::new("User").with_function(Function::new("b").with_body("2")));
+    m.generate_and_verify();
+
+    assert!(m.diff(&m.clone()).is_empty());
+    assert_eq!(m.semver_check(&m.clone()).bump(), Bump::Patch);
+
+    let mut new = m.clone();
+    new.add_impl(Impl::new("User").with_function(Function::new("c")));
+    assert_eq!(m.diff(&new).to_string(), "added impl `User`\n");
+}
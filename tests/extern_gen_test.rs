@@ -0,0 +1,99 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn extern_block_gen() {
+    let block = ExternBlock::new("C")
+        .add_attribute("#[link(name = \"foo\")]")
+        .add_function(
+            FunctionSignature::new("foo")
+                .set_is_pub(true)
+                .add_parameter(Parameter::new("x", "i32"))
+                .set_return_ty(Some("i32"))
+                .to_owned(),
+        )
+        .add_static(ForeignStatic::new("BAR", "u32"))
+        .add_static(
+            ForeignStatic::new("BAZ", "*mut u8")
+                .set_is_mut(true)
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        #[link(name = "foo")]
+        extern "C"
+        {
+            static BAR: u32;
+            static mut BAZ: *mut u8;
+            pub fn foo(x: i32) -> i32;
+        }
+    "#;
+    let src_code = block.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn extern_block_variadic_and_types() {
+    let block = ExternBlock::new("C")
+        .add_type(ForeignType::new("FILE").set_is_pub(true).to_owned())
+        .add_function(
+            FunctionSignature::new("printf")
+                .add_parameter(Parameter::new("format", "*const u8"))
+                .set_return_ty(Some("i32"))
+                .set_is_variadic(true)
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        extern "C"
+        {
+            pub type FILE;
+            fn printf(format: *const u8, ...) -> i32;
+        }
+    "#;
+    let src_code = block.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn extern_block_in_module() {
+    let m = Module::new("ffi")
+        .add_extern_block(
+            ExternBlock::new("system")
+                .add_function(FunctionSignature::new("tick"))
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        mod ffi
+        {
+            extern "system"
+            {
+                fn tick() -> ();
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn extern_block_variadic_without_parameters() {
+    let block = ExternBlock::new("C")
+        .add_function(FunctionSignature::new("f").set_is_variadic(true).to_owned())
+        .to_owned();
+    let expected = r#"
+        extern "C"
+        {
+            fn f(...) -> ();
+        }
+    "#;
+    let src_code = block.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
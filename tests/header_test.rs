@@ -0,0 +1,104 @@
+use proffer::*;
+
+#[test]
+fn header_gen() {
+    let header = FileHeader::new("proffer")
+        .set_license(Some("Copyright (c) Example\n\nLicensed under MIT"))
+        .add_attribute("#![allow(clippy::all)]")
+        .add_attribute("#![allow(dead_code)]")
+        .to_owned();
+    let m = Module::new("lib")
+        .set_header(Some(header))
+        .add_attribute("#![deny(missing_docs)]")
+        .add_struct(Struct::new("Foo"))
+        .to_owned();
+    let expected = r#"
+        // Copyright (c) Example
+        //
+        // Licensed under MIT
+        // @generated by proffer — DO NOT EDIT
+        #![allow(clippy::all)]
+        #![allow(dead_code)]
+        #![deny(missing_docs)]
+        struct Foo
+        {
+        }
+    "#;
+    let src_code = m.generate_file();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+    assert_eq!(FileHeader::check(&src_code), HashStatus::Missing);
+    syn::parse_file(&src_code).unwrap();
+
+    // The header only applies when rendering the module as a file.
+    assert!(!m.generate().contains("@generated"));
+}
+
+#[test]
+fn header_template_and_hash() {
+    let header = FileHeader::new("gen")
+        .set_input(Some("users.sql"))
+        .set_template("// Code generated by {{ tool }} ({{ input }}); DO NOT EDIT.")
+        .unwrap()
+        .set_hashed(true)
+        .to_owned();
+    let src_code = header.apply("pub struct User;\n");
+    let mut lines = src_code.lines();
+    assert_eq!(
+        lines.next(),
+        Some("// Code generated by gen (users.sql); DO NOT EDIT.")
+    );
+    assert!(lines.next().unwrap().starts_with("// @generated-hash: "));
+    assert_eq!(lines.next(), Some("pub struct User;"));
+
+    assert_eq!(FileHeader::check(&src_code), HashStatus::Valid);
+    assert_eq!(
+        FileHeader::check(&src_code.replace("pub ", "")),
+        HashStatus::Modified
+    );
+    assert_eq!(
+        FileHeader::check(&format!("{}// hand edit\n", src_code)),
+        HashStatus::Modified
+    );
+    // Regenerating the same contents gives the same hash.
+    assert_eq!(header.apply("pub struct User;\n"), src_code);
+}
+
+#[test]
+fn header_invalid_template() {
+    let mut header = FileHeader::new("gen");
+    let err = header.set_template("// by {{ tool ").unwrap_err();
+    assert_eq!(err.template(), "// by {{ tool ");
+    let err = header
+        .set_template("// by {{ tool }}\n#![allow(dead_code)]")
+        .unwrap_err();
+    assert!(err.to_string().contains("`//` comments only"));
+    assert!(FileHeader::new("gen")
+        .with_template("{% if input %}// from {{ input }}{% endif %}")
+        .is_ok());
+
+    // The header is left as it was.
+    assert_eq!(header.template(), FileHeader::new("gen").template());
+}
+
+#[test]
+fn header_escapes_tool_and_input() {
+    let header = FileHeader::new("gen\r")
+        .with_input(Some("schema.json\nfn injected() {}"))
+        .with_attribute("#[allow(dead_code)]");
+    assert_eq!(header.tool(), "gen\\r");
+    assert_eq!(header.input(), Some("schema.json\\nfn injected() {}"));
+    assert_eq!(
+        header.attributes(),
+        [Attribute::ScopeAttr("#![allow(dead_code)]".to_string())]
+    );
+
+    let src_code = Module::new("lib")
+        .with_header(Some(header))
+        .with_struct(Struct::new("Foo"))
+        .generate_file();
+    assert!(src_code.starts_with(
+        "// @generated by gen\\r from schema.json\\nfn injected() {} — DO NOT EDIT\n#![allow(dead_code)]\n"
+    ));
+    syn::parse_file(&src_code).unwrap();
+}
@@ -0,0 +1,124 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn validate_identifiers() {
+    assert!(validate_ident("user_id").is_ok());
+    assert!(validate_ident("r#type").is_ok());
+    assert!(validate_ident("名前").is_ok());
+    // Only reserved from the 2021 and 2024 editions onwards
+    assert!(validate_ident("gen").is_ok());
+    assert_eq!(validate_ident(""), Err(IdentError::Empty));
+    assert_eq!(validate_ident("_"), Err(IdentError::Underscore));
+    assert_eq!(
+        validate_ident("match"),
+        Err(IdentError::Keyword("match".to_string()))
+    );
+    assert_eq!(
+        validate_ident("r#self"),
+        Err(IdentError::Keyword("self".to_string()))
+    );
+    assert_eq!(validate_ident("1st"), Err(IdentError::InvalidStart('1')));
+    assert_eq!(
+        validate_ident("content-type"),
+        Err(IdentError::InvalidCharacter('-'))
+    );
+}
+
+#[test]
+fn escape_policies() {
+    let raw = IdentPolicy::raw();
+    assert_eq!(raw.escape("id").ident(), "id");
+    assert_eq!(raw.escape("type").ident(), "r#type");
+    assert_eq!(raw.escape("type").original(), None);
+    assert_eq!(raw.escape("self").ident(), "self_");
+    assert_eq!(raw.escape("self").original(), Some("self"));
+    assert_eq!(raw.escape("content-type").ident(), "content_type");
+
+    let sanitize = IdentPolicy::sanitize();
+    assert_eq!(sanitize.escape("type").ident(), "type_");
+    assert_eq!(sanitize.escape("2fa").ident(), "_2fa");
+    assert_eq!(sanitize.escape("_").ident(), "__");
+    assert_eq!(sanitize.escape("").ident(), "__");
+}
+
+#[test]
+fn invalid_rename_attribute() {
+    let err = IdentPolicy::sanitize()
+        .with_rename_attribute(Some("#[serde(rename = \"{{ name \")]"))
+        .unwrap_err();
+    assert_eq!(err.template(), "#[serde(rename = \"{{ name \")]");
+
+    let err = IdentPolicy::sanitize()
+        .set_rename_attribute(Some("serde(rename = \"{{ name }}\")"))
+        .unwrap_err();
+    assert!(err.to_string().contains("expected an attribute"));
+
+    let mut policy = IdentPolicy::sanitize();
+    assert!(policy
+        .set_rename_attribute(Some("#![allow(unused)]"))
+        .is_err());
+    assert_eq!(policy.rename_attribute(&policy.escape("type")), None);
+}
+
+#[test]
+fn escaped_struct_gen() {
+    let policy = IdentPolicy::sanitize()
+        .with_rename_attribute(Some("#[serde(rename = \"{{ name }}\")]"))
+        .unwrap();
+    let s = Struct::new_escaped("match", &policy)
+        .add_field(Field::new_escaped("type", "String", &policy))
+        .add_field(Field::new_escaped("say \"hi\"", "String", &policy))
+        .add_field(Field::new_escaped("id", "u64", &policy))
+        .to_owned();
+    let expected = r#"
+        struct match_
+        {
+            #[serde(rename = "type")]
+            type_: String,
+            #[serde(rename = "say \"hi\"")]
+            say__hi_: String,
+            id: u64,
+        }
+    "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn escaped_raw_gen() {
+    let policy = IdentPolicy::raw();
+    let e = Enum::new("Token")
+        .add_variant(Variant::new_escaped("Self", &policy))
+        .add_variant(Variant::new_escaped("Move", &policy))
+        .to_owned();
+    let f = Function::new_escaped("loop", &policy)
+        .add_parameter(Parameter::new_escaped("in", "u8", &policy))
+        .to_owned();
+    let t = TypeAlias::new_escaped("where", "u8", &policy);
+    let m = Module::new_escaped("mod", &policy)
+        .add_type_alias(t)
+        .add_enum(e)
+        .add_function(f)
+        .to_owned();
+    let expected = r#"
+        mod r#mod
+        {
+            type r#where = u8;
+            fn r#loop(r#in: u8) -> ()
+            {
+            }
+            enum Token
+            {
+                Self_,
+                Move,
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
@@ -125,7 +125,7 @@ fn impl_with_associated_type_attributes() {
         )
         .add_associated_type(
             AssociatedTypeDefinition::new("BAR", "Foo")
-                .add_attributes(&["#[foo]", "#[bar]"])
+                .add_attributes(["#[foo]", "#[bar]"])
                 .to_owned(),
         )
         .to_owned();
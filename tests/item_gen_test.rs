@@ -0,0 +1,87 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn const_gen() {
+    let c = Const::new("TABLE", "[u8; 3]", "[1, 2, 3]")
+        .set_is_pub(true)
+        .add_doc("/// Lookup table")
+        .add_attribute("#[allow(dead_code)]")
+        .to_owned();
+    let expected = r#"
+        /// Lookup table
+        #[allow(dead_code)]
+        pub const TABLE: [u8; 3] = [1, 2, 3];
+    "#;
+    let src_code = c.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn static_gen() {
+    let s = Static::new("NAME", "&'static str", "\"users\"")
+        .set_is_pub(true)
+        .to_owned();
+    let expected = r#"
+        pub static NAME: &'static str = "users";
+    "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    let s = Static::new("COUNTER", "usize", "0")
+        .set_is_mut(true)
+        .add_doc("/// Mutable counter")
+        .to_owned();
+    let expected = r#"
+        /// Mutable counter
+        static mut COUNTER: usize = 0;
+    "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn type_alias_gen() {
+    let alias = TypeAlias::new("Result", "std::result::Result<T, E>")
+        .set_is_pub(true)
+        .add_generic(Generic::new("T"))
+        .add_generic(Generic::new("E"))
+        .add_attribute("#[allow(dead_code)]")
+        .to_owned();
+    let expected = r#"
+        #[allow(dead_code)]
+        pub type Result<T, E> = std::result::Result<T, E>;
+    "#;
+    let src_code = alias.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn items_in_module() {
+    let m = Module::new("schema")
+        .add_const(Const::new("VERSION", "u32", "2"))
+        .add_static(Static::new("NAME", "&str", "\"schema\""))
+        .add_type_alias(TypeAlias::new("Id", "u64"))
+        .add_struct(Struct::new("User"))
+        .to_owned();
+    let expected = r#"
+        mod schema
+        {
+            const VERSION: u32 = 2;
+            static NAME: &str = "schema";
+            type Id = u64;
+            struct User
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
@@ -0,0 +1,95 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn macro_rules_gen() {
+    let m = MacroRules::new("getter")
+        .set_is_exported(true)
+        .add_doc("/// Generate a getter")
+        .add_rule(MacroRule::new("$name:ident", "getter!($name, String)"))
+        .add_rule(MacroRule::new(
+            "$name:ident, $ty:ty",
+            "fn $name(&self) -> &$ty { &self.$name }",
+        ))
+        .to_owned();
+    let expected = r#"
+        /// Generate a getter
+        #[macro_export]
+        macro_rules! getter
+        {
+            ($name:ident) => { getter!($name, String) };
+            ($name:ident, $ty:ty) => { fn $name(&self) -> &$ty { &self.$name } };
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn macro_invocation_gen() {
+    let m = MacroInvocation::new("lazy_static")
+        .set_tokens("static ref NAMES: Vec<&'static str> = vec![\"a\", \"b\"];")
+        .to_owned();
+    let expected = r#"
+        lazy_static!
+        {
+            static ref NAMES: Vec<&'static str> = vec!["a", "b"];
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    let m = MacroInvocation::new("include")
+        .set_delimiter(MacroDelimiter::Paren)
+        .set_tokens("\"gen.rs\"")
+        .add_attribute("#[cfg(feature = \"gen\")]")
+        .to_owned();
+    let expected = r#"
+        #[cfg(feature = "gen")]
+        include!("gen.rs");
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn macros_in_module() {
+    let m = Module::new("gen")
+        .add_function(Function::new("foo").set_body("answer!()").to_owned())
+        .add_macro(
+            MacroRules::new("answer")
+                .add_rule(MacroRule::new("", "42"))
+                .to_owned(),
+        )
+        .add_macro_invocation(
+            MacroInvocation::new("thread_local")
+                .set_tokens("static DEPTH: usize = 0;")
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        mod gen
+        {
+            macro_rules! answer
+            {
+                () => { 42 };
+            }
+            thread_local!
+            {
+                static DEPTH: usize = 0;
+            }
+            fn foo() -> ()
+            {
+                answer!()
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
@@ -0,0 +1,198 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn test_merge_error_policy() {
+    let first = Module::new("api")
+        .with_struct(Struct::new("User").with_field(Field::new("id", "u64")))
+        .with_const(Const::new("VERSION", "u32", "1"))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_body("0")))
+        .with_submodule(
+            Module::new("models").with_enum(Enum::new("Role").with_variant(Variant::new("Admin"))),
+        );
+    let second = Module::new("api")
+        .with_struct(Struct::new("User").with_field(Field::new("id", "u64")))
+        .with_const(Const::new("VERSION", "u32", "2"))
+        .with_impl(Impl::new("User").with_function(Function::new("name").with_body("1")))
+        .with_submodule(
+            Module::new("models").with_enum(Enum::new("Role").with_variant(Variant::new("Guest"))),
+        );
+
+    let mut m = first.clone();
+    let err = m.merge(second, MergePolicy::Error).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "conflicting items: const `VERSION`, impl `User`, enum `models::Role`"
+    );
+    assert_eq!(err.conflicts()[2].path(), ["models"]);
+    assert_eq!(err.conflicts()[2].kind(), ItemKind::Enum);
+    assert_eq!(m, first);
+}
+
+#[test]
+fn test_merge_namespace_conflicts() {
+    let first = Module::new("api")
+        .with_struct(Struct::new("User"))
+        .with_const(Const::new("login", "u32", "1"))
+        .with_submodule(Module::new("models"));
+    let second = Module::new("api")
+        .with_enum(Enum::new("User"))
+        .with_function(Function::new("login"))
+        .with_trait(Trait::new("models"));
+
+    let mut m = first.clone();
+    let err = m
+        .clone()
+        .merge(second.clone(), MergePolicy::Error)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "conflicting items: enum `User`, trait `models`, fn `login`"
+    );
+    assert!(m
+        .clone()
+        .merge(second.clone(), MergePolicy::MergeImpls)
+        .is_err());
+
+    let report = m.merge(second.clone(), MergePolicy::KeepFirst).unwrap();
+    assert_eq!(report.conflicts().len(), 3);
+    assert_eq!(m, first);
+
+    let mut m = first.clone();
+    m.merge(second.clone(), MergePolicy::Replace).unwrap();
+    assert!(m.get_struct("User").is_none() && m.get_enum("User").is_some());
+    assert!(m.get_const("login").is_none() && m.get_function("login").is_some());
+    assert!(m.get_submodule("models").is_none() && m.get_trait("models").is_some());
+    m.generate_and_verify();
+}
+
+#[test]
+fn test_merge_identical_modules() {
+    let m = Module::new("api")
+        .with_use_statement("use std::fmt;")
+        .with_struct(Struct::new("User").with_field(Field::new("id", "u64")))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_body("0")))
+        .with_submodule(Module::new("models").with_struct(Struct::new("Group")));
+
+    for &policy in &[
+        MergePolicy::Error,
+        MergePolicy::KeepFirst,
+        MergePolicy::Replace,
+        MergePolicy::MergeImpls,
+    ] {
+        let mut merged = m.clone();
+        let report = merged.merge(m.clone(), policy).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(merged, m);
+    }
+}
+
+#[test]
+fn test_merge_keep_first() {
+    let mut m = Module::new("api")
+        .with_use_statement("use std::fmt;")
+        .with_const(Const::new("VERSION", "u32", "1"))
+        .with_submodule(
+            Module::new("models").with_enum(Enum::new("Role").with_variant(Variant::new("Admin"))),
+        );
+    let other = Module::new("api")
+        .with_use_statement("use std::fmt;")
+        .with_use_statement("use std::io;")
+        .with_const(Const::new("VERSION", "u32", "2"))
+        .with_submodule(
+            Module::new("models")
+                .with_enum(Enum::new("Role").with_variant(Variant::new("Guest")))
+                .with_struct(Struct::new("Group")),
+        )
+        .with_submodule(Module::new("client"));
+
+    let report = m.merge(other, MergePolicy::KeepFirst).unwrap();
+    assert_eq!(report.conflicts().len(), 2);
+    assert_eq!(m.get_const("VERSION").unwrap().value(), "1");
+    assert_eq!(m.use_statements(), ["use std::fmt;", "use std::io;"]);
+    let models = m.get_submodule("models").unwrap();
+    assert!(models.get_struct("Group").is_some());
+    assert!(models
+        .get_enum("Role")
+        .unwrap()
+        .get_variant("Admin")
+        .is_some());
+    assert!(m.get_submodule("client").is_some());
+    m.generate_and_verify();
+}
+
+#[test]
+fn test_merge_replace() {
+    let mut m = Module::new("api")
+        .with_const(Const::new("VERSION", "u32", "1"))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_body("0")));
+    let other = Module::new("api")
+        .with_const(Const::new("VERSION", "u32", "2"))
+        .with_impl(Impl::new("User").with_function(Function::new("name").with_body("1")));
+
+    let report = m.merge(other, MergePolicy::Replace).unwrap();
+    assert_eq!(report.conflicts().len(), 2);
+    assert_eq!(m.get_const("VERSION").unwrap().value(), "2");
+    assert!(m.impls()[0].get_function("name").is_some());
+    assert!(m.impls()[0].get_function("id").is_none());
+}
+
+#[test]
+fn test_merge_impls_policy() {
+    let mut m = Module::new("api")
+        .with_struct(Struct::new("User").with_field(Field::new("id", "u64")))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_body("0")));
+    let other = Module::new("api")
+        .with_impl(Impl::new("User").with_function(Function::new("name").with_body("1")));
+
+    let report = m.merge(other, MergePolicy::MergeImpls).unwrap();
+    assert_eq!(report.conflicts().len(), 1);
+    assert_eq!(report.conflicts()[0].to_string(), "impl `User`");
+    let ipl = &m.impls()[0];
+    assert!(ipl.get_function("id").is_some() && ipl.get_function("name").is_some());
+    m.generate_and_verify();
+
+    let other = Module::new("api")
+        .with_const(Const::new("VERSION", "u32", "1"))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_body("1")));
+    let err = m
+        .clone()
+        .with_const(Const::new("VERSION", "u32", "2"))
+        .merge(other, MergePolicy::MergeImpls)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "conflicting items: const `VERSION`, fn `User::id`"
+    );
+}
+
+#[test]
+fn test_merge_duplicate_impls() {
+    let m = Module::new("api")
+        .with_struct(Struct::new("User"))
+        .with_impl(Impl::new("User").with_function(Function::new("a")))
+        .with_impl(Impl::new("User").with_function(Function::new("b")))
+        .with_impl(
+            Impl::new("Wrapper")
+                .with_generic(Generic::new("T"))
+                .with_function(Function::new("a")),
+        )
+        .with_impl(Impl::new("Wrapper").with_function(Function::new("b")));
+
+    let mut merged = m.clone();
+    let report = merged.merge(m.clone(), MergePolicy::Error).unwrap();
+    assert!(report.is_empty());
+    assert_eq!(merged, m);
+
+    let mut merged = m.clone();
+    merged.merge(m.clone(), MergePolicy::MergeImpls).unwrap();
+    assert_eq!(merged, m);
+
+    let other = Module::new("api")
+        .with_impl(Impl::new("User").with_function(Function::new("a")))
+        .with_impl(Impl::new("User").with_function(Function::new("c")));
+    let err = m.clone().merge(other, MergePolicy::Error).unwrap_err();
+    assert_eq!(err.to_string(), "conflicting items: impl `User`");
+}
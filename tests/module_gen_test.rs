@@ -121,3 +121,204 @@ fn test_module_submodule() {
     println!("{}", &src_code);
     assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
 }
+
+#[test]
+fn test_module_default_derives() {
+    let m = Module::new("models")
+        .add_default_derives(vec!["Debug", "Clone"])
+        .add_struct(Struct::new("User").add_derive("Hash").to_owned())
+        .add_enum(
+            Enum::new("Role")
+                .add_attribute("#[repr(u8)]")
+                .add_derive("Clone")
+                .add_variant(Variant::new("Admin"))
+                .to_owned(),
+        )
+        .add_union(
+            Union::new("Raw")
+                .add_derive("Copy")
+                .add_field(Field::new("a", "u8"))
+                .to_owned(),
+        )
+        .add_submodule(
+            Module::new("nested")
+                .add_struct(Struct::new("Plain"))
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        mod models
+        {
+            #[derive(Debug, Clone, Hash)]
+            struct User
+            {
+            }
+            #[repr(u8)]
+            #[derive(Debug, Clone)]
+            enum Role
+            {
+                Admin,
+            }
+            #[derive(Debug, Clone, Copy)]
+            union Raw
+            {
+                a: u8,
+            }
+            mod nested
+            {
+                struct Plain
+                {
+                }
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn test_module_inner_docs() {
+    let m = Module::new("docs")
+        .add_doc("Inner documentation\nover two lines")
+        .add_doc_example("docs::run();")
+        .add_struct(Struct::new("Foo").add_doc("Outer docs").to_owned())
+        .to_owned();
+    let expected = r#"
+        mod docs
+        {
+            //! Inner documentation
+            //! over two lines
+            //!
+            //! # Examples
+            //!
+            //! ```
+            //! docs::run();
+            //! ```
+            /// Outer docs
+            struct Foo
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn test_module_restyles_doc_lines() {
+    let m = Module::new("docs")
+        .add_doc("/// Inner documentation")
+        .add_doc("#[doc = \" from an attribute\"]")
+        .add_struct(Struct::new("Foo").add_doc("//! Outer docs").to_owned())
+        .to_owned();
+    let expected = r#"
+        mod docs
+        {
+            //! Inner documentation
+            #![doc = " from an attribute"]
+            /// Outer docs
+            struct Foo
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn test_module_equality_and_hash() {
+    use std::collections::HashSet;
+
+    let a = Module::new("a");
+    let b = Module::new("b").add_struct(Struct::new("Foo")).to_owned();
+
+    let first = Module::new("root")
+        .add_submodule(a.clone())
+        .add_submodule(b.clone())
+        .to_owned();
+    let second = Module::new("root")
+        .add_submodule(b.clone())
+        .add_submodule(a.clone())
+        .to_owned();
+    assert_eq!(first, second);
+
+    let mut set = HashSet::new();
+    set.insert(first);
+    set.insert(second);
+    for _ in 0..16 {
+        let m = Module::new("root")
+            .add_submodule(a.clone())
+            .add_submodule(b.clone())
+            .to_owned();
+        set.insert(m);
+    }
+    assert_eq!(set.len(), 1);
+
+    let changed = Module::new("root")
+        .add_submodule(a)
+        .add_submodule(Module::new("b").add_struct(Struct::new("Bar")).to_owned())
+        .to_owned();
+    assert!(!set.contains(&changed));
+
+    assert_eq!(Field::new("id", "u64"), Field::new("id", "u64"));
+    assert_ne!(
+        Field::new("id", "u64"),
+        Field::new("id", "u64").set_is_pub(true).to_owned()
+    );
+}
+
+#[test]
+fn test_module_by_value_builders() {
+    let by_ref = Module::new("models")
+        .set_is_pub(true)
+        .add_use_statement("use std::fmt;")
+        .add_struct(
+            Struct::new("User")
+                .add_derive("Debug")
+                .add_derive("Clone")
+                .remove_derive("Clone")
+                .add_doc("A user")
+                .add_field(Field::new("id", "u64").set_is_pub(true).to_owned())
+                .to_owned(),
+        )
+        .add_function(
+            Function::new("user")
+                .add_parameter(Parameter::new("id", "u64"))
+                .set_return_ty("User")
+                .set_body("User { id }")
+                .to_owned(),
+        )
+        .add_submodule(
+            Module::new("nested")
+                .add_doc("Nested")
+                .clear_docs()
+                .to_owned(),
+        )
+        .to_owned();
+
+    let by_value = Module::new("models")
+        .with_is_pub(true)
+        .with_use_statement("use std::fmt;")
+        .with_struct(
+            Struct::new("User")
+                .with_derive("Debug")
+                .with_derive("Clone")
+                .without_derive("Clone")
+                .with_doc("A user")
+                .with_field(Field::new("id", "u64").with_is_pub(true)),
+        )
+        .with_function(
+            Function::new("user")
+                .with_parameter(Parameter::new("id", "u64"))
+                .with_return_ty("User")
+                .with_body("User { id }"),
+        )
+        .with_submodule(Module::new("nested").with_doc("Nested").without_docs());
+
+    assert_eq!(by_ref, by_value);
+    assert_eq!(by_ref.generate(), by_value.generate_and_verify());
+}
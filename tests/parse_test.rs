@@ -0,0 +1,320 @@
+#![cfg(feature = "parse")]
+
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+use std::convert::TryFrom;
+
+#[test]
+fn struct_from_syn() {
+    let item: syn::ItemStruct = syn::parse_str(
+        r#"
+        /// A user
+        #[derive(Debug, Clone)]
+        pub struct User<'a, T: Clone> where T: Default {
+            /// Identifier
+            pub id: u64,
+            #[serde(rename = "user name")]
+            name: &'a str,
+            extra: Option<Vec<T>>,
+        }
+        "#,
+    )
+    .unwrap();
+    let s = Struct::try_from(&item)
+        .unwrap()
+        .add_field(Field::new("age", "u8"))
+        .to_owned();
+    let expected = r#"
+        /// A user
+        #[derive(Debug, Clone)]
+        pub struct User<'a, T>
+            where
+                'a: ,
+                T: Clone + Default,
+        {
+            /// Identifier
+            pub id: u64,
+            #[serde(rename = "user name")]
+            name: &'a str,
+            extra: Option<Vec<T>>,
+            age: u8,
+        }
+    "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn enum_from_syn() {
+    let item: syn::ItemEnum = syn::parse_str(
+        r#"
+        pub enum Shape {
+            /// Nothing
+            Empty,
+            Circle(f64),
+            Rect { w: f64, h: f64 },
+            Other = 10,
+        }
+        "#,
+    )
+    .unwrap();
+    let e = Enum::try_from(&item).unwrap();
+    let expected = r#"
+        pub enum Shape
+        {
+            /// Nothing
+            Empty,
+            Circle(f64),
+            Rect { w: f64, h: f64 },
+            Other = 10,
+        }
+    "#;
+    let src_code = e.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn impl_and_trait_from_syn() {
+    let item: syn::ItemImpl = syn::parse_str(
+        r#"
+        impl<T> Container for Wrapper<T> where T: Clone {
+            type Item = T;
+            fn get(&self) -> T {
+                self.0.clone()
+            }
+        }
+        "#,
+    )
+    .unwrap();
+    let ipl = Impl::try_from(&item).unwrap();
+    let expected = r#"
+        impl<T> Container for Wrapper<T>
+            where
+                T: Clone,
+        {
+            type Item = T;
+            fn get(self: &Self) -> T
+            {
+                self . 0 . clone ()
+            }
+        }
+    "#;
+    let src_code = ipl.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    let item: syn::ItemTrait = syn::parse_str(
+        r#"
+        pub trait Container {
+            type Item: Clone;
+            fn get(&self) -> Self::Item;
+            fn cloned(&self) -> Self::Item { self.get() }
+        }
+        "#,
+    )
+    .unwrap();
+    let tr8t = Trait::try_from(&item).unwrap();
+    let expected = r#"
+        pub trait Container
+        {
+            type Item: Clone;
+            fn get(self: &Self) -> Self::Item;
+            fn cloned(self: &Self) -> Self::Item
+            {
+                self . get ()
+            }
+        }
+    "#;
+    let src_code = tr8t.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn documented_members_from_syn() {
+    let file: syn::File = syn::parse_str(
+        r#"
+        pub enum Event {
+            Joined {
+                /// When the user joined
+                #[serde(default)]
+                at: u64,
+            },
+        }
+
+        pub trait Named {
+            /// The name type
+            type Name: ToString;
+        }
+
+        impl Named for Event {
+            /// Always a string
+            type Name = String;
+        }
+        "#,
+    )
+    .unwrap();
+    let (mut m, unsupported) = Module::from_file(&file);
+    assert!(unsupported.is_empty());
+    m.set_name("events");
+
+    let variant = m.get_enum("Event").unwrap().get_variant("Joined").unwrap();
+    assert_eq!(
+        variant.inner(),
+        Some(" { /// When the user joined\n#[serde(default)] at: u64 }")
+    );
+    assert_eq!(
+        m.get_trait("Named").unwrap().associated_types()[0].docs(),
+        ["/// The name type"]
+    );
+    assert_eq!(
+        m.impls()[0].associated_types()[0].docs(),
+        ["/// Always a string"]
+    );
+
+    // Rendering and parsing again gives the same module
+    let reparsed: syn::ItemMod = syn::parse_str(&m.generate_and_verify()).unwrap();
+    assert_eq!(Module::try_from(&reparsed).unwrap(), m);
+
+    let item: syn::ItemFn = syn::parse_str("fn f(/// Doc\n a: u8) {}").unwrap();
+    let err = Function::try_from(&item).unwrap_err();
+    assert_eq!(err.item(), "f");
+}
+
+#[test]
+fn unsupported_items() {
+    let item: syn::ItemStruct = syn::parse_str("pub struct Id(u64);").unwrap();
+    let err = Struct::try_from(&item).err().unwrap();
+    assert_eq!(err.item(), "Id");
+
+    let item: syn::ItemFn = syn::parse_str("pub(crate) fn foo() {}").unwrap();
+    assert!(Function::try_from(&item).is_err());
+}
+
+#[test]
+fn module_from_file() {
+    let file = syn::parse_file(
+        r#"
+        //! Generated schema
+        #![allow(dead_code)]
+        use std::collections::HashMap;
+
+        macro_rules! square {
+            ($x:expr) => { $x * $x };
+        }
+
+        pub struct Id(u64);
+
+        pub const VERSION: u32 = 1;
+
+        pub mod nested {
+            pub fn answer() -> u32 { 42 }
+        }
+        "#,
+    )
+    .unwrap();
+    let (mut m, unsupported) = Module::from_file(&file);
+    assert_eq!(unsupported.len(), 1);
+    assert_eq!(unsupported[0].item(), "Id");
+    assert_eq!(m.raw_items(), ["pub struct Id (u64) ;"]);
+    m.set_name("schema");
+    let expected = r#"
+        mod schema
+        {
+            #![allow(dead_code)]
+            //! Generated schema
+            use std::collections::HashMap;
+            macro_rules! square
+            {
+                ($ x : expr) => { $ x * $ x };
+            }
+            pub struct Id (u64) ;
+            pub const VERSION: u32 = 1;
+            pub mod nested
+            {
+                pub fn answer() -> u32
+                {
+                    42
+                }
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn raw_items_precede_the_next_item_from_file() {
+    let file = syn::parse_file(
+        r#"
+        struct B {}
+        pub struct Id(u64);
+        fn a() {}
+        pub struct Pair(u8, u8);
+        struct C {}
+        "#,
+    )
+    .unwrap();
+    let (mut m, unsupported) = Module::from_file(&file);
+    assert_eq!(unsupported.len(), 2);
+    m.set_name("raw");
+    let expected = r#"
+        mod raw
+        {
+            pub struct Id (u64) ;
+            fn a() -> ()
+            {
+            }
+            struct B
+            {
+            }
+            pub struct Pair (u8 , u8) ;
+            struct C
+            {
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn char_literals_and_multi_line_docs_from_syn() {
+    let item: syn::ItemStruct = syn::parse_str(
+        r#"
+        #[doc = "First\nsecond"]
+        #[doc = "carriage\rreturn"]
+        #[csv(quote = '"', escape = '\'', sep = ',')]
+        pub struct Row<'a> {
+            cell: &'a str,
+        }
+        "#,
+    )
+    .unwrap();
+    let s = Struct::try_from(&item).unwrap();
+    assert_eq!(
+        s.docs(),
+        ["///First", "///second", "#[doc = \"carriage\\rreturn\"]"]
+    );
+    let expected = r#"
+        ///First
+        ///second
+        #[doc = "carriage\rreturn"]
+        #[csv(quote = '"', escape = '\'', sep = ',')]
+        pub struct Row<'a>
+            where
+                'a: ,
+        {
+            cell: &'a str,
+        }
+    "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
@@ -0,0 +1,141 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn test_recursion_across_modules() {
+    let mut m = Module::new("schema")
+        .with_use_statement("use self::messages::Person;")
+        .with_struct(
+            Struct::new("Company")
+                .with_field(Field::new("ceo", "Person"))
+                .with_field(Field::new("staff", "Vec<Person>")),
+        )
+        .with_submodule(
+            Module::new("messages")
+                .with_is_pub(true)
+                .with_use_statement("use super::*;")
+                .with_struct(
+                    Struct::new("Person")
+                        .with_is_pub(true)
+                        .with_field(Field::new("employer", "Option<Company>"))
+                        .with_field(Field::new("parents", "(crate::messages::Person, Person)")),
+                ),
+        );
+    let original = m.clone();
+
+    let cycles = m.recursive_types();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].types(), ["Company", "messages::Person"]);
+    assert_eq!(
+        cycles[0].to_string(),
+        "recursive types `Company`, `messages::Person`"
+    );
+
+    let edges = m.recursive_edges();
+    assert_eq!(m, original);
+    assert_eq!(m.box_recursive_types(), edges);
+    assert_eq!(
+        edges
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+        vec![
+            "field `messages::Person::employer` holds `Company` by value",
+            "field `messages::Person::parents` holds `messages::Person` by value",
+        ]
+    );
+    assert_eq!(edges[0].path(), ["messages"]);
+    assert_eq!(edges[0].kind(), ItemKind::Field);
+    assert_eq!(edges[0].target(), "Company");
+
+    let person = m
+        .get_submodule("messages")
+        .and_then(|msgs| msgs.get_struct("Person"))
+        .unwrap();
+    assert_eq!(
+        person.get_field("employer").unwrap().ty(),
+        "Option<Box<Company>>"
+    );
+    assert_eq!(
+        person.get_field("parents").unwrap().ty(),
+        "(Box<crate::messages::Person>, Box<Person>)"
+    );
+    assert!(m.recursive_types().is_empty());
+    m.generate_and_verify();
+}
+
+#[test]
+fn test_recursion_through_indirections() {
+    let m = Module::new("tree")
+        .with_struct(
+            Struct::new("Tree")
+                .with_generic(Generic::new("Leaf"))
+                .with_field(Field::new("children", "Vec<Tree<Leaf>>"))
+                .with_field(Field::new("parent", "Option<std::rc::Weak<Tree<Leaf>>>"))
+                .with_field(Field::new("index", "HashMap<String, Box<Tree<Leaf>>>"))
+                .with_field(Field::new("root", "&'static Tree<Leaf>"))
+                .with_field(Field::new("visit", "fn(Tree<Leaf>) -> Tree<Leaf>"))
+                .with_field(Field::new("leaf", "Leaf")),
+        )
+        .with_struct(Struct::new("Leaf").with_field(Field::new("tree", "Tree<u8>")));
+
+    assert!(m.recursive_types().is_empty());
+    assert!(m.recursive_edges().is_empty());
+}
+
+#[test]
+fn test_recursion_through_variants() {
+    let mut m = Module::new("json").with_enum(
+        Enum::new("Value")
+            .with_variant(Variant::new("Null"))
+            .with_variant(Variant::new("Array").with_inner(Some("(Vec<Value>)")))
+            .with_variant(
+                Variant::new("Pair").with_inner(Some("{ key: String, value: [Value; 1] }")),
+            ),
+    );
+
+    let boxed = m.box_recursive_types();
+    assert_eq!(boxed.len(), 1);
+    assert_eq!(boxed[0].kind(), ItemKind::Variant);
+    assert_eq!(boxed[0].name(), "Value::Pair");
+    assert_eq!(
+        m.get_enum("Value")
+            .and_then(|e| e.get_variant("Pair"))
+            .and_then(Variant::inner),
+        Some("{ key: String, value: [Box<Value>; 1] }")
+    );
+    m.generate_and_verify();
+}
+
+#[test]
+fn test_recursion_boxes_fewest_edges() {
+    let mut m = Module::new("graph")
+        .with_struct(Struct::new("A").with_field(Field::new("b", "B")))
+        .with_struct(
+            Struct::new("B")
+                .with_field(Field::new("a", "A"))
+                .with_field(Field::new("c", "C")),
+        )
+        .with_struct(
+            Struct::new("C")
+                .with_field(Field::new("a", "A"))
+                .with_field(Field::new("b", "B")),
+        );
+
+    assert_eq!(m.recursive_types()[0].types(), ["A", "B", "C"]);
+    let boxed = m.box_recursive_types();
+    assert_eq!(
+        boxed
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+        vec![
+            "field `A::b` holds `B` by value",
+            "field `B::c` holds `C` by value",
+        ]
+    );
+    assert!(m.recursive_types().is_empty());
+    m.generate_and_verify();
+}
@@ -0,0 +1,159 @@
+use std::fs;
+
+use proffer::*;
+
+#[test]
+fn replace_regions_keeps_hand_written_code() {
+    let existing = "\
+use std::fmt;
+
+// proffer:begin a
+old a
+// proffer:end a
+
+fn hand_written() {}
+
+    // proffer:begin b
+    old b
+    // proffer:end b
+";
+    let src_code = replace_regions(
+        existing,
+        &[("a", "new a\n".to_string()), ("b", "    new b".to_string())],
+    )
+    .unwrap();
+    let expected = "\
+use std::fmt;
+
+// proffer:begin a
+new a
+// proffer:end a
+
+fn hand_written() {}
+
+    // proffer:begin b
+    new b
+    // proffer:end b
+";
+    assert_eq!(src_code, expected);
+
+    // Regions which aren't regenerated are kept.
+    let src_code = replace_regions(existing, &[("b", "new b".to_string())]).unwrap();
+    assert!(src_code.contains("old a"));
+    assert!(!src_code.contains("old b"));
+}
+
+#[test]
+fn replace_regions_errors() {
+    let regions = [("a", String::new())];
+    let cases = [
+        (
+            "// proffer:begin b\n// proffer:end b\n",
+            "region `a` not found",
+        ),
+        (
+            "// proffer:begin a\n// proffer:end a\n// proffer:begin a\n// proffer:end a\n",
+            "line 3: region `a` is opened twice",
+        ),
+        (
+            "// proffer:begin a\n// proffer:begin b\n// proffer:end b\n// proffer:end a\n",
+            "line 2: region `b` is opened within region `a`",
+        ),
+        (
+            "x\n// proffer:begin a\n",
+            "line 2: region `a` is never closed",
+        ),
+        ("// proffer:end a\n", "line 1: unexpected end of region `a`"),
+        (
+            "// proffer:begin a\n// proffer:end b\n",
+            "line 2: unexpected end of region `b`",
+        ),
+        (
+            "// proffer:begin a\n// proffer:end\n",
+            "line 2: marker without a region id",
+        ),
+        (
+            "x\n  // proffer:begin   \n// proffer:end a\n",
+            "line 2: marker without a region id",
+        ),
+    ];
+    for (src, message) in cases.iter() {
+        let err = replace_regions(src, &regions).unwrap_err();
+        assert_eq!(&err.to_string(), message);
+    }
+
+    let src = "// proffer:begin a\n// proffer:end a\n";
+    let err = replace_regions(src, &[("a", "x".to_string()), ("a", "y".to_string())]).unwrap_err();
+    assert_eq!(err.to_string(), "region `a` is given more than once");
+}
+
+#[test]
+fn regenerate_into_file() {
+    let path = std::env::temp_dir().join(format!("proffer_region_{}.rs", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    let module = Module::new("models")
+        .add_struct(Struct::new("User"))
+        .to_owned();
+    regenerate_into(&path, &module).unwrap();
+    let created = fs::read_to_string(&path).unwrap();
+    assert!(created.starts_with("// proffer:begin models\n"));
+    assert!(created.ends_with("// proffer:end models\n"));
+
+    let edited = format!("// hand-written header\n{}\nimpl User {{}}\n", created);
+    fs::write(&path, &edited).unwrap();
+    let module = Module::new("models")
+        .add_struct(Struct::new("Account"))
+        .to_owned();
+    regenerate_into(&path, &module).unwrap();
+    let regenerated = fs::read_to_string(&path).unwrap();
+    assert!(regenerated.starts_with("// hand-written header\n"));
+    assert!(regenerated.ends_with("\nimpl User {}\n"));
+    assert!(regenerated.contains("struct Account"));
+    assert!(!regenerated.contains("struct User"));
+
+    // A failing regeneration leaves the file untouched.
+    let other = Module::new("other");
+    assert!(matches!(
+        regenerate_into(&path, &other),
+        Err(RegionError::Missing(_))
+    ));
+    assert_eq!(fs::read_to_string(&path).unwrap(), regenerated);
+    assert!(matches!(
+        regenerate_into(&path, &Module::new("")),
+        Err(RegionError::InvalidId(_))
+    ));
+    assert_eq!(fs::read_to_string(&path).unwrap(), regenerated);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn regenerate_into_skips_file_level_parts() {
+    let path =
+        std::env::temp_dir().join(format!("proffer_region_header_{}.rs", std::process::id()));
+    fs::write(
+        &path,
+        "use std::fmt;\n\n// proffer:begin models\n// proffer:end models\n",
+    )
+    .unwrap();
+
+    let module = Module::new("models")
+        .with_header(Some(FileHeader::new("gen").with_hashed(true)))
+        .with_attribute("#![allow(dead_code)]")
+        .with_doc("Models")
+        .with_struct(Struct::new("User"));
+    regenerate_into(&path, &module).unwrap();
+    let regenerated = fs::read_to_string(&path).unwrap();
+    println!("{}", &regenerated);
+    assert!(regenerated.starts_with("use std::fmt;\n"));
+    assert!(regenerated.contains("struct User"));
+    assert!(!regenerated.contains("@generated"));
+    assert!(!regenerated.contains("#!["));
+    assert!(!regenerated.contains("//!"));
+    syn::parse_file(&regenerated).unwrap();
+
+    let mut tmp_path = path.clone().into_os_string();
+    tmp_path.push(".proffer-tmp");
+    assert!(!std::path::Path::new(&tmp_path).exists());
+    fs::remove_file(&path).unwrap();
+}
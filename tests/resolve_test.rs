@@ -0,0 +1,155 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn test_resolve_valid_module() {
+    let m = Module::new("api")
+        .with_use_statement("use std::collections::{HashMap, hash_map::Entry as MapEntry};")
+        .with_use_statement("use self::models::*;")
+        .with_struct(
+            Struct::new("Cache")
+                .with_generic(Generic::new("K"))
+                .with_field(Field::new("entries", "HashMap<K, Vec<Group>>"))
+                .with_field(Field::new("role", "Option<crate::models::Role>"))
+                .with_field(Field::new("count", "&'static [u8; 4]")),
+        )
+        .with_enum(
+            Enum::new("Event")
+                .with_variant(
+                    Variant::new("Joined").with_inner(Some("{ user: models::User, at: u64 }")),
+                )
+                .with_variant(
+                    Variant::new("Other").with_inner(Some("(Box<dyn Iterator<Item = Role>>)")),
+                ),
+        )
+        .with_impl(
+            Impl::new("Cache")
+                .with_generic(Generic::new("K"))
+                .with_function(
+                    Function::new("entry")
+                        .with_parameter(Parameter::new("key", "K"))
+                        .with_return_ty("Option<MapEntry<'_, K, Vec<Group>>>")
+                        .with_body("None"),
+                ),
+        )
+        .with_submodule(
+            Module::new("models")
+                .with_is_pub(true)
+                .with_struct(
+                    Struct::new("User")
+                        .with_is_pub(true)
+                        .with_field(Field::new("group", "super::models::Group")),
+                )
+                .with_struct(Struct::new("Group").with_is_pub(true))
+                .with_enum(Enum::new("Role").with_is_pub(true)),
+        );
+    m.generate_and_verify();
+    assert_eq!(TypeResolver::new().validate(&m), Ok(()));
+}
+
+#[test]
+fn test_resolve_reports_unresolved_types() {
+    let m = Module::new("api")
+        .with_use_statement("use self::models::*;")
+        .with_function(
+            Function::new("login")
+                .with_parameter(Parameter::new("user", "&models::Usr"))
+                .with_return_ty("Result<Session, AuthError>"),
+        )
+        .with_impl(Impl::new("Sesion").with_impl_trait(Some(Trait::new("Named"))))
+        .with_submodule(
+            Module::new("auth")
+                .with_struct(Struct::new("Token").with_field(Field::new("owner", "User"))),
+        )
+        .with_submodule(Module::new("models").with_struct(Struct::new("User").with_is_pub(true)));
+
+    let err = TypeResolver::new().validate(&m).unwrap_err();
+    assert_eq!(
+        err.unresolved()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<String>>(),
+        vec![
+            "`models::Usr` in parameter `user` of fn `login`",
+            "`Session` in return type of fn `login`",
+            "`AuthError` in return type of fn `login`",
+            "`Sesion` in impl `Named for Sesion`",
+            "`Named` in impl `Named for Sesion`",
+            "`User` in field `auth::Token::owner`",
+        ]
+    );
+    assert_eq!(err.unresolved()[5].path(), ["auth"]);
+    assert_eq!(err.unresolved()[5].name(), "User");
+
+    let resolver = TypeResolver::new()
+        .with_known_types(vec!["Session", "auth_lib"])
+        .with_known_type("Named");
+    let m = m
+        .with_function(Function::new("token").with_return_ty("auth_lib::Token"))
+        .with_function(
+            Function::new("check").with_parameter(Parameter::new("t", "auth_lib::Token")),
+        );
+    assert_eq!(resolver.validate(&m).unwrap_err().unresolved().len(), 4);
+}
+
+#[test]
+fn test_resolve_empty_resolver() {
+    let m = Module::new("api").with_struct(Struct::new("User").with_field(Field::new("id", "u64")));
+    assert!(TypeResolver::empty().validate(&m).is_err());
+    assert!(TypeResolver::empty()
+        .with_known_type("u64")
+        .validate(&m)
+        .is_ok());
+}
+
+#[test]
+fn test_resolve_qualified_paths() {
+    let m = Module::new("api").with_struct(
+        Struct::new("Buffer")
+            .with_field(Field::new("iter", "<Vec<u8> as IntoIterator>::IntoIter"))
+            .with_field(Field::new("chunks", "[[u8; 4]; 16]"))
+            .with_field(Field::new("visit", "fn(&Buffer) -> Option<Chunk>")),
+    );
+
+    let err = TypeResolver::new().validate(&m).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unresolved types: `Chunk` in field `Buffer::visit`"
+    );
+}
+
+#[test]
+fn test_resolve_imports_within_the_tree() {
+    let m = Module::new("api")
+        .with_use_statement("use self::models::Usr;")
+        .with_use_statement("use crate::models::User as Person;")
+        .with_use_statement("use serde::Serialize;")
+        .with_struct(
+            Struct::new("Session")
+                .with_field(Field::new("user", "Usr"))
+                .with_field(Field::new("person", "Person"))
+                .with_field(Field::new("format", "Serialize")),
+        )
+        .with_submodule(
+            Module::new("auth")
+                .with_use_statement("use super::models::{Group, Role};")
+                .with_struct(
+                    Struct::new("Token")
+                        .with_field(Field::new("group", "Group"))
+                        .with_field(Field::new("role", "Role")),
+                ),
+        )
+        .with_submodule(
+            Module::new("models")
+                .with_struct(Struct::new("User").with_is_pub(true))
+                .with_struct(Struct::new("Group").with_is_pub(true)),
+        );
+
+    let err = TypeResolver::new().validate(&m).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unresolved types: `Usr` in field `Session::user`, `Role` in field `auth::Token::role`"
+    );
+}
@@ -0,0 +1,238 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn test_semver_identical_modules() {
+    let m = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_is_pub(true)
+                .with_field(Field::new("id", "u64").with_is_pub(true)),
+        )
+        .with_impl(Impl::new("User").with_function(Function::new("a").with_is_pub(true)))
+        .with_impl(Impl::new("User").with_function(Function::new("b").with_is_pub(true)));
+
+    let report = m.semver_check(&m.clone());
+    assert!(report.changes().is_empty());
+    assert_eq!(report.bump(), Bump::Patch);
+    assert!(report.check(Bump::Patch).is_ok());
+}
+
+#[test]
+fn test_semver_private_changes_are_patches() {
+    let old = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_is_pub(true)
+                .with_field(Field::new("id", "u64").with_is_pub(true))
+                .with_field(Field::new("secret", "String")),
+        )
+        .with_function(
+            Function::new("login")
+                .with_is_pub(true)
+                .with_parameter(Parameter::new("user", "&User")),
+        )
+        .with_function(Function::new("hash").with_parameter(Parameter::new("s", "&str")))
+        .with_submodule(
+            Module::new("internal").with_struct(Struct::new("Cache").with_is_pub(true)),
+        );
+    let new = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_is_pub(true)
+                .with_field(Field::new("id", "u64").with_is_pub(true)),
+        )
+        .with_function(
+            Function::new("login")
+                .with_is_pub(true)
+                .with_parameter(Parameter::new("user", "&User"))
+                .with_body("todo!()"),
+        )
+        .with_function(Function::new("hash").with_parameter(Parameter::new("s", "String")))
+        .with_submodule(Module::new("internal"));
+
+    let report = old.semver_check(&new);
+    assert_eq!(report.changes().len(), 4);
+    assert_eq!(report.bump(), Bump::Patch);
+    assert!(!report.is_breaking());
+    assert!(report.check(Bump::Patch).is_ok());
+}
+
+#[test]
+fn test_semver_breaking_changes() {
+    let old = Module::new("api")
+        .with_struct(
+            Struct::new("User")
+                .with_is_pub(true)
+                .with_field(Field::new("id", "u64").with_is_pub(true)),
+        )
+        .with_struct(
+            Struct::new("Point")
+                .with_is_pub(true)
+                .with_field(Field::new("x", "i32").with_is_pub(true)),
+        )
+        .with_enum(
+            Enum::new("Role")
+                .with_is_pub(true)
+                .with_variant(Variant::new("Admin")),
+        )
+        .with_trait(
+            Trait::new("Named")
+                .with_is_pub(true)
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String"))),
+        )
+        .with_function(
+            Function::new("login")
+                .with_is_pub(true)
+                .with_parameter(Parameter::new("user", "&User")),
+        );
+    let new = Module::new("api")
+        .with_struct(Struct::new("User").with_is_pub(true))
+        .with_struct(
+            Struct::new("Point")
+                .with_is_pub(true)
+                .with_field(Field::new("x", "i32").with_is_pub(true))
+                .with_field(Field::new("y", "i32").with_is_pub(true)),
+        )
+        .with_enum(
+            Enum::new("Role")
+                .with_is_pub(true)
+                .with_variant(Variant::new("Admin"))
+                .with_variant(Variant::new("Guest")),
+        )
+        .with_trait(
+            Trait::new("Named")
+                .with_is_pub(true)
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String")))
+                .with_signature(FunctionSignature::new("id").with_return_ty(Some("u64"))),
+        )
+        .with_function(
+            Function::new("login")
+                .with_is_pub(true)
+                .with_parameter(Parameter::new("user", "&User"))
+                .with_parameter(Parameter::new("password", "&str")),
+        );
+    new.generate_and_verify();
+
+    let report = old.semver_check(&new);
+    assert_eq!(
+        report.to_string(),
+        "\
+major: added fn `Named::id`
+major: fn `login` signature changed from `fn login(user: &User)` to `fn login(user: &User, password: &str)`
+major: removed field `User::id`
+major: added field `Point::y`
+major: added variant `Role::Guest`
+"
+    );
+    assert_eq!(report.breaking().count(), 5);
+
+    let err = report.check(Bump::Minor).unwrap_err();
+    assert_eq!(err.bump(), Bump::Major);
+    assert_eq!(err.changes().len(), 5);
+}
+
+#[test]
+fn test_semver_additions_are_minor() {
+    let old = Module::new("api")
+        .with_enum(
+            Enum::new("Role")
+                .with_is_pub(true)
+                .with_attribute(Attribute::from("#[non_exhaustive]"))
+                .with_variant(Variant::new("Admin")),
+        )
+        .with_trait(
+            Trait::new("Named")
+                .with_is_pub(true)
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String"))),
+        );
+    let new = Module::new("api")
+        .with_const(Const::new("VERSION", "u32", "2").with_is_pub(true))
+        .with_enum(
+            Enum::new("Role")
+                .with_is_pub(true)
+                .with_attribute(Attribute::from("#[non_exhaustive]"))
+                .with_variant(Variant::new("Admin"))
+                .with_variant(Variant::new("Guest")),
+        )
+        .with_trait(
+            Trait::new("Named")
+                .with_is_pub(true)
+                .with_signature(FunctionSignature::new("name").with_return_ty(Some("String")))
+                .with_function(
+                    Function::new("greet")
+                        .with_return_ty("String")
+                        .with_body("self.name()"),
+                ),
+        );
+
+    let report = old.semver_check(&new);
+    assert_eq!(
+        report.to_string(),
+        "\
+minor: added const `VERSION`
+minor: added fn `Named::greet`
+minor: added variant `Role::Guest`
+"
+    );
+    assert_eq!(report.bump(), Bump::Minor);
+    assert!(report.check(Bump::Minor).is_ok());
+}
+
+#[test]
+fn test_semver_generic_and_repeated_impls() {
+    let old = Module::new("api")
+        .with_struct(
+            Struct::new("Wrapper")
+                .with_is_pub(true)
+                .with_generic(Generic::new("T"))
+                .with_field(Field::new("inner", "T")),
+        )
+        .with_struct(Struct::new("User").with_is_pub(true))
+        .with_impl(
+            Impl::new("Wrapper")
+                .with_generic(Generic::new("T"))
+                .with_function(Function::new("get").with_is_pub(true)),
+        )
+        .with_impl(
+            Impl::new("Wrapper")
+                .with_generic(Generic::new("T"))
+                .with_impl_trait(Some(Trait::new("Clone")))
+                .with_function(Function::new("clone").with_body("todo!()")),
+        )
+        .with_impl(Impl::new("User").with_function(Function::new("check")))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_is_pub(true)));
+    let new = Module::new("api")
+        .with_struct(
+            Struct::new("Wrapper")
+                .with_is_pub(true)
+                .with_generic(Generic::new("T"))
+                .with_field(Field::new("inner", "T")),
+        )
+        .with_struct(Struct::new("User").with_is_pub(true))
+        .with_impl(Impl::new("User").with_function(Function::new("check")));
+    new.generate_and_verify();
+
+    let report = old.semver_check(&new);
+    assert_eq!(
+        report.to_string(),
+        "\
+major: removed impl `Wrapper<T>`
+major: removed impl `Clone for Wrapper<T>`
+major: removed impl `User`
+"
+    );
+
+    // The second `impl User` block is private, removing it is a patch
+    let old = Module::new("api")
+        .with_struct(Struct::new("User").with_is_pub(true))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_is_pub(true)))
+        .with_impl(Impl::new("User").with_function(Function::new("check")));
+    let new = Module::new("api")
+        .with_struct(Struct::new("User").with_is_pub(true))
+        .with_impl(Impl::new("User").with_function(Function::new("id").with_is_pub(true)));
+    let report = old.semver_check(&new);
+    assert_eq!(report.to_string(), "patch: removed impl `User`\n");
+}
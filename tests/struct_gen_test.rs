@@ -85,3 +85,81 @@ fn gen_with_doc() {
     println!("{}", &src_code);
     assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
 }
+
+#[test]
+fn derive_gen() {
+    let mut s = Struct::new("Derived")
+        .add_derive("Debug")
+        .add_attribute("#[serde(default)]")
+        .add_derives(vec!["Clone", "Debug", "PartialEq"])
+        .add_attribute("#[derive(Eq)]")
+        .add_field(Field::new("a", "u8"))
+        .to_owned();
+    assert!(s.has_derive("Clone"));
+    assert_eq!(s.derives(), vec!["Debug", "Clone", "PartialEq", "Eq"]);
+
+    s.remove_derive("Clone").remove_derive("Hash");
+    assert!(!s.has_derive("Clone"));
+    let expected = r#"
+        #[derive(Debug, PartialEq, Eq)]
+        #[serde(default)]
+        struct Derived
+        {
+            a: u8,
+        }
+        "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+
+    s.remove_derive("Debug")
+        .remove_derive("PartialEq")
+        .remove_derive("Eq");
+    assert!(s.derives().is_empty());
+    assert!(!s.generate().contains("derive"));
+}
+
+#[test]
+fn doc_normalisation_gen() {
+    let s = Struct::new("Doc")
+        .add_doc("A struct\nspanning lines.\n")
+        .add_doc("/// Already prefixed")
+        .add_doc("// note\n//// rule")
+        .add_doc("carriage\rreturn")
+        .add_doc_section("Safety", "Never.")
+        .add_field(
+            Field::new("a", "u8")
+                .add_doc_code(
+                    "// setup\nlet a = 1;\n\n/// no doc\nassert_eq!(a, 1);",
+                    Some("ignore"),
+                )
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        /// A struct
+        /// spanning lines.
+        /// Already prefixed
+        // note
+        //// rule
+        #[doc = " carriage\rreturn"]
+        ///
+        /// # Safety
+        ///
+        /// Never.
+        struct Doc
+        {
+            /// ```ignore
+            /// // setup
+            /// let a = 1;
+            ///
+            /// /// no doc
+            /// assert_eq!(a, 1);
+            /// ```
+            a: u8,
+        }
+        "#;
+    let src_code = s.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
@@ -0,0 +1,253 @@
+#![cfg(feature = "tokens")]
+
+use proc_macro2::{TokenStream, TokenTree};
+use proffer::*;
+use quote::{quote, ToTokens};
+
+/// The tokens of `tokens`, with groups flattened and regardless of their spacing,
+/// which differs between lexed and quoted punctuation, ie. `#!`.
+fn token_strings(tokens: TokenStream) -> Vec<String> {
+    let mut strings = vec![];
+    for token in tokens {
+        match token {
+            TokenTree::Group(group) => {
+                strings.push(format!("{:?}", group.delimiter()));
+                strings.extend(token_strings(group.stream()));
+                strings.push("end".to_string());
+            }
+            token => strings.push(token.to_string()),
+        }
+    }
+    strings
+}
+
+/// Assert the tokens of `$item` are those written out by hand in `$expected`.
+macro_rules! assert_tokens {
+    ($item:expr, { $($expected:tt)* }) => {
+        let item = $item;
+        assert_eq!(
+            token_strings(item.to_token_stream()),
+            token_strings(quote! { $($expected)* })
+        );
+    };
+}
+
+#[test]
+fn struct_tokens() {
+    let s = Struct::new("User")
+        .set_is_pub(true)
+        .add_attribute("#[derive(Clone)]")
+        .add_field(Field::new("id", "u64").set_is_pub(true).to_owned())
+        .to_owned();
+    let expected = quote! {
+        #[derive(Clone)]
+        pub struct User {
+            pub id: u64,
+        }
+    };
+    assert_eq!(expected.to_string(), quote! { #s }.to_string());
+}
+
+#[test]
+fn function_tokens() {
+    let f = Function::new("double")
+        .add_parameter(Parameter::new("x", "u32"))
+        .set_return_ty("u32")
+        .set_block(
+            Block::new()
+                .add_stmt(Stmt::expr(Expr::binary("x", "*", "2")))
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = quote! {
+        fn double(x: u32) -> u32 {
+            x * 2
+        }
+    };
+    assert_eq!(expected.to_string(), quote! { #f }.to_string());
+}
+
+#[test]
+fn module_tokens() {
+    let m = Module::new("gen")
+        .add_enum(
+            Enum::new("Color")
+                .add_variant(Variant::new("Red"))
+                .add_variant(Variant::new("Green"))
+                .to_owned(),
+        )
+        .add_impl(
+            Impl::new("Color")
+                .set_impl_trait(Some(Trait::new("Default")))
+                .add_function(
+                    Function::new("default")
+                        .set_return_ty("Self")
+                        .set_body("Color::Red")
+                        .to_owned(),
+                )
+                .to_owned(),
+        )
+        .add_trait(Trait::new("Paint"))
+        .to_owned();
+    let expected = quote! {
+        mod gen {
+            trait Paint {}
+            impl Default for Color {
+                fn default() -> Self {
+                    Color::Red
+                }
+            }
+            enum Color {
+                Red,
+                Green,
+            }
+        }
+    };
+    assert_eq!(expected.to_string(), quote! { #m }.to_string());
+}
+
+#[test]
+fn docs_become_doc_attributes() {
+    let c = Const::new("MAX", "usize", "10")
+        .add_doc("/// The maximum")
+        .to_owned();
+    let expected = quote! {
+        #[doc = " The maximum"]
+        const MAX: usize = 10;
+    };
+    assert_eq!(expected.to_string(), c.to_token_stream().to_string());
+}
+
+#[test]
+fn untokenizable_source_is_reported() {
+    let f = Function::new("broken").set_body("foo(").to_owned();
+    let err = f.try_to_tokens().unwrap_err();
+    assert!(err.src_code().contains("foo("));
+    assert!(err.to_string().starts_with("Failed to tokenize"));
+
+    let tokens = f.to_token_stream().to_string();
+    assert!(tokens.starts_with("compile_error !"));
+}
+
+#[test]
+fn generic_and_comment_tokens() {
+    let g = Generic::new("T")
+        .with_trait_bound("Clone")
+        .with_trait_bound("Send");
+    assert_eq!(
+        quote! { T: Clone + Send }.to_string(),
+        quote! { #g }.to_string()
+    );
+
+    let c = Comment::line("not part of the tokens");
+    assert!(c.try_to_tokens().unwrap().is_empty());
+}
+
+#[test]
+fn each_object_matches_quote() {
+    assert_tokens!(
+        AssociatedTypeDeclaration::new("Key").with_trait_bound("Hash"),
+        {
+            type Key: Hash;
+        }
+    );
+    assert_tokens!(AssociatedTypeDefinition::new("Key", "u64"), {
+        type Key = u64;
+    });
+    assert_tokens!(Attribute::from("#[inline]"), { #[inline] });
+    assert_tokens!(
+        Block::new()
+            .with_stmt(Stmt::let_("a", "1"))
+            .with_stmt(Stmt::expr("a")),
+        {
+            {
+                let a = 1;
+                a
+            }
+        }
+    );
+    assert_tokens!(Cfg::not(Cfg::feature("std")), { not(feature = "std") });
+    assert_tokens!(Enum::new("Unit").with_variant(Variant::new("A")), {
+        enum Unit {
+            A,
+        }
+    });
+    assert_tokens!(Expr::binary("a", "+", "b"), { a + b });
+    assert_tokens!(
+        ExternBlock::new("C").with_function(FunctionSignature::new("abort")),
+        {
+            extern "C" {
+                fn abort() -> ();
+            }
+        }
+    );
+    assert_tokens!(Field::new("id", "u64"), { id: u64, });
+    assert_tokens!(
+        FileHeader::new("gen").with_attribute("#![allow(dead_code)]"),
+        {
+            #![allow(dead_code)]
+        }
+    );
+    assert_tokens!(ForeignStatic::new("errno", "i32"), {
+        static errno: i32;
+    });
+    assert_tokens!(ForeignType::new("Handle"), {
+        type Handle;
+    });
+    assert_tokens!(
+        FunctionBody::default().with_attribute("#![allow(unused)]"),
+        {
+            #![allow(unused)]
+        }
+    );
+    assert_tokens!(FunctionSignature::new("len").with_return_ty(Some("usize")), {
+        fn len() -> usize
+    });
+    assert_tokens!(Impl::new("Unit"), {
+        impl Unit {}
+    });
+    assert_tokens!(
+        MacroInvocation::new("thread_local").with_tokens("static A: u8 = 0;"),
+        {
+            thread_local! { static A: u8 = 0; }
+        }
+    );
+    assert_tokens!(MacroRule::new("$a:expr", "$a"), { ($a:expr) => { $a }; });
+    assert_tokens!(
+        MacroRules::new("id").with_rule(MacroRule::new("$a:expr", "$a")),
+        {
+            macro_rules! id {
+                ($a:expr) => {
+                    $a
+                };
+            }
+        }
+    );
+    assert_tokens!(MatchArm::new("Some(a)", "a"), { Some(a) => a, });
+    assert_tokens!(Meta::from("test"), { test });
+    assert_tokens!(
+        MetaList::new("derive").with_paths(vec!["Debug", "Clone"]),
+        { derive(Debug, Clone) }
+    );
+    assert_tokens!(Parameter::new("a", "&str"), { a: &str });
+    assert_tokens!(Static::new("COUNT", "u32", "0"), {
+        static COUNT: u32 = 0;
+    });
+    assert_tokens!(Stmt::semi(Expr::call("run", vec![])), {
+        run();
+    });
+    assert_tokens!(Trait::new("Named"), {
+        trait Named {}
+    });
+    assert_tokens!(TypeAlias::new("Id", "u64"), {
+        type Id = u64;
+    });
+    assert_tokens!(Union::new("Bits").with_field(Field::new("int", "u32")), {
+        union Bits {
+            int: u32,
+        }
+    });
+    assert_tokens!(Variant::new("Pair").with_inner(Some("(u8, u8)")), {
+        Pair(u8, u8)
+    });
+}
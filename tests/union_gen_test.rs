@@ -0,0 +1,70 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::*;
+
+#[test]
+fn basic_gen() {
+    let u = Union::new("Value")
+        .set_is_pub(true)
+        .add_attribute("#[repr(C)]")
+        .add_doc("/// A C compatible value")
+        .add_field(Field::new("int", "i64").set_is_pub(true).to_owned())
+        .add_field(Field::new("float", "f64").set_is_pub(true).to_owned())
+        .to_owned();
+    let expected = r#"
+        /// A C compatible value
+        #[repr(C)]
+        pub union Value
+        {
+            pub int: i64,
+            pub float: f64,
+        }
+    "#;
+    let src_code = u.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn generic_gen() {
+    let u = Union::new("Slot")
+        .add_generic(Generic::new("T").add_trait_bound("Copy").to_owned())
+        .add_fields(&[Field::new("value", "T"), Field::new("raw", "usize")])
+        .to_owned();
+    let expected = r#"
+        union Slot<T>
+            where
+                T: Copy,
+        {
+            value: T,
+            raw: usize,
+        }
+    "#;
+    let src_code = u.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
+
+#[test]
+fn union_in_module() {
+    let m = Module::new("ffi")
+        .add_union(
+            Union::new("Value")
+                .add_field(Field::new("int", "i64"))
+                .to_owned(),
+        )
+        .to_owned();
+    let expected = r#"
+        mod ffi
+        {
+            union Value
+            {
+                int: i64,
+            }
+        }
+    "#;
+    let src_code = m.generate_and_verify();
+    println!("{}", &src_code);
+    assert_eq!(norm_whitespace(expected), norm_whitespace(&src_code));
+}
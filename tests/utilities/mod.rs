@@ -39,3 +39,43 @@ impl Verify for Enum {
 impl Verify for Function {
     type ExpectedType = ItemFn;
 }
+
+impl Verify for Const {
+    type ExpectedType = ItemConst;
+}
+
+impl Verify for Static {
+    type ExpectedType = ItemStatic;
+}
+
+impl Verify for TypeAlias {
+    type ExpectedType = ItemType;
+}
+
+impl Verify for Union {
+    type ExpectedType = ItemUnion;
+}
+
+impl Verify for MacroRules {
+    type ExpectedType = ItemMacro;
+}
+
+impl Verify for MacroInvocation {
+    type ExpectedType = ItemMacro;
+}
+
+impl Verify for ExternBlock {
+    type ExpectedType = ItemForeignMod;
+}
+
+impl Verify for proffer::Block {
+    type ExpectedType = syn::Block;
+}
+
+impl Verify for proffer::Expr {
+    type ExpectedType = syn::Expr;
+}
+
+impl Verify for proffer::Stmt {
+    type ExpectedType = syn::Stmt;
+}
@@ -0,0 +1,196 @@
+pub mod utilities;
+use crate::utilities::Verify;
+
+use proffer::visit::{self, Visit, VisitMut};
+use proffer::*;
+
+fn module() -> Module {
+    Module::new("api")
+        .add_struct(
+            Struct::new("User")
+                .add_generic(Generic::new("T"))
+                .add_field(Field::new("id", "u64").add_doc("The id").to_owned())
+                .add_field(Field::new("tag", "T"))
+                .add_doc("A user")
+                .to_owned(),
+        )
+        .add_enum(
+            Enum::new("Role")
+                .add_variant(Variant::new("Admin").add_doc("Can do anything").to_owned())
+                .to_owned(),
+        )
+        .add_function(
+            Function::new("login")
+                .add_parameter(Parameter::new("user", "&User<u8>"))
+                .to_owned(),
+        )
+        .add_submodule(
+            Module::new("b")
+                .add_struct(
+                    Struct::new("Group")
+                        .add_field(Field::new("id", "u64"))
+                        .to_owned(),
+                )
+                .to_owned(),
+        )
+        .add_submodule(Module::new("a").add_struct(Struct::new("Team")).to_owned())
+        .to_owned()
+}
+
+#[derive(Default)]
+struct Collect(Vec<String>);
+
+impl Visit for Collect {
+    fn visit_module(&mut self, node: &Module) {
+        self.0.push(format!("mod {}", node.name()));
+        visit::visit_module(self, node);
+    }
+    fn visit_struct(&mut self, node: &Struct) {
+        self.0.push(format!("struct {}", node.name()));
+        visit::visit_struct(self, node);
+    }
+    fn visit_variant(&mut self, node: &Variant) {
+        self.0.push(format!("variant {}", node.name()));
+    }
+    fn visit_field(&mut self, node: &Field) {
+        self.0.push(format!("field {}", node.name()));
+    }
+    fn visit_parameter(&mut self, node: &Parameter) {
+        self.0.push(format!("param {}", node.name()));
+    }
+    fn visit_generic(&mut self, node: &Generic) {
+        self.0.push(format!("generic {}", node.name()));
+    }
+}
+
+#[test]
+fn visit_in_render_order() {
+    let mut collect = Collect::default();
+    collect.visit_module(&module());
+    assert_eq!(
+        collect.0,
+        vec![
+            "mod api",
+            "param user",
+            "struct User",
+            "generic T",
+            "field id",
+            "field tag",
+            "variant Admin",
+            "mod a",
+            "struct Team",
+            "mod b",
+            "struct Group",
+            "field id",
+        ]
+    );
+}
+
+struct Transform;
+
+impl VisitMut for Transform {
+    fn visit_module_mut(&mut self, node: &mut Module) {
+        node.clear_docs();
+        visit::visit_module_mut(self, node);
+    }
+    fn visit_struct_mut(&mut self, node: &mut Struct) {
+        let name = format!("Api{}", node.name());
+        node.set_name(name).add_derive("Debug").clear_docs();
+        visit::visit_struct_mut(self, node);
+    }
+    fn visit_field_mut(&mut self, node: &mut Field) {
+        node.clear_docs();
+    }
+    fn visit_variant_mut(&mut self, node: &mut Variant) {
+        node.clear_docs();
+    }
+}
+
+#[test]
+fn visit_mut_transforms_in_place() {
+    let mut m = module();
+    Transform.visit_module_mut(&mut m);
+
+    let src_code = m.generate_and_verify();
+    assert!(!src_code.contains("///"));
+    assert!(src_code.contains("#[derive(Debug)]"));
+    assert!(m.get_struct("ApiUser").is_some());
+    assert!(m.get_struct("User").is_none());
+    assert!(m
+        .get_submodule("a")
+        .and_then(|a| a.get_struct("ApiTeam"))
+        .is_some());
+    assert!(m
+        .get_submodule("b")
+        .and_then(|b| b.get_struct("ApiGroup"))
+        .is_some());
+}
+
+struct Flatten(Vec<String>);
+
+impl VisitMut for Flatten {
+    fn visit_module_mut(&mut self, node: &mut Module) {
+        if node.name() != "root" {
+            node.set_name("merged");
+        }
+        visit::visit_module_mut(self, node);
+    }
+    fn merge_submodules_mut(&mut self, first: &mut Module, other: Module) {
+        self.0.push(other.name().to_string());
+        visit::merge_submodules_mut(self, first, other);
+    }
+}
+
+#[test]
+fn visit_mut_merges_colliding_submodules() {
+    let mut m = Module::new("root")
+        .with_submodule(Module::new("a").with_struct(Struct::new("A")))
+        .with_submodule(Module::new("b").with_struct(Struct::new("B")));
+    let mut flatten = Flatten(vec![]);
+    flatten.visit_module_mut(&mut m);
+
+    assert_eq!(flatten.0, vec!["merged"]);
+    assert_eq!(m.submodules().len(), 1);
+    let merged = m.get_submodule("merged").unwrap();
+    assert!(merged.get_struct("A").is_some());
+    assert!(merged.get_struct("B").is_some());
+}
+
+struct StripAttributes(usize);
+
+impl VisitMut for StripAttributes {
+    fn visit_associated_type_declaration_mut(&mut self, node: &mut AssociatedTypeDeclaration) {
+        self.0 += 1;
+        visit::visit_associated_type_declaration_mut(self, node);
+    }
+    fn visit_associated_type_definition_mut(&mut self, node: &mut AssociatedTypeDefinition) {
+        self.0 += 1;
+        visit::visit_associated_type_definition_mut(self, node);
+    }
+    fn visit_attribute_mut(&mut self, node: &mut Attribute) {
+        *node = Attribute::from("#[allow(unused)]");
+    }
+}
+
+#[test]
+fn visit_mut_associated_types() {
+    let mut m = Module::new("api")
+        .with_trait(Trait::new("Store").with_associated_type(
+            AssociatedTypeDeclaration::new("Key").with_attribute("#[cfg(unix)]"),
+        ))
+        .with_struct(Struct::new("Db"))
+        .with_impl(
+            Impl::new("Db")
+                .with_impl_trait(Some(Trait::new("Store")))
+                .with_associated_type(
+                    AssociatedTypeDefinition::new("Key", "u64").with_attribute("#[cfg(unix)]"),
+                ),
+        );
+    let mut strip = StripAttributes(0);
+    strip.visit_module_mut(&mut m);
+    assert_eq!(strip.0, 2);
+
+    let src_code = m.generate_and_verify();
+    assert!(!src_code.contains("cfg(unix)"));
+    assert_eq!(src_code.matches("#[allow(unused)]").count(), 2);
+}